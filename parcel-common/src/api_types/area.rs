@@ -3,6 +3,7 @@ use diesel::{
     backend::Backend, deserialize::FromSql, serialize::ToSql, sql_types::Integer, AsExpression,
     FromSqlRow,
 };
+use parcel_game_data::Area;
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
 #[derive(
@@ -20,6 +21,30 @@ pub enum AreaHash {
     WesternRegion = 21299,
 }
 
+impl TryFrom<Area> for AreaHash {
+    type Error = anyhow::Error;
+
+    /// Fails if the area isn't one of the regions that players can share objects in.
+    fn try_from(area: Area) -> Result<Self, Self::Error> {
+        match area {
+            Area::Area01 => Ok(AreaHash::EasternRegion),
+            Area::Area02 => Ok(AreaHash::CentralRegion),
+            Area::Area04 => Ok(AreaHash::WesternRegion),
+            other => anyhow::bail!("{:?} is not a shared region", other),
+        }
+    }
+}
+
+impl From<AreaHash> for Area {
+    fn from(area_hash: AreaHash) -> Self {
+        match area_hash {
+            AreaHash::EasternRegion => Area::Area01,
+            AreaHash::CentralRegion => Area::Area02,
+            AreaHash::WesternRegion => Area::Area04,
+        }
+    }
+}
+
 #[cfg(feature = "diesel")]
 impl<DB> ToSql<Integer, DB> for AreaHash
 where
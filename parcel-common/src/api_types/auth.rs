@@ -49,6 +49,9 @@ pub enum Provider {
     Steam = 0,
     #[serde(rename = "epic")]
     Epic = 1,
+    /// An account that was authenticated by the server itself without contacting Steam or Epic
+    #[serde(rename = "local")]
+    Local = 2,
 }
 
 impl Display for Provider {
@@ -56,6 +59,7 @@ impl Display for Provider {
         match self {
             Provider::Steam => write!(f, "Steam"),
             Provider::Epic => write!(f, "Epic"),
+            Provider::Local => write!(f, "Local"),
         }
     }
 }
@@ -73,6 +77,7 @@ where
         match self {
             Provider::Steam => 0.to_sql(out),
             Provider::Epic => 1.to_sql(out),
+            Provider::Local => 2.to_sql(out),
         }
     }
 }
@@ -87,6 +92,7 @@ where
         match i32::from_sql(bytes)? {
             0 => Ok(Provider::Steam),
             1 => Ok(Provider::Epic),
+            2 => Ok(Provider::Local),
             other => Err(format!("Unknown Provider variant: {}", other).into()),
         }
     }
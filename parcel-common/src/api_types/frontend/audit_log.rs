@@ -0,0 +1,132 @@
+#[cfg(feature = "diesel")]
+use diesel::{
+    backend::Backend, deserialize::FromSql, serialize::ToSql, sql_types::Integer, AsExpression,
+    FromSqlRow,
+};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ts")]
+use typescript_type_def::TypeDef;
+
+use super::accounts::GameAccountSummary;
+
+/// An administrative or destructive action that is recorded in the audit log.
+#[repr(i32)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "diesel", derive(FromSqlRow, AsExpression))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = Integer))]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub enum AuditAction {
+    SetAccountPermissions = 0,
+    CreateCredentials = 1,
+    ResetPassword = 2,
+    CreateFrontendAccount = 3,
+    SetServerSettings = 4,
+    SetWhitelist = 5,
+    /// A player deleted an object in game
+    DeleteObject = 6,
+    CreateBan = 7,
+    LiftBan = 8,
+    /// The target is the game account that was kicked, or empty if every session was ended
+    KickGameSessions = 9,
+    SetMissionProgressState = 10,
+    ResetMission = 11,
+    DeleteMission = 12,
+    SetObjectDeleted = 13,
+    SetObjectCreator = 14,
+    /// The target is the game account that created the objects
+    SetObjectsDeletedByCreator = 15,
+    /// The retention rules were applied, the value after is the number of removed rows of each kind
+    ApplyRetentionRules = 16,
+}
+
+#[cfg(feature = "diesel")]
+impl<DB> ToSql<Integer, DB> for AuditAction
+where
+    DB: Backend,
+    i32: ToSql<Integer, DB>,
+{
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, DB>,
+    ) -> diesel::serialize::Result {
+        match self {
+            Self::SetAccountPermissions => 0.to_sql(out),
+            Self::CreateCredentials => 1.to_sql(out),
+            Self::ResetPassword => 2.to_sql(out),
+            Self::CreateFrontendAccount => 3.to_sql(out),
+            Self::SetServerSettings => 4.to_sql(out),
+            Self::SetWhitelist => 5.to_sql(out),
+            Self::DeleteObject => 6.to_sql(out),
+            Self::CreateBan => 7.to_sql(out),
+            Self::LiftBan => 8.to_sql(out),
+            Self::KickGameSessions => 9.to_sql(out),
+            Self::SetMissionProgressState => 10.to_sql(out),
+            Self::ResetMission => 11.to_sql(out),
+            Self::DeleteMission => 12.to_sql(out),
+            Self::SetObjectDeleted => 13.to_sql(out),
+            Self::SetObjectCreator => 14.to_sql(out),
+            Self::SetObjectsDeletedByCreator => 15.to_sql(out),
+            Self::ApplyRetentionRules => 16.to_sql(out),
+        }
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl<DB> FromSql<Integer, DB> for AuditAction
+where
+    DB: Backend,
+    i32: FromSql<Integer, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        match i32::from_sql(bytes)? {
+            0 => Ok(Self::SetAccountPermissions),
+            1 => Ok(Self::CreateCredentials),
+            2 => Ok(Self::ResetPassword),
+            3 => Ok(Self::CreateFrontendAccount),
+            4 => Ok(Self::SetServerSettings),
+            5 => Ok(Self::SetWhitelist),
+            6 => Ok(Self::DeleteObject),
+            7 => Ok(Self::CreateBan),
+            8 => Ok(Self::LiftBan),
+            9 => Ok(Self::KickGameSessions),
+            10 => Ok(Self::SetMissionProgressState),
+            11 => Ok(Self::ResetMission),
+            12 => Ok(Self::DeleteMission),
+            13 => Ok(Self::SetObjectDeleted),
+            14 => Ok(Self::SetObjectCreator),
+            15 => Ok(Self::SetObjectsDeletedByCreator),
+            16 => Ok(Self::ApplyRetentionRules),
+            other => Err(format!("Unknown AuditAction variant: {}", other).into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogEntry {
+    pub id: i64,
+    pub created_at: String,
+    /// Set if the action was made through the frontend
+    pub frontend_actor_id: Option<i64>,
+    /// Set if the action was made by a player in game
+    pub game_actor: Option<GameAccountSummary>,
+    pub action: AuditAction,
+    /// The id of the account, object, etc that was changed, if the action has one
+    pub target_id: Option<String>,
+    /// The value before the change as JSON, if it's known
+    pub before: Option<String>,
+    /// The value after the change as JSON, if it's known
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct ListAuditLogResponse {
+    pub entries: Vec<AuditLogEntry>,
+    /// The total number of entries that match the filter
+    pub total: i64,
+}
@@ -27,7 +27,7 @@ pub struct LocalAuthRequest {
     pub password: String,
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "ts", derive(TypeDef))]
 #[serde(rename_all = "camelCase")]
 pub struct AuthAccountInfo {
@@ -53,10 +53,12 @@ flags! {
     pub enum FrontendPermissions: i64 {
         ManageAccounts = 1 << 0,
         ManageServerSettings = 1 << 1,
+        ManageObjects = 1 << 2,
+        ManageMissions = 1 << 3,
     }
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "ts", derive(TypeDef))]
 #[serde(rename_all = "camelCase", tag = "type")]
 pub enum CheckAuthResponse {
@@ -26,9 +26,22 @@ pub struct LostCargoListItem {
     pub category: String,
     pub location: String,
     pub end_location: String,
+    /// The delivery point that the game data says the cargo belongs to.
+    /// None if the cargo isn't in the game's lost cargo table.
+    pub expected_destination: Option<String>,
     pub creator: String,
 }
 
+/// Lost cargo that the game expects to be delivered to a delivery point, but isn't in the world.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct MissingLostCargoListItem {
+    pub name: String,
+    pub category: String,
+    pub destination: String,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[cfg_attr(feature = "ts", derive(TypeDef))]
 #[serde(rename_all = "camelCase")]
@@ -70,6 +83,8 @@ pub struct ListSharedCargoResponse {
 #[serde(rename_all = "camelCase")]
 pub struct ListLostCargoResponse {
     pub baggages: Vec<LostCargoListItem>,
+    /// Empty if the game data doesn't include the lost cargo table
+    pub missing: Vec<MissingLostCargoListItem>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ts")]
+use typescript_type_def::TypeDef;
+
+use crate::api_types::auth::Provider;
+
+use super::accounts::GameAccountSummary;
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct BanListItem {
+    pub id: i64,
+    /// The banned game account, if the ban was issued for an account or the provider id belongs to one
+    pub account: Option<GameAccountSummary>,
+    pub provider: Provider,
+    pub provider_id: String,
+    pub reason: String,
+    /// The id of the frontend account that issued the ban
+    pub issued_by: Option<i64>,
+    pub created_at: String,
+    /// Not set for permanent bans
+    pub expires_at: Option<String>,
+    pub lifted_at: Option<String>,
+    pub lifted_by: Option<i64>,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum BanTarget {
+    #[serde(rename_all = "camelCase")]
+    Account { account_id: String },
+    #[serde(rename_all = "camelCase")]
+    Provider {
+        provider: Provider,
+        provider_id: String,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBanRequest {
+    pub target: BanTarget,
+    pub reason: String,
+    /// The date the ban expires in RFC 3339 format, or null for a permanent ban
+    pub expires_at: Option<String>,
+}
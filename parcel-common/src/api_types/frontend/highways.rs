@@ -0,0 +1,43 @@
+use parcel_game_data::Area;
+use serde::Serialize;
+
+#[cfg(feature = "ts")]
+use typescript_type_def::TypeDef;
+
+use super::accounts::GameAccountSummary;
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct HighwayConstruction {
+    /// The id of the highway segment
+    pub construction_id: i32,
+    /// Null if the segment isn't in the game data
+    pub name: Option<String>,
+    pub area: Option<Area>,
+    pub resources: Vec<HighwayResourceProgress>,
+    /// The accounts that devoted the most resources, sorted by the number of devoted resources
+    pub top_contributors: Vec<HighwayContributor>,
+    /// Null if the resources required to finish the segment are unknown
+    pub completion_percentage: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct HighwayResourceProgress {
+    pub resource_id: i16,
+    pub name: Option<String>,
+    pub num_resources: i64,
+    /// Null if the resource isn't required or the requirements are unknown
+    pub required_resources: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct HighwayContributor {
+    pub account: GameAccountSummary,
+    /// The total number of resources of every type the account devoted to the segment
+    pub num_resources: i64,
+}
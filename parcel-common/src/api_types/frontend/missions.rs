@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ts")]
+use typescript_type_def::TypeDef;
+
+use crate::api_types::mission::{MissionType, OnlineMissionType, ProgressState};
+
+use super::accounts::GameAccountSummary;
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct MissionDetails {
+    pub id: String,
+    pub creator: GameAccountSummary,
+    pub worker: Option<GameAccountSummary>,
+    pub qpid_id: i32,
+    pub qpid_start_location: i32,
+    pub qpid_end_location: i32,
+    pub qpid_delivered_location: Option<i32>,
+    pub mission_static_id: i64,
+    /// Null if the mission isn't defined in the game data
+    pub mission_name: Option<String>,
+    pub mission_type: MissionType,
+    pub online_mission_type: OnlineMissionType,
+    pub progress_state: ProgressState,
+    pub registered_time: String,
+    pub expiration_time: String,
+    pub supply_info: Option<MissionSupplyInfo>,
+    pub dynamic_start_info: Option<MissionDynamicLocationInfo>,
+    pub dynamic_end_info: Option<MissionDynamicLocationInfo>,
+    pub dynamic_delivered_info: Option<MissionDynamicLocationInfo>,
+    pub dynamic_mission_info: Option<MissionDynamicMissionInfo>,
+    pub catapult_shell_info: Option<MissionCatapultShellInfo>,
+    pub baggages: Vec<MissionBaggage>,
+    /// The accounts that have interacted with the mission
+    pub relations: Vec<GameAccountSummary>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct MissionSupplyInfo {
+    pub item_hash: i64,
+    pub item_name: Option<String>,
+    pub amount: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct MissionDynamicLocationInfo {
+    pub location_id: String,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct MissionDynamicMissionInfo {
+    pub client_name_hash: i32,
+    pub reward_name_hash: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct MissionCatapultShellInfo {
+    pub local_id: i32,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct MissionBaggage {
+    pub id: i64,
+    pub name_hash: i32,
+    pub name: Option<String>,
+    pub amount: i32,
+    pub user_index: i32,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub is_returned: bool,
+    pub ammo_info: Option<MissionBaggageAmmoInfo>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct MissionBaggageAmmoInfo {
+    pub ammo_id: String,
+    pub clip_count: i16,
+    pub count: i16,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct SetMissionProgressStateRequest {
+    pub progress_state: ProgressState,
+    /// If true the current worker is removed from the mission.
+    #[serde(default)]
+    pub clear_worker: bool,
+}
@@ -1,5 +1,12 @@
 pub mod accounts;
+pub mod audit_log;
 pub mod auth;
 pub mod baggages;
+pub mod bans;
+pub mod highways;
+pub mod missions;
 pub mod qpid_objects;
+pub mod rankings;
+pub mod sessions;
 pub mod settings;
+pub mod statistics;
@@ -19,10 +19,141 @@ pub struct QpidObject {
     /// Only applicable for vehicles. If true then the vehicle is not in a garage (i think).
     /// For all other object types this is always true.
     pub is_lost: bool,
+    pub is_deleted: bool,
     pub creator: GameAccountSummary,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct QpidObjectDetails {
+    pub object: QpidObject,
+    pub sub_type: String,
+    pub likes: i64,
+    pub updated_time: String,
+    pub tags: Vec<String>,
+    pub comment: Option<QpidObjectComment>,
+    pub construction_materials: Vec<QpidObjectMaterialsContribution>,
+    pub recycle_materials: Vec<QpidObjectMaterialsContribution>,
+    pub rope_info: Option<QpidObjectRopeInfo>,
+    pub stone_info: Option<QpidObjectStoneInfo>,
+    pub bridge_info: Option<QpidObjectBridgeInfo>,
+    pub parking_info: Option<QpidObjectParkingInfo>,
+    pub vehicle_info: Option<QpidObjectVehicleInfo>,
+    pub extra_info: Option<QpidObjectExtraInfo>,
+    pub customize_info: Option<QpidObjectCustomizeInfo>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct QpidObjectComment {
+    pub writer: String,
+    pub likes: i64,
+    pub parent_index: i16,
+    pub is_deleted: bool,
+    pub reference_object: String,
+    /// The ids of the phrases in the order they're displayed
+    pub phrases: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct QpidObjectMaterialsContribution {
+    /// None if the materials were contributed by the creator when the object was built
+    pub contributor: Option<GameAccountSummary>,
+    pub materials: Vec<i32>,
+    /// Only set for construction materials
+    pub repair_materials: Option<Vec<i32>>,
+    pub time: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct QpidObjectRopeInfo {
+    pub pitch: i32,
+    pub heading: i32,
+    pub length: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct QpidObjectStoneInfo {
+    pub resting_count: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct QpidObjectBridgeInfo {
+    pub angle: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct QpidObjectParkingInfo {
+    pub location_id: i32,
+    pub dynamic_location_id: String,
+    pub current_qpid_id: i32,
+    pub is_parking: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct QpidObjectVehicleInfo {
+    pub location_id: i32,
+    pub dynamic_location_id: String,
+    pub current_qpid_id: i32,
+    pub is_parking: bool,
+    pub is_lost: bool,
+    pub is_race: bool,
+    pub customize_type: i32,
+    pub customize_color: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct QpidObjectExtraInfo {
+    pub alternative_qpid_id: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct QpidObjectCustomizeInfo {
+    pub customize_param: i32,
+    pub customize_color: i32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct SetObjectDeletedRequest {
+    pub is_deleted: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct SetObjectCreatorRequest {
+    /// The game account id of the new creator
+    pub account_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct SetObjectsDeletedResponse {
+    pub num_affected_objects: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(TypeDef))]
 #[serde(rename_all = "camelCase")]
 pub enum QpidObjectType {
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ts")]
+use typescript_type_def::TypeDef;
+
+use crate::api_types::rank::RankingCategory;
+
+use super::accounts::GameAccountSummary;
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct RankingScheduleListItem {
+    pub id: i32,
+    pub category: RankingCategory,
+    pub start_date: String,
+    pub end_date: String,
+    /// The date the rewards were computed, if the schedule has ended
+    pub closed_at: Option<String>,
+    pub num_participants: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRankingScheduleRequest {
+    pub category: RankingCategory,
+    /// The start date in RFC 3339 format
+    pub start_date: String,
+    /// The end date in RFC 3339 format
+    pub end_date: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct RankingRecordListItem {
+    pub account: GameAccountSummary,
+    pub score: i64,
+    pub try_count: i32,
+    pub clear_count: i32,
+    /// Only set once the schedule has been closed
+    pub rank: Option<i32>,
+    /// Only set once the schedule has been closed
+    pub reward_tier: Option<i16>,
+}
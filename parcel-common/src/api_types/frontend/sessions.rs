@@ -0,0 +1,27 @@
+use serde::Serialize;
+
+#[cfg(feature = "ts")]
+use typescript_type_def::TypeDef;
+
+use crate::api_types::auth::Provider;
+
+use super::accounts::GameAccountSummary;
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct GameSessionListItem {
+    pub account: GameAccountSummary,
+    pub provider: Provider,
+    pub provider_id: String,
+    pub logged_in_at: String,
+    /// The last time the player made a request, accurate to about a minute
+    pub last_seen_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct KickSessionsResponse {
+    pub num_kicked: usize,
+}
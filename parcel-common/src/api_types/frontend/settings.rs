@@ -15,6 +15,9 @@ pub struct SettingsValues {
     /// frontend. Otherwise an admin must first create a frontend account
     /// for the user.
     pub allow_frontend_login: bool,
+    /// Rules for automatically removing old content from the world.
+    #[serde(default)]
+    pub retention: RetentionSettings,
 }
 
 // This could be implemented automatically with macro trait at the moment,
@@ -24,10 +27,60 @@ impl Default for SettingsValues {
         Self {
             public_server: false,
             allow_frontend_login: false,
+            retention: RetentionSettings::default(),
         }
     }
 }
 
+/// Rules that are periodically applied to keep the world from filling up.
+///
+/// Every rule is disabled when its value is null.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionSettings {
+    /// If false, the rules are never applied automatically.
+    pub enabled: bool,
+    /// Delete objects without any likes that haven't been updated in this many days.
+    pub delete_unliked_objects_after_days: Option<u32>,
+    /// Delete delivered missions this many days after they were registered.
+    pub delete_delivered_missions_after_days: Option<u32>,
+    /// Delete lost cargo that nobody has picked up this many days after it was registered.
+    pub delete_lost_cargo_after_days: Option<u32>,
+    /// Delete wasted baggages (broken or used items) after this many days.
+    pub delete_wasted_baggages_after_days: Option<u32>,
+    /// Maximum number of objects in each qpid area. The least liked objects, and then the oldest ones,
+    /// are deleted first.
+    pub max_objects_per_qpid_area: Option<u32>,
+    /// Delete audit log entries after this many days.
+    pub delete_audit_log_after_days: Option<u32>,
+}
+
+/// The number of things that are, or would be, removed by the retention rules.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionReport {
+    pub unliked_objects: usize,
+    pub objects_over_area_limit: usize,
+    pub delivered_missions: usize,
+    pub lost_cargo: usize,
+    pub wasted_baggages: usize,
+    pub audit_log_entries: usize,
+}
+
+impl RetentionReport {
+    /// Returns true if nothing is removed.
+    pub fn is_empty(&self) -> bool {
+        self.unliked_objects == 0
+            && self.objects_over_area_limit == 0
+            && self.delivered_missions == 0
+            && self.lost_cargo == 0
+            && self.wasted_baggages == 0
+            && self.audit_log_entries == 0
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(TypeDef))]
 #[serde(rename_all = "camelCase")]
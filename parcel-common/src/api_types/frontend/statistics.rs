@@ -0,0 +1,74 @@
+use serde::Serialize;
+
+#[cfg(feature = "ts")]
+use typescript_type_def::TypeDef;
+
+use super::qpid_objects::QpidObjectType;
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct Statistics {
+    /// Objects that haven't been deleted, by type and area
+    pub objects: Vec<ObjectCountStatistic>,
+    pub likes_per_day: Vec<LikesPerDayStatistic>,
+    pub devoted_resources: Vec<DevotedResourcesStatistic>,
+    pub missions: MissionStatistics,
+    /// Lifetime totals from the player profiles, which are not affected by the date range.
+    /// Null if the account doesn't have a profile yet.
+    pub profile: Option<ProfileStatistics>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectCountStatistic {
+    pub object_type: QpidObjectType,
+    /// 0 = eastern region, 1 = central region, 2 = western region
+    pub area_id: i32,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct LikesPerDayStatistic {
+    /// The date in YYYY-MM-DD format (UTC)
+    pub date: String,
+    /// For server wide statistics every like counts as both given and received
+    pub likes_given: i64,
+    pub likes_received: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct DevotedResourcesStatistic {
+    /// The id of the highway segment
+    pub construction_id: i32,
+    pub resource_id: i16,
+    pub num_resources: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct MissionStatistics {
+    pub created: i64,
+    /// Missions that were delivered by the account, or by anyone for server wide statistics
+    pub completed: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileStatistics {
+    pub delivered_baggage: i64,
+    pub delivered_weight: i64,
+    pub distance_traveled: i64,
+    pub num_likes_received_npc: i64,
+    pub num_likes_received_online: i64,
+    pub legend_count: i64,
+    pub super_legend_count: i64,
+    pub ss_legend_count: i64,
+}
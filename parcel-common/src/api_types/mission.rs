@@ -5,11 +5,15 @@ use diesel::{
 };
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "ts")]
+use typescript_type_def::TypeDef;
+
 use crate::serde_util::deserialize_bool_from_number;
 
 use super::area::AreaHash;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
 #[cfg_attr(feature = "diesel", derive(FromSqlRow, AsExpression))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = Integer))]
 #[repr(i32)]
@@ -69,6 +73,7 @@ where
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
 #[cfg_attr(feature = "diesel", derive(FromSqlRow, AsExpression))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = Integer))]
 #[repr(i32)]
@@ -128,6 +133,7 @@ where
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
 #[cfg_attr(feature = "diesel", derive(FromSqlRow, AsExpression))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = Integer))]
 #[repr(i32)]
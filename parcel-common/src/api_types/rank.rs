@@ -1,5 +1,13 @@
+#[cfg(feature = "diesel")]
+use diesel::{
+    backend::Backend, deserialize::FromSql, serialize::ToSql, sql_types::Integer, AsExpression,
+    FromSqlRow,
+};
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "ts")]
+use typescript_type_def::TypeDef;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RankingRecord {
     #[serde(rename = "c")]
@@ -23,3 +31,55 @@ pub struct RankingRecord {
     #[serde(rename = "sid")]
     pub season_id: i32,
 }
+
+/// The statistic that players are ranked by in a ranking schedule.
+///
+/// This is a server side concept, the game only sees the category as an integer in `RankingRecord`.
+#[repr(i32)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "diesel", derive(FromSqlRow, AsExpression))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = Integer))]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub enum RankingCategory {
+    /// Number of baggages delivered by completing missions
+    Deliveries = 0,
+    /// Number of likes received from other players
+    Likes = 1,
+    /// Number of resources devoted to highway construction
+    HighwayResources = 2,
+}
+
+#[cfg(feature = "diesel")]
+impl<DB> ToSql<Integer, DB> for RankingCategory
+where
+    DB: Backend,
+    i32: ToSql<Integer, DB>,
+{
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, DB>,
+    ) -> diesel::serialize::Result {
+        match self {
+            Self::Deliveries => 0.to_sql(out),
+            Self::Likes => 1.to_sql(out),
+            Self::HighwayResources => 2.to_sql(out),
+        }
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl<DB> FromSql<Integer, DB> for RankingCategory
+where
+    DB: Backend,
+    i32: FromSql<Integer, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        match i32::from_sql(bytes)? {
+            0 => Ok(Self::Deliveries),
+            1 => Ok(Self::Likes),
+            2 => Ok(Self::HighwayResources),
+            other => Err(format!("Unknown RankingCategory variant: {}", other).into()),
+        }
+    }
+}
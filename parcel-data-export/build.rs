@@ -0,0 +1,36 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    path::Path,
+};
+
+/// The directory with the code that decodes .core files, the cached files need to be decoded again when it changes.
+const READERS_DIR: &str = "src/readers";
+
+fn main() {
+    println!("cargo:rerun-if-changed={READERS_DIR}");
+    println!("cargo:rustc-env=READERS_HASH={:016x}", hash_readers());
+}
+
+/// Hashes the name and contents of every file in the readers directory.
+fn hash_readers() -> u64 {
+    let mut file_paths = std::fs::read_dir(Path::new(READERS_DIR))
+        .expect("Readers directory should always exist")
+        .map(|entry| entry.expect("Readers directory should be readable").path())
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
+
+    // The order of the entries depends on the file system
+    file_paths.sort();
+
+    let mut hasher = DefaultHasher::new();
+
+    for file_path in file_paths {
+        file_path.file_name().hash(&mut hasher);
+        std::fs::read(&file_path)
+            .expect("Reader files should be readable")
+            .hash(&mut hasher);
+    }
+
+    hasher.finish()
+}
@@ -107,7 +107,7 @@ fn read_baggages_from_file(
     Ok(baggages)
 }
 
-fn get_names_and_descriptions(
+pub fn get_names_and_descriptions(
     item: &GameListItemBase,
     load_context: &mut LoadContext,
 ) -> Result<(BTreeMap<Language, String>, BTreeMap<Language, String>), anyhow::Error> {
@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+
+use parcel_game_data::{ConstructionPoint, ConstructionPointMetaData, ConstructionPointType};
+
+use crate::{
+    qpid_areas::{get_location_files, get_names},
+    readers::{LoadContext, RTTITypeHash},
+};
+
+/// Reads the construction points of structures, such as bridges, ladders and watch towers.
+///
+/// Qpid areas and road rebuilders are skipped, see qpid_areas::read_qpid_areas and highways::read_highway_segments.
+pub fn read_construction_points(
+    load_context: &mut LoadContext,
+    construction_points: &mut BTreeMap<i32, ConstructionPoint>,
+) -> Result<(), anyhow::Error> {
+    // The construction points are stored in the same files as the qpid areas
+    for file_path in get_location_files(load_context)? {
+        let core_file = load_context.load_file(&file_path)?.clone(); // cloning is necessary to avoid borrowing issues
+
+        for delivery_point in core_file.get_objects(&RTTITypeHash::DeliveryPointInfoResource)? {
+            let delivery_point = delivery_point
+                .as_delivery_point_info_resource()
+                .expect("Entry should always be a DeliveryPointInfoResource");
+
+            // Skip delivery points, safe houses and posts since they are read as qpid areas, and road rebuilders since they are read as highway segments
+            match &delivery_point.delivery_point_type {
+                ConstructionPointType::StageWatchTower
+                | ConstructionPointType::PlayerWatchTower
+                | ConstructionPointType::NetWatchTower
+                | ConstructionPointType::StageCharger
+                | ConstructionPointType::PlayerCharger
+                | ConstructionPointType::NetCharger
+                | ConstructionPointType::StageRainShelter
+                | ConstructionPointType::PlayerRainShelter
+                | ConstructionPointType::NetRainShelter
+                | ConstructionPointType::StageZipline
+                | ConstructionPointType::PlayerZipline
+                | ConstructionPointType::NetZipline
+                | ConstructionPointType::StageLadder
+                | ConstructionPointType::PlayerLadder
+                | ConstructionPointType::NetLadder
+                | ConstructionPointType::StageFieldRope
+                | ConstructionPointType::PlayerFieldRope
+                | ConstructionPointType::NetFieldRope
+                | ConstructionPointType::StageBridge30m
+                | ConstructionPointType::PlayerBridge30m
+                | ConstructionPointType::NetBridge30m
+                | ConstructionPointType::StageBridge45m
+                | ConstructionPointType::PlayerBridge45m
+                | ConstructionPointType::NetBridge45m => {}
+                _ => continue,
+            }
+
+            let names = get_names(delivery_point, load_context)?;
+            let metadata = ConstructionPointMetaData {
+                order_in_list: delivery_point.order_in_list,
+                construction_type: delivery_point.delivery_point_type,
+                area: delivery_point.area,
+                location: delivery_point.world_transform.position,
+            };
+
+            construction_points.insert(
+                delivery_point.delivery_point_locator_id,
+                ConstructionPoint {
+                    construction_id: delivery_point.delivery_point_locator_id,
+                    names,
+                    metadata,
+                },
+            );
+        }
+    }
+
+    Ok(())
+}
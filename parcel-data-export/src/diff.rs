@@ -0,0 +1,222 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    path::Path,
+};
+
+use parcel_game_data::Language;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::GameDataExport;
+
+pub fn load_export(file_path: &Path) -> Result<GameDataExport, anyhow::Error> {
+    let file = std::fs::File::open(file_path)?;
+
+    Ok(serde_json::from_reader(std::io::BufReader::new(file))?)
+}
+
+/// Prints the baggages, qpid areas and lost cargo that were added, removed or changed since the previous export,
+/// for example to see what a game patch changed.
+pub fn print_diff(
+    previous: &GameDataExport,
+    current: &GameDataExport,
+) -> Result<(), anyhow::Error> {
+    print_map_diff(
+        "Baggages",
+        &previous.baggages,
+        &current.baggages,
+        |name_hash| {
+            current
+                .baggages
+                .get(name_hash)
+                .or_else(|| previous.baggages.get(name_hash))
+                .and_then(|baggage| baggage.names.get(&Language::English))
+        },
+    )?;
+    print_map_diff(
+        "Qpid areas",
+        &previous.qpid_areas,
+        &current.qpid_areas,
+        |qpid_id| {
+            current
+                .qpid_areas
+                .get(qpid_id)
+                .or_else(|| previous.qpid_areas.get(qpid_id))
+                .and_then(|area| area.names.get(&Language::English))
+        },
+    )?;
+
+    let previous_lost_cargo = get_lost_cargo(previous);
+    let current_lost_cargo = get_lost_cargo(current);
+    let added = current_lost_cargo
+        .difference(&previous_lost_cargo)
+        .collect::<Vec<_>>();
+    let removed = previous_lost_cargo
+        .difference(&current_lost_cargo)
+        .collect::<Vec<_>>();
+
+    println!(
+        "Lost cargo: {} added, {} removed",
+        added.len(),
+        removed.len()
+    );
+
+    for (prefix, entries) in [("+", added), ("-", removed)] {
+        for (qpid_id, name_hash) in entries {
+            let baggage_name = current
+                .baggages
+                .get(name_hash)
+                .or_else(|| previous.baggages.get(name_hash))
+                .and_then(|baggage| baggage.names.get(&Language::English));
+            let area_name = current
+                .qpid_areas
+                .get(qpid_id)
+                .or_else(|| previous.qpid_areas.get(qpid_id))
+                .and_then(|area| area.names.get(&Language::English));
+
+            println!(
+                "  {prefix} {} for {}",
+                format_entry(name_hash, baggage_name),
+                format_entry(qpid_id, area_name)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the keys that were added or removed, and the keys of the values that changed along with the names of the fields that changed.
+fn print_map_diff<'a, TKey, TValue>(
+    title: &str,
+    previous: &BTreeMap<TKey, TValue>,
+    current: &BTreeMap<TKey, TValue>,
+    get_name: impl Fn(&TKey) -> Option<&'a String>,
+) -> Result<(), anyhow::Error>
+where
+    TKey: Ord + Display,
+    TValue: Serialize,
+{
+    let added = current
+        .keys()
+        .filter(|key| !previous.contains_key(key))
+        .collect::<Vec<_>>();
+    let removed = previous
+        .keys()
+        .filter(|key| !current.contains_key(key))
+        .collect::<Vec<_>>();
+    let mut changed = Vec::new();
+
+    for (key, current_value) in current {
+        let Some(previous_value) = previous.get(key) else {
+            continue;
+        };
+
+        // The values are compared as json since not every exported type implements PartialEq
+        let changed_fields = get_changed_fields(
+            &serde_json::to_value(previous_value)?,
+            &serde_json::to_value(current_value)?,
+        );
+
+        if !changed_fields.is_empty() {
+            changed.push((key, changed_fields));
+        }
+    }
+
+    println!(
+        "{title}: {} added, {} removed, {} changed",
+        added.len(),
+        removed.len(),
+        changed.len()
+    );
+
+    for key in added {
+        println!("  + {}", format_entry(key, get_name(key)));
+    }
+
+    for key in removed {
+        println!("  - {}", format_entry(key, get_name(key)));
+    }
+
+    for (key, changed_fields) in changed {
+        println!(
+            "  ~ {}: {}",
+            format_entry(key, get_name(key)),
+            changed_fields.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns the names of the top level fields that are different, or an empty list if the values are equal.
+fn get_changed_fields(previous: &Value, current: &Value) -> Vec<String> {
+    match (previous, current) {
+        (Value::Object(previous), Value::Object(current)) => previous
+            .keys()
+            .chain(current.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|field| previous.get(*field) != current.get(*field))
+            .cloned()
+            .collect(),
+        _ if previous != current => vec!["value".to_owned()],
+        _ => Vec::new(),
+    }
+}
+
+/// Returns every combination of delivery point qpid id and baggage name hash in the lost cargo lists.
+fn get_lost_cargo(export: &GameDataExport) -> BTreeSet<(i32, u32)> {
+    export
+        .lost_baggages
+        .iter()
+        .flat_map(|(qpid_id, name_hashes)| {
+            name_hashes.iter().map(|name_hash| (*qpid_id, *name_hash))
+        })
+        .collect()
+}
+
+fn format_entry(key: &impl Display, name: Option<&String>) -> String {
+    match name {
+        Some(name) => format!("{key} ({name})"),
+        None => key.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn equal_values_have_no_changed_fields() {
+        let value = json!({ "names": { "english": "Metals" }, "weight": 1.5 });
+
+        assert!(get_changed_fields(&value, &value).is_empty());
+        assert!(get_changed_fields(&json!(5), &json!(5)).is_empty());
+    }
+
+    #[test]
+    fn changed_added_and_removed_fields_are_listed_in_order() {
+        let previous = json!({ "weight": 1.5, "names": { "english": "Metals" }, "removed": true });
+        let current = json!({ "weight": 2.0, "names": { "english": "Metal" }, "added": 1 });
+
+        assert_eq!(
+            get_changed_fields(&previous, &current),
+            ["added", "names", "removed", "weight"]
+        );
+    }
+
+    #[test]
+    fn values_that_are_not_objects_are_compared_as_a_whole() {
+        assert_eq!(
+            get_changed_fields(&json!([1, 2]), &json!([1, 3])),
+            ["value"]
+        );
+        assert_eq!(
+            get_changed_fields(&json!({ "a": 1 }), &json!(null)),
+            ["value"]
+        );
+    }
+}
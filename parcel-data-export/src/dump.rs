@@ -0,0 +1,312 @@
+use std::path::Path;
+
+use anyhow::Context;
+use binary_reader::BinaryReader;
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+use crate::readers::{
+    core_file::RawObject,
+    get_path_str,
+    reference::UnresolvedRef,
+    schema::{FieldType, Schema, TypeLayout},
+    string::DSString,
+    LoadContext, Read,
+};
+
+/// Decodes every object in the file with the type layouts in the schema and writes them to the output file as json.
+///
+/// References are followed up to `resolve_depth` levels deep and the referenced objects are included in the output.
+/// Objects with a type that's not in the schema only include their type hash and offset.
+pub fn dump(
+    load_context: &mut LoadContext,
+    schema: &Schema,
+    file_path: &Path,
+    resolve_depth: u32,
+    output_path: &Path,
+) -> Result<(), anyhow::Error> {
+    let path_str = get_path_str(file_path);
+    let objects = load_context.load_raw_file(file_path)?.to_vec(); // cloning is necessary to avoid borrowing issues
+
+    let mut dumper = Dumper {
+        load_context,
+        schema,
+        object_stack: Vec::new(),
+    };
+    let objects = objects
+        .iter()
+        .map(|object| dumper.dump_object(&path_str, object, resolve_depth))
+        .collect::<Vec<_>>();
+
+    let output = json!({
+        "file": path_str,
+        "objects": objects,
+    });
+    let new_file = std::fs::File::create(output_path)?;
+    serde_json::to_writer_pretty(new_file, &output)?;
+
+    Ok(())
+}
+
+/// Decodes the objects in the file that have the type with the name in the schema, without following references.
+///
+/// This lets the export read types that don't have a reader. Unlike when dumping, an object that can't be read is an error.
+pub fn read_schema_objects(
+    load_context: &mut LoadContext,
+    schema: &Schema,
+    file_path: &Path,
+    type_name: &str,
+) -> Result<Vec<(Uuid, Map<String, Value>)>, anyhow::Error> {
+    let layout = schema
+        .get(type_name)
+        .with_context(|| format!("{type_name} is not in the schema"))?;
+    let hash = layout
+        .hash
+        .with_context(|| format!("{type_name} has no hash in the schema"))?;
+
+    let path_str = get_path_str(file_path);
+    let objects = load_context.load_raw_file(file_path)?.to_vec(); // cloning is necessary to avoid borrowing issues
+
+    let mut dumper = Dumper {
+        load_context,
+        schema,
+        object_stack: Vec::new(),
+    };
+    let mut result = Vec::new();
+
+    for object in objects.iter().filter(|object| object.type_hash == hash) {
+        let uuid = object.uuid().unwrap_or_default();
+        dumper.object_stack.push((path_str.clone(), uuid));
+
+        let mut reader = BinaryReader::from_u8(&object.data);
+        reader.set_endian(binary_reader::Endian::Little);
+
+        let mut fields = Map::new();
+        let read_result = dumper.read_fields(layout, &mut reader, &mut fields, 0);
+
+        dumper.object_stack.pop();
+        // Mismatches are reported by --verify, they're taken so that they aren't attributed to the next object that's read
+        dumper.load_context.take_checksum_mismatches();
+
+        read_result.with_context(|| {
+            format!(
+                "Reading {type_name} at offset {} in {path_str}",
+                object.offset
+            )
+        })?;
+
+        result.push((uuid, fields));
+    }
+
+    Ok(result)
+}
+
+struct Dumper<'a> {
+    load_context: &'a mut LoadContext,
+    schema: &'a Schema,
+    /// The file path and uuid of the objects that are being dumped, used to avoid following references in a loop
+    object_stack: Vec<(String, Uuid)>,
+}
+
+impl<'a> Dumper<'a> {
+    fn dump_object(&mut self, file_path: &str, object: &RawObject, resolve_depth: u32) -> Value {
+        let schema = self.schema;
+        let layout = schema.get_by_hash(object.type_hash);
+        let mut result = Map::new();
+        result.insert(
+            "type".into(),
+            match layout {
+                Some(layout) => layout.name.clone().into(),
+                None => format!("0x{:X}", object.type_hash).into(),
+            },
+        );
+        result.insert("offset".into(), object.offset.into());
+
+        let Some(layout) = layout else {
+            return Value::Object(result);
+        };
+
+        let stack_entry = (file_path.to_owned(), object.uuid().unwrap_or_default());
+        self.object_stack.push(stack_entry);
+
+        // The object that references this one is still being read, so its checksum mismatches are put back afterwards
+        let referencing_mismatches = self.load_context.take_checksum_mismatches();
+
+        let mut reader = BinaryReader::from_u8(&object.data);
+        reader.set_endian(binary_reader::Endian::Little);
+
+        // Fields that were read before an error are kept to make it easier to find where the layout is wrong
+        let mut fields = Map::new();
+        let read_result = self.read_fields(layout, &mut reader, &mut fields, resolve_depth);
+        result.insert("fields".into(), Value::Object(fields));
+
+        let checksum_mismatches = self.load_context.take_checksum_mismatches();
+
+        for mismatch in referencing_mismatches {
+            self.load_context.record_checksum_mismatch(mismatch);
+        }
+
+        if !checksum_mismatches.is_empty() {
+            result.insert(
+                "stringChecksumMismatches".into(),
+                checksum_mismatches.len().into(),
+            );
+        }
+
+        match read_result {
+            Ok(()) => {
+                if reader.pos != reader.length {
+                    result.insert("unreadBytes".into(), (reader.length - reader.pos).into());
+                }
+            }
+            Err(err) => {
+                result.insert("error".into(), format!("{err:#}").into());
+                result.insert(
+                    "offsetAfterRead".into(),
+                    (object.data_offset() + reader.pos).into(),
+                );
+            }
+        }
+
+        self.object_stack.pop();
+
+        Value::Object(result)
+    }
+
+    fn read_fields(
+        &mut self,
+        layout: &TypeLayout,
+        reader: &mut BinaryReader,
+        fields: &mut Map<String, Value>,
+        resolve_depth: u32,
+    ) -> Result<(), anyhow::Error> {
+        let schema = self.schema;
+
+        for (name, ty) in schema.all_fields(layout) {
+            let value = self.read_value(ty, reader, resolve_depth)?;
+            fields.insert(name.clone(), value);
+        }
+
+        Ok(())
+    }
+
+    fn read_value(
+        &mut self,
+        ty: &FieldType,
+        reader: &mut BinaryReader,
+        resolve_depth: u32,
+    ) -> Result<Value, anyhow::Error> {
+        Ok(match ty {
+            FieldType::Bool => (reader.read_u8()? != 0).into(),
+            FieldType::U8 => reader.read_u8()?.into(),
+            FieldType::I8 => reader.read_i8()?.into(),
+            FieldType::U16 => reader.read_u16()?.into(),
+            FieldType::I16 => reader.read_i16()?.into(),
+            FieldType::U32 => reader.read_u32()?.into(),
+            FieldType::I32 => reader.read_i32()?.into(),
+            FieldType::U64 => reader.read_u64()?.into(),
+            FieldType::I64 => reader.read_i64()?.into(),
+            FieldType::F32 => reader.read_f32()?.into(),
+            FieldType::F64 => reader.read_f64()?.into(),
+            FieldType::String => String::from(DSString::read(reader, self.load_context)?).into(),
+            FieldType::ShortString => {
+                let len = reader.read_u16()?;
+                String::from_utf8(reader.read_bytes(len as usize)?.to_vec())?.into()
+            }
+            FieldType::Uuid => Uuid::from_slice_le(reader.read_bytes(16)?)?
+                .to_string()
+                .into(),
+            FieldType::Ref => {
+                let reference = UnresolvedRef::read(reader, self.load_context)?;
+                self.resolve_ref(reference, resolve_depth)
+            }
+            FieldType::Array(element_type) => {
+                let len = reader.read_u32()? as usize;
+
+                // same sanity check as readers::array::Array
+                if len > 10000 {
+                    anyhow::bail!("Something is probably wrong, reported array size = {len}");
+                }
+
+                let mut values = Vec::with_capacity(len);
+
+                for _ in 0..len {
+                    values.push(self.read_value(element_type, reader, resolve_depth)?);
+                }
+
+                values.into()
+            }
+            FieldType::FixedArray(element_type, len) => {
+                let mut values = Vec::with_capacity(*len);
+
+                for _ in 0..*len {
+                    values.push(self.read_value(element_type, reader, resolve_depth)?);
+                }
+
+                values.into()
+            }
+            FieldType::Type(name) => {
+                let schema = self.schema;
+                let layout = schema
+                    .get(name)
+                    .expect("Field types should always exist in the schema");
+                let mut fields = Map::new();
+                self.read_fields(layout, reader, &mut fields, resolve_depth)?;
+
+                Value::Object(fields)
+            }
+        })
+    }
+
+    /// Returns the uuid and path of the reference, and the referenced object if it's not too deep or already being dumped.
+    fn resolve_ref(&mut self, reference: UnresolvedRef, resolve_depth: u32) -> Value {
+        let Some(uuid) = reference.uuid else {
+            return Value::Null;
+        };
+
+        // Internal references don't have a path since they point to an object in the same file
+        let file_path = match reference.path {
+            Some(path) => get_path_str(Path::new(&path)),
+            None => self
+                .object_stack
+                .last()
+                .map(|(file_path, _)| file_path.clone())
+                .expect("A reference should always be read while dumping an object"),
+        };
+
+        let mut result = Map::new();
+        result.insert("uuid".into(), uuid.to_string().into());
+        result.insert("path".into(), file_path.clone().into());
+
+        let stack_entry = (file_path.clone(), uuid);
+
+        if resolve_depth == 0 || self.object_stack.contains(&stack_entry) {
+            return Value::Object(result);
+        }
+
+        let object = self
+            .load_context
+            .load_raw_file(Path::new(&file_path))
+            .map(|objects| {
+                objects
+                    .iter()
+                    .find(|object| object.uuid() == Some(uuid))
+                    .cloned() // cloning is necessary to avoid borrowing issues
+            });
+
+        match object {
+            Ok(Some(object)) => {
+                let value = self.dump_object(&file_path, &object, resolve_depth - 1);
+                result.insert("object".into(), value);
+            }
+            Ok(None) => {
+                result.insert("error".into(), "Object not found".into());
+            }
+            Err(err) => {
+                result.insert("error".into(), format!("{err:#}").into());
+            }
+        }
+
+        Value::Object(result)
+    }
+}
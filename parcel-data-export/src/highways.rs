@@ -0,0 +1,200 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use parcel_game_data::{
+    ConstructionPointType, ContentsType, HighwayResource, HighwaySegment, HighwaySegmentMetaData,
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    baggages::get_names_and_descriptions,
+    dump::read_schema_objects,
+    qpid_areas::{get_location_files, get_names},
+    readers::{
+        raw_material_list_item::RawMaterialListItem, reference::Ref, schema::Schema, LoadContext,
+        RTTITypeHash,
+    },
+};
+
+/// The name of the road rebuilder config type in the schema, see `read_highway_requirements`.
+const ROAD_REBUILDER_CONFIG_TYPE: &str = "RoadRebuilderConfig";
+
+/// Reads the highway segments, which are the delivery points of the road rebuilders.
+///
+/// The construction id the game uses when devoting resources is the locator id of the delivery point.
+pub fn read_highway_segments(
+    load_context: &mut LoadContext,
+    segments: &mut BTreeMap<i32, HighwaySegment>,
+) -> Result<(), anyhow::Error> {
+    // The road rebuilders are stored in the same files as the qpid areas
+    for file_path in get_location_files(load_context)? {
+        let core_file = load_context.load_file(&file_path)?.clone(); // cloning is necessary to avoid borrowing issues
+
+        for delivery_point in core_file.get_objects(&RTTITypeHash::DeliveryPointInfoResource)? {
+            let delivery_point = delivery_point
+                .as_delivery_point_info_resource()
+                .expect("Entry should always be a DeliveryPointInfoResource");
+
+            if delivery_point.delivery_point_type != ConstructionPointType::RoadRebuilder {
+                continue;
+            }
+
+            let names = get_names(delivery_point, load_context)?;
+            let metadata = HighwaySegmentMetaData {
+                order_in_list: delivery_point.order_in_list,
+                area: delivery_point.area,
+                location: delivery_point.world_transform.position,
+            };
+
+            segments.insert(
+                delivery_point.delivery_point_locator_id,
+                HighwaySegment {
+                    construction_id: delivery_point.delivery_point_locator_id,
+                    names,
+                    metadata,
+                    // Filled in by read_highway_requirements
+                    required_resources: BTreeMap::new(),
+                },
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Adds the resources that are needed to finish each segment from the road rebuilder configs.
+///
+/// There's no reader for the config, so its layout is taken from the schema. The type has to be named `RoadRebuilderConfig`
+/// and have a `DeliveryPoint` ref to the delivery point of the road rebuilder, and a `RequiredResources` array with the
+/// `RawMaterialType` and `Amount` of each resource. The requirements are left empty if the schema doesn't have the type.
+pub fn read_highway_requirements(
+    load_context: &mut LoadContext,
+    schema: Option<&Schema>,
+    segments: &mut BTreeMap<i32, HighwaySegment>,
+) -> Result<(), anyhow::Error> {
+    let Some(schema) = schema.filter(|schema| schema.get(ROAD_REBUILDER_CONFIG_TYPE).is_some())
+    else {
+        println!(
+            "There's no {ROAD_REBUILDER_CONFIG_TYPE} in the schema, the resources required to finish highway segments are not exported"
+        );
+        return Ok(());
+    };
+
+    let location_files = get_location_files(load_context)?;
+
+    // The config can be in another file than the delivery point it refers to
+    let mut construction_ids = HashMap::<Uuid, i32>::new();
+
+    for file_path in &location_files {
+        let core_file = load_context.load_file(file_path)?;
+
+        for delivery_point in core_file.get_objects(&RTTITypeHash::DeliveryPointInfoResource)? {
+            let uuid = *delivery_point.object_uuid();
+            let delivery_point = delivery_point
+                .as_delivery_point_info_resource()
+                .expect("Entry should always be a DeliveryPointInfoResource");
+
+            if delivery_point.delivery_point_type == ConstructionPointType::RoadRebuilder {
+                construction_ids.insert(uuid, delivery_point.delivery_point_locator_id);
+            }
+        }
+    }
+
+    for file_path in &location_files {
+        for (uuid, fields) in
+            read_schema_objects(load_context, schema, file_path, ROAD_REBUILDER_CONFIG_TYPE)?
+        {
+            let delivery_point_uuid = fields
+                .get("DeliveryPoint")
+                .and_then(|reference| reference.get("uuid"))
+                .and_then(Value::as_str)
+                .map(Uuid::parse_str)
+                .transpose()?;
+
+            let Some(segment) = delivery_point_uuid
+                .and_then(|delivery_point_uuid| construction_ids.get(&delivery_point_uuid))
+                .and_then(|construction_id| segments.get_mut(construction_id))
+            else {
+                println!("{ROAD_REBUILDER_CONFIG_TYPE} {uuid} doesn't refer to a road rebuilder");
+                continue;
+            };
+
+            let required_resources = fields
+                .get("RequiredResources")
+                .and_then(Value::as_array)
+                .with_context(|| {
+                    format!("{ROAD_REBUILDER_CONFIG_TYPE} {uuid} has no RequiredResources array")
+                })?;
+
+            for required in required_resources {
+                let resource_id = required
+                    .get("RawMaterialType")
+                    .and_then(Value::as_i64)
+                    .with_context(|| format!("{ROAD_REBUILDER_CONFIG_TYPE} {uuid} has a required resource without a RawMaterialType"))?;
+                let amount = required
+                    .get("Amount")
+                    .and_then(Value::as_i64)
+                    .with_context(|| format!("{ROAD_REBUILDER_CONFIG_TYPE} {uuid} has a required resource without an Amount"))?;
+
+                *segment
+                    .required_resources
+                    .entry(resource_id as i16)
+                    .or_default() += amount;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the resources that can be devoted to highway segments.
+///
+/// The resource id the game uses when devoting resources is the raw material type.
+pub fn read_highway_resources(
+    load_context: &mut LoadContext,
+    resources: &mut BTreeMap<i16, HighwayResource>,
+) -> Result<(), anyhow::Error> {
+    let file = load_context
+        .load_file(Path::new("ds/catalogue/baggages/baggage_rawmaterial.core"))?
+        .clone();
+
+    for baggage in file.get_objects(&RTTITypeHash::BaggageListItem)? {
+        let baggage = baggage
+            .as_baggage_list_item()
+            .expect("Entry should always be a BaggageListItem");
+
+        if baggage.type_contents != ContentsType::RawMaterial {
+            continue;
+        }
+
+        // The contents are always stored in another file
+        let (Some(uuid), Some(path)) = (baggage.contents.uuid, baggage.contents.path.as_ref())
+        else {
+            continue;
+        };
+
+        let raw_material = Ref::<RawMaterialListItem>::new(PathBuf::from(path), uuid)
+            .load_resolve(load_context)?
+            .context("Ref should never be None")?
+            .as_raw_material_list_item()
+            .context("Ref should always be a RawMaterialListItem")?
+            .clone(); // clone required to avoid borrowing issues
+
+        let resource_id = raw_material.raw_material_type as i16;
+
+        // There's a baggage for every amount of a raw material, they all refer to the same contents
+        if resources.contains_key(&resource_id) {
+            continue;
+        }
+
+        let (names, _) = get_names_and_descriptions(&raw_material, load_context)?;
+
+        resources.insert(resource_id, HighwayResource { resource_id, names });
+    }
+
+    Ok(())
+}
@@ -1,46 +1,145 @@
 mod baggages;
+mod construction_points;
+mod diff;
+mod dump;
+mod highways;
 mod lost_baggages;
+mod missions;
 pub mod qpid_areas;
 mod readers;
+mod verify;
 
 use std::{collections::BTreeMap, path::PathBuf};
 
 use anyhow::Context;
 use clap::Parser;
-use parcel_game_data::{Baggage, QpidArea};
-use readers::LoadContext;
+use parcel_game_data::{
+    Baggage, ConstructionPoint, HighwayResource, HighwaySegment, MissionDefinition, QpidArea,
+};
+use readers::{file_cache::FileCache, schema::Schema, LoadContext};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, clap::Parser)]
 struct Options {
     #[clap(id = "EXTRACTED_DATA_DIR")]
     data_directory: PathBuf,
-    output_path: PathBuf,
+    #[clap(required_unless_present = "verify")]
+    output_path: Option<PathBuf>,
+    /// Check every file in the data directory for corrupt or unknown objects instead of exporting the game data
+    #[clap(long, conflicts_with = "dump")]
+    verify: bool,
+    /// Write the objects in this .core file to the output path as json instead of exporting the game data.
+    /// The path is relative to the data directory
+    #[clap(long, requires = "schema")]
+    dump: Option<PathBuf>,
+    /// The json file with the type layouts that are used when dumping, see readers::schema.
+    /// When exporting it's used to read the road rebuilder configs and missions, see highways::read_highway_requirements and missions::read_missions
+    #[clap(long)]
+    schema: Option<PathBuf>,
+    /// How many levels of references to include the referenced objects of when dumping
+    #[clap(long, default_value_t = 1)]
+    resolve_depth: u32,
+    /// Store decoded files in this directory, so that files that haven't changed don't have to be decoded again on the next run
+    #[clap(long)]
+    cache_dir: Option<PathBuf>,
+    /// Print what was added, removed or changed compared to this previously exported file.
+    /// It can be the same file as the output path
+    #[clap(long, conflicts_with_all = ["verify", "dump"])]
+    diff: Option<PathBuf>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct GameDataExport {
     pub baggages: BTreeMap<u32, Baggage>,
     pub qpid_areas: BTreeMap<i32, QpidArea>,
     /// The key is the qpid id of the delivery point and the value is a list of
     /// baggage name hashes (which matches a key in the `self.baggages` map).
     pub lost_baggages: BTreeMap<i32, Vec<u32>>,
+    pub highway_segments: BTreeMap<i32, HighwaySegment>,
+    pub highway_resources: BTreeMap<i16, HighwayResource>,
+    pub construction_points: BTreeMap<i32, ConstructionPoint>,
+    pub missions: BTreeMap<i64, MissionDefinition>,
 }
 
 fn main() -> Result<(), anyhow::Error> {
     let args = Options::parse();
-    let mut output = GameDataExport::default();
     let mut load_context = LoadContext::new(args.data_directory.clone());
 
+    if let Some(cache_dir) = &args.cache_dir {
+        load_context.set_cache(FileCache::new(cache_dir.clone()));
+    }
+
+    if args.verify {
+        return verify::verify(&mut load_context);
+    }
+
+    let output_path = args
+        .output_path
+        .expect("Output path should always be Some when not verifying");
+
+    let schema = args
+        .schema
+        .as_deref()
+        .map(Schema::load)
+        .transpose()
+        .context("Could not load schema")?;
+
+    if let Some(dump_path) = &args.dump {
+        let schema = schema
+            .as_ref()
+            .expect("Schema should always be Some when dumping");
+
+        return dump::dump(
+            &mut load_context,
+            schema,
+            dump_path,
+            args.resolve_depth,
+            &output_path,
+        );
+    }
+
+    // Loaded before exporting since the output path can be the same file
+    let previous_export = args
+        .diff
+        .as_deref()
+        .map(diff::load_export)
+        .transpose()
+        .context("Could not load previous export")?;
+    let mut output = GameDataExport::default();
+
+    // The location files are used by several of the readers below, loading them first lets them be decoded in parallel
+    load_context.preload_files(&qpid_areas::get_location_files(&load_context)?);
+
     baggages::read_baggages(&mut load_context, &mut output.baggages)
         .context("Could not read baggages")?;
     qpid_areas::read_qpid_areas(&mut load_context, &mut output.qpid_areas)
         .context("Could not read qpid areas")?;
     lost_baggages::read_lost_baggages(&mut load_context, &mut output.lost_baggages)?;
+    highways::read_highway_segments(&mut load_context, &mut output.highway_segments)
+        .context("Could not read highway segments")?;
+    highways::read_highway_requirements(
+        &mut load_context,
+        schema.as_ref(),
+        &mut output.highway_segments,
+    )
+    .context("Could not read highway requirements")?;
+    highways::read_highway_resources(&mut load_context, &mut output.highway_resources)
+        .context("Could not read highway resources")?;
+    construction_points::read_construction_points(
+        &mut load_context,
+        &mut output.construction_points,
+    )
+    .context("Could not read construction points")?;
+    missions::read_missions(&mut load_context, schema.as_ref(), &mut output.missions)
+        .context("Could not read missions")?;
 
-    let new_file = std::fs::File::create(args.output_path)?;
+    let new_file = std::fs::File::create(output_path)?;
     serde_json::to_writer_pretty(new_file, &output)?;
 
+    if let Some(previous_export) = &previous_export {
+        diff::print_diff(previous_export, &output)?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,89 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use anyhow::Context;
+use parcel_game_data::{Language, MissionDefinition};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    dump::read_schema_objects,
+    qpid_areas::get_location_files,
+    readers::{
+        localized_text_resource::LocalizedTextResource, reference::Ref, schema::Schema, LoadContext,
+    },
+};
+
+/// The name of the mission definition type in the schema, see `read_missions`.
+const MISSION_DEFINITION_TYPE: &str = "MissionDefinition";
+
+/// Reads the missions that are defined in the game from the location files, which is where the points they start and end at are.
+///
+/// There's no reader for the missions, so their layout is taken from the schema. The type has to be named `MissionDefinition`
+/// and have a `MissionStaticId` integer and a `Name` ref to a localized text. No missions are read if the schema doesn't have the type.
+pub fn read_missions(
+    load_context: &mut LoadContext,
+    schema: Option<&Schema>,
+    missions: &mut BTreeMap<i64, MissionDefinition>,
+) -> Result<(), anyhow::Error> {
+    let Some(schema) = schema.filter(|schema| schema.get(MISSION_DEFINITION_TYPE).is_some()) else {
+        println!("There's no {MISSION_DEFINITION_TYPE} in the schema, missions are not exported");
+        return Ok(());
+    };
+
+    for file_path in get_location_files(load_context)? {
+        for (uuid, fields) in
+            read_schema_objects(load_context, schema, &file_path, MISSION_DEFINITION_TYPE)?
+        {
+            let mission_static_id = fields
+                .get("MissionStaticId")
+                .and_then(Value::as_i64)
+                .with_context(|| {
+                    format!("{MISSION_DEFINITION_TYPE} {uuid} has no MissionStaticId")
+                })?;
+            let names = get_names(fields.get("Name"), load_context)
+                .with_context(|| format!("Reading the name of {MISSION_DEFINITION_TYPE} {uuid}"))?;
+
+            missions.insert(
+                mission_static_id,
+                MissionDefinition {
+                    mission_static_id,
+                    names,
+                },
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the text of the localized text resource that the ref read with the schema points to,
+/// or no names if the ref is null.
+fn get_names(
+    reference: Option<&Value>,
+    load_context: &mut LoadContext,
+) -> Result<BTreeMap<Language, String>, anyhow::Error> {
+    let mut names = BTreeMap::new();
+
+    let (Some(uuid), Some(path)) = (
+        reference
+            .and_then(|reference| reference.get("uuid"))
+            .and_then(Value::as_str),
+        reference
+            .and_then(|reference| reference.get("path"))
+            .and_then(Value::as_str),
+    ) else {
+        return Ok(names);
+    };
+
+    let text = Ref::<LocalizedTextResource>::new(PathBuf::from(path), Uuid::parse_str(uuid)?)
+        .load_resolve(load_context)?
+        .context("Ref should never be None")?
+        .as_localized_text_resource()
+        .context("Name should always be LocalizedTextResource")?;
+
+    for (language, localized) in &text.languages {
+        names.insert(*language, localized.text.clone());
+    }
+
+    Ok(names)
+}
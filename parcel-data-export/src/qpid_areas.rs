@@ -1,4 +1,8 @@
-use std::{collections::BTreeMap, ffi::OsStr, path::Path};
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
 
 use parcel_game_data::{ConstructionPointType, Language, QpidArea, QpidAreaMetaData};
 
@@ -10,7 +14,60 @@ pub fn read_qpid_areas(
     load_context: &mut LoadContext,
     qpid_areas: &mut BTreeMap<i32, QpidArea>,
 ) -> Result<(), anyhow::Error> {
+    for file_path in get_location_files(load_context)? {
+        let core_file = load_context.load_file(&file_path)?.clone(); // cloning is necessary to avoid borrowing issues. there is probably a better way but i don't know it
+
+        for delivery_point in core_file.get_objects(&RTTITypeHash::DeliveryPointInfoResource)? {
+            let delivery_point = delivery_point
+                .as_delivery_point_info_resource()
+                .expect("Entry should always be a DeliveryPointInfoResource");
+
+            // Skip invalid construction types
+            match &delivery_point.delivery_point_type {
+                ConstructionPointType::DeliveryBase
+                | ConstructionPointType::PreppersShelter
+                | ConstructionPointType::StageSafetyHouse
+                | ConstructionPointType::PlayerSafetyHouse
+                | ConstructionPointType::NetSafetyHouse
+                | ConstructionPointType::StagePost
+                | ConstructionPointType::PlayerPost
+                | ConstructionPointType::NetPost
+                | ConstructionPointType::MulePost => {}
+                _ => continue,
+            }
+
+            let names = get_names(delivery_point, load_context)?;
+
+            // If there's no localization text for this area it's probably not relevant
+            if names.is_empty() {
+                continue;
+            }
+
+            let metadata = QpidAreaMetaData {
+                order_in_list: delivery_point.order_in_list,
+                construction_type: delivery_point.delivery_point_type,
+                area: delivery_point.area,
+                location: delivery_point.world_transform.position,
+            };
+
+            qpid_areas.insert(
+                delivery_point.delivery_point_locator_id,
+                QpidArea {
+                    qpid_id: delivery_point.delivery_point_locator_id,
+                    names,
+                    metadata,
+                },
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the relative paths of the files in the qpid area directories of every area, which contain the delivery points.
+pub fn get_location_files(load_context: &LoadContext) -> Result<Vec<PathBuf>, anyhow::Error> {
     let location_dir = load_context.get_absolute_path(&Path::new("ds").join("location"));
+    let mut file_paths = Vec::new();
 
     // Iterate each folder in location dir, which consists of a folder for each area/level in the game
     for area_dir in location_dir.read_dir()? {
@@ -45,61 +102,15 @@ pub fn read_qpid_areas(
                     continue;
                 }
 
-                let relative_path = load_context.get_relative_path(&file_path)?;
-                let core_file = load_context.load_file(relative_path)?.clone(); // cloning is necessary to avoid borrowing issues. there is probably a better way but i don't know it
-
-                for delivery_point in
-                    core_file.get_objects(&RTTITypeHash::DeliveryPointInfoResource)?
-                {
-                    let delivery_point = delivery_point
-                        .as_delivery_point_info_resource()
-                        .expect("Entry should always be a DeliveryPointInfoResource");
-
-                    // Skip invalid construction types
-                    match &delivery_point.delivery_point_type {
-                        ConstructionPointType::DeliveryBase
-                        | ConstructionPointType::PreppersShelter
-                        | ConstructionPointType::StageSafetyHouse
-                        | ConstructionPointType::PlayerSafetyHouse
-                        | ConstructionPointType::NetSafetyHouse
-                        | ConstructionPointType::StagePost
-                        | ConstructionPointType::PlayerPost
-                        | ConstructionPointType::NetPost
-                        | ConstructionPointType::MulePost => {}
-                        _ => continue,
-                    }
-
-                    let names = get_names(delivery_point, load_context)?;
-
-                    // If there's no localization text for this area it's probably not relevant
-                    if names.is_empty() {
-                        continue;
-                    }
-
-                    let metadata = QpidAreaMetaData {
-                        order_in_list: delivery_point.order_in_list,
-                        construction_type: delivery_point.delivery_point_type,
-                        area: delivery_point.area,
-                        location: delivery_point.world_transform.position,
-                    };
-
-                    qpid_areas.insert(
-                        delivery_point.delivery_point_locator_id,
-                        QpidArea {
-                            qpid_id: delivery_point.delivery_point_locator_id,
-                            names,
-                            metadata,
-                        },
-                    );
-                }
+                file_paths.push(load_context.get_relative_path(&file_path)?.to_owned());
             }
         }
     }
 
-    Ok(())
+    Ok(file_paths)
 }
 
-fn get_names(
+pub fn get_names(
     delivery_point: &DeliveryPointInfoResource,
     load_context: &mut LoadContext,
 ) -> Result<BTreeMap<Language, String>, anyhow::Error> {
@@ -1,8 +1,10 @@
 use std::ops::{Deref, DerefMut};
 
+use serde::{Deserialize, Serialize};
+
 use super::Read;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Array<T: Read + Clone>(Vec<T>);
 
 impl<T: Read + Clone> super::Read for Array<T> {
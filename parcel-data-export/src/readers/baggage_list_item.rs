@@ -3,6 +3,7 @@ use std::ops::{Deref, DerefMut};
 use parcel_game_data::{
     BaggageAttribute, BaggageCaseType, ContentsDamageType, ContentsType, VolumeType,
 };
+use serde::{Deserialize, Serialize};
 
 use super::{game_list_item_base::GameListItemBase, reference::UnresolvedRef};
 
@@ -105,7 +106,7 @@ impl super::Read for VolumeType {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BaggageListItem {
     base: GameListItemBase,
     pub attribute_of_baggage: BaggageAttribute,
@@ -1,6 +1,7 @@
 use std::ops::{Deref, DerefMut};
 
 use parcel_game_data::{EffectivenessType, TypeOfConsume};
+use serde::{Deserialize, Serialize};
 
 use super::{game_list_item_base_with_icon::GameListItemBaseWithIcon, LoadContext};
 
@@ -36,7 +37,7 @@ impl super::Read for EffectivenessType {
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommodityListItem {
     base: GameListItemBaseWithIcon,
     type_of_consume: TypeOfConsume,
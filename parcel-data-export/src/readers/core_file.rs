@@ -2,6 +2,7 @@ use std::{collections::HashMap, fs::File};
 
 use binary_reader::BinaryReader;
 use int_enum::IntEnum;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::{
@@ -18,16 +19,105 @@ use super::{
     LoadContext, RTTIType, RTTITypeHash, Read, ReadRTTIType,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Entry {
     pub type_hash: u64,
     pub uuid: Uuid,
     pub value: RTTIType,
 }
 
+/// An object in a .core file that hasn't been decoded.
 #[derive(Debug, Clone)]
+pub struct RawObject {
+    pub type_hash: u64,
+    /// The offset of the object in the file
+    pub offset: usize,
+    pub data: Vec<u8>,
+}
+
+impl RawObject {
+    /// The size of the type hash and data length that are stored before the data.
+    pub const HEADER_SIZE: usize = 12;
+
+    /// Returns the offset of the data in the file.
+    pub fn data_offset(&self) -> usize {
+        self.offset + Self::HEADER_SIZE
+    }
+
+    /// Returns the uuid of the object, which is stored at the start of the data since every object is an RTTIRefObject.
+    pub fn uuid(&self) -> Option<Uuid> {
+        self.data
+            .get(..16)
+            .and_then(|bytes| Uuid::from_slice_le(bytes).ok())
+    }
+}
+
+/// Splits the file into its objects without decoding them.
+pub fn read_raw_objects(file: &mut File) -> Result<Vec<RawObject>, anyhow::Error> {
+    let mut objects = Vec::new();
+    let mut reader = BinaryReader::from_file(file);
+    reader.set_endian(binary_reader::Endian::Little);
+
+    while reader.pos < reader.length {
+        let offset = reader.pos;
+        let type_hash = reader.read_u64()?;
+        let len = reader.read_u32()? as usize;
+
+        if reader.pos + len > reader.length {
+            anyhow::bail!("Unexpected end of file");
+        }
+
+        objects.push(RawObject {
+            type_hash,
+            offset,
+            data: reader.read_bytes(len)?.to_vec(),
+        });
+    }
+
+    if reader.pos != reader.length {
+        anyhow::bail!("Expected end of file");
+    }
+
+    Ok(objects)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectProblem {
+    pub type_hash: u64,
+    /// The offset of the object in the file
+    pub offset: usize,
+    pub kind: ObjectProblemKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ObjectProblemKind {
+    /// There is no reader for the type, so the object was skipped
+    UnknownType,
+    /// The object is corrupt or the reader doesn't match the data
+    ReadFailed {
+        error: String,
+        /// The offset in the file where reading failed
+        read_offset: usize,
+    },
+    /// The object was read but there is data left after it
+    NotFullyRead { num_unread_bytes: usize },
+    /// The object was read but the checksum of one of its strings didn't match
+    StringChecksumMismatch { expected: u32, actual: u32 },
+}
+
+impl ObjectProblem {
+    /// Returns the name of the type if it's known, otherwise the hash as hex.
+    pub fn type_name(&self) -> String {
+        RTTITypeHash::from_int(self.type_hash)
+            .map(|ty| format!("{:?}", ty))
+            .unwrap_or_else(|_| format!("0x{:X}", self.type_hash))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoreFile {
     entries: Vec<Entry>,
+    problems: Vec<ObjectProblem>,
 
     /// The value is the index of the entry in the `entries` vector
     uuid_lookup: HashMap<Uuid, usize>,
@@ -39,53 +129,75 @@ pub struct CoreFile {
 impl CoreFile {
     pub fn from_file(file: &mut File, context: &mut LoadContext) -> Result<Self, anyhow::Error> {
         let mut entries = Vec::new();
-        let mut reader = BinaryReader::from_file(file);
-        reader.set_endian(binary_reader::Endian::Little);
-
-        while reader.pos < reader.length {
-            let hash = reader.read_u64()?;
-            let len = reader.read_u32()? as usize;
-            let obj_type_name = RTTITypeHash::from_int(hash)
-                .map(|ty| format!("{:?}", ty))
-                .unwrap_or_else(|_| format!("0x{:X}", hash));
-
-            if reader.pos + len > reader.length {
-                anyhow::bail!("Unexpected end of file");
+        let mut problems = Vec::new();
+
+        for object in read_raw_objects(file)? {
+            let hash = object.type_hash;
+            let offset = object.offset;
+
+            if RTTITypeHash::from_int(hash).is_err() {
+                problems.push(ObjectProblem {
+                    type_hash: hash,
+                    offset,
+                    kind: ObjectProblemKind::UnknownType,
+                });
+                continue;
             }
 
-            let slice = reader.read_bytes(len)?;
-            let mut slice_reader = BinaryReader::from_u8(slice);
+            let mut slice_reader = BinaryReader::from_u8(&object.data);
             slice_reader.set_endian(binary_reader::Endian::Little);
 
-            match read_object(hash, &mut slice_reader, context) {
+            let result = read_object(hash, &mut slice_reader, context);
+            let checksum_mismatches = context.take_checksum_mismatches();
+
+            match result {
                 Ok(obj) => {
                     let uuid = *obj.object_uuid();
 
+                    problems.extend(checksum_mismatches.into_iter().map(|mismatch| {
+                        ObjectProblem {
+                            type_hash: hash,
+                            offset,
+                            kind: ObjectProblemKind::StringChecksumMismatch {
+                                expected: mismatch.expected,
+                                actual: mismatch.actual,
+                            },
+                        }
+                    }));
+
                     entries.push(Entry {
                         type_hash: hash,
                         uuid,
                         value: obj,
                     });
+
+                    // The object is still used since the unread data is usually fields that aren't read yet
+                    if slice_reader.pos != slice_reader.length {
+                        problems.push(ObjectProblem {
+                            type_hash: hash,
+                            offset,
+                            kind: ObjectProblemKind::NotFullyRead {
+                                num_unread_bytes: slice_reader.length - slice_reader.pos,
+                            },
+                        });
+                    }
                 }
                 Err(err) => {
-                    // Don't log unknown type hashes to avoid log spam
-                    if !err.to_string().contains("Unknown RTTI type hash") {
-                        println!(
-                            "Could not read object {obj_type_name} from {}: {err} (offset after read: {})",
-                            context.current_file_path().expect("Current file path should always be Some"),
-                            (reader.pos - slice_reader.length) + slice_reader.pos
-                        );
-                    }
+                    problems.push(ObjectProblem {
+                        type_hash: hash,
+                        offset,
+                        kind: ObjectProblemKind::ReadFailed {
+                            error: err.to_string(),
+                            read_offset: object.data_offset() + slice_reader.pos,
+                        },
+                    });
                 }
             }
         }
 
-        if reader.pos != reader.length {
-            anyhow::bail!("Expected end of file");
-        }
-
         let mut result = CoreFile {
             entries,
+            problems,
             uuid_lookup: HashMap::new(),
             hash_lookup: HashMap::new(),
         };
@@ -93,6 +205,11 @@ impl CoreFile {
         Ok(result)
     }
 
+    /// The objects that could not be read, were not read completely or have strings with mismatching checksums.
+    pub fn problems(&self) -> &[ObjectProblem] {
+        &self.problems
+    }
+
     pub fn find_object<T: ReadRTTIType>(
         &self,
         uuid: &Uuid,
@@ -189,6 +306,6 @@ fn read_object(
             let item = LostBaggageWithNameAndIconListResource::read(reader, context)?;
             Ok(RTTIType::LostBaggageWithNameAndIconListResource(item))
         }
-        _ => anyhow::bail!("Unknown RTTI type hash"),
+        _ => anyhow::bail!("Unknown RTTI type hash 0x{hash:X}"),
     }
 }
@@ -1,8 +1,10 @@
 use std::ops::{Deref, DerefMut};
 
+use serde::{Deserialize, Serialize};
+
 use super::{rtti_ref_object::RTTIRefObject, LoadContext};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoreObject {
     base: RTTIRefObject,
 }
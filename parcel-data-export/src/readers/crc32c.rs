@@ -0,0 +1,61 @@
+//! The CRC32C variant the game uses for checksums, such as the one stored before every string.
+//!
+//! It uses the Castagnoli polynomial like the standard CRC32C, but without inverting the initial value and the result.
+//! This hasn't been checked against a string from a .core file yet, so strings with a mismatching checksum are still read
+//! and the mismatch is only reported by `--verify`.
+
+const POLYNOMIAL: u32 = 0x82F63B78;
+
+const TABLE: [u32; 256] = build_table();
+
+const fn build_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+}
+
+pub fn checksum(data: &[u8]) -> u32 {
+    update(0, data)
+}
+
+fn update(crc: u32, data: &[u8]) -> u32 {
+    data.iter().fold(crc, |crc, byte| {
+        TABLE[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_standard_crc32c_when_inverted() {
+        // The check value from the CRC catalogue, which inverts the initial value and the result
+        assert_eq!(!update(!0, b"123456789"), 0xE3069283);
+    }
+
+    #[test]
+    fn does_not_invert_the_initial_value_or_the_result() {
+        assert_eq!(checksum(b""), 0);
+        assert_eq!(checksum(&[0; 16]), 0);
+        assert_ne!(checksum(b"123456789"), 0xE3069283);
+    }
+}
@@ -2,6 +2,7 @@ use std::ops::{Deref, DerefMut};
 
 use int_enum::IntEnum;
 use parcel_game_data::{Area, ConstructionPointType};
+use serde::{Deserialize, Serialize};
 
 use super::{
     array::Array,
@@ -31,7 +32,7 @@ impl super::Read for ConstructionPointType {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorldTransform {
     pub position: (f64, f64, f64),
     pub orientation: Vec<u8>, // RotMatrix (36 bytes)
@@ -52,7 +53,7 @@ impl super::Read for WorldTransform {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeliveryPointInfoResource {
     base: MissionStaticAbstractPointResource,
     pub description_text: Ref<LocalizedTextResource>,
@@ -1,6 +1,7 @@
 use std::ops::{Deref, DerefMut};
 
 use parcel_game_data::EquipmentType;
+use serde::{Deserialize, Serialize};
 
 use super::game_list_item_base_with_icon::GameListItemBaseWithIcon;
 
@@ -18,7 +19,7 @@ impl super::Read for EquipmentType {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EquipmentListItem {
     base: GameListItemBaseWithIcon,
     pub equipment_type: EquipmentType,
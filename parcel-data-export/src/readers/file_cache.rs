@@ -0,0 +1,100 @@
+//! Stores decoded .core files on disk, so that files that haven't changed since the last export don't have to be decoded again.
+
+use std::{
+    fs::{File, Metadata},
+    io::{BufReader, BufWriter, Write},
+    path::PathBuf,
+    time::SystemTime,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::core_file::CoreFile;
+
+/// Increase this if the layout of the cache files changes.
+///
+/// It's stored before the header so that files with an older header layout are never decoded as the current one.
+const CACHE_VERSION: u32 = 2;
+
+/// A hash of the reader code generated by the build script, so that files decoded by an older reader are decoded again
+/// without having to remember to increase the cache version.
+const READERS_HASH: &str = env!("READERS_HASH");
+
+/// Stored before the decoded file to check if the cached file can be used.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct CacheHeader {
+    package_version: String,
+    readers_hash: String,
+    modified_at: SystemTime,
+    file_size: u64,
+}
+
+impl CacheHeader {
+    fn new(source_metadata: &Metadata) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            package_version: env!("CARGO_PKG_VERSION").to_owned(),
+            readers_hash: READERS_HASH.to_owned(),
+            modified_at: source_metadata.modified()?,
+            file_size: source_metadata.len(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FileCache {
+    directory: PathBuf,
+}
+
+impl FileCache {
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    /// Returns the cached file if the source file hasn't been modified since it was cached.
+    ///
+    /// Cache files that can't be read are treated as missing, they are replaced the next time the file is cached.
+    pub fn get(&self, path_str: &str, source_metadata: &Metadata) -> Option<CoreFile> {
+        let expected_header = CacheHeader::new(source_metadata).ok()?;
+        let file = File::open(self.get_cache_path(path_str)).ok()?;
+        let mut reader = BufReader::new(file);
+
+        let version: u32 = bincode::deserialize_from(&mut reader).ok()?;
+
+        if version != CACHE_VERSION {
+            return None;
+        }
+
+        let header: CacheHeader = bincode::deserialize_from(&mut reader).ok()?;
+
+        if header != expected_header {
+            return None;
+        }
+
+        bincode::deserialize_from(&mut reader).ok()
+    }
+
+    pub fn insert(
+        &self,
+        path_str: &str,
+        source_metadata: &Metadata,
+        core_file: &CoreFile,
+    ) -> Result<(), anyhow::Error> {
+        let cache_path = self.get_cache_path(path_str);
+
+        if let Some(parent) = cache_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut writer = BufWriter::new(File::create(cache_path)?);
+        bincode::serialize_into(&mut writer, &CACHE_VERSION)?;
+        bincode::serialize_into(&mut writer, &CacheHeader::new(source_metadata)?)?;
+        bincode::serialize_into(&mut writer, core_file)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    fn get_cache_path(&self, path_str: &str) -> PathBuf {
+        self.directory.join(format!("{path_str}.bin"))
+    }
+}
@@ -1,10 +1,12 @@
 use std::ops::{Deref, DerefMut};
 
+use serde::{Deserialize, Serialize};
+
 use super::{
     localized_text_resource::LocalizedTextResource, reference::Ref, resource::Resource, LoadContext,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameListItemBase {
     base: Resource,
     pub localized_name: Ref<LocalizedTextResource>,
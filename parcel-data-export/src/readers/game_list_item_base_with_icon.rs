@@ -1,10 +1,11 @@
 use std::ops::{Deref, DerefMut};
 
 use binary_reader::BinaryReader;
+use serde::{Deserialize, Serialize};
 
 use super::{game_list_item_base::GameListItemBase, string::DSString, LoadContext};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum ListItemColor {
     Red,
@@ -29,7 +30,7 @@ impl super::Read for ListItemColor {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameListItemBaseWithIcon {
     base: GameListItemBase,
     pub ui_texture_base_name: String,
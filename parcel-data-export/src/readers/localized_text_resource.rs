@@ -5,16 +5,17 @@ use std::{
 
 use enum_iterator::all;
 use parcel_game_data::Language;
+use serde::{Deserialize, Serialize};
 
 use super::{resource::Resource, LoadContext};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LocalizedTextResource {
     base: Resource,
     pub languages: HashMap<Language, DSLocalizedText>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DSLocalizedText {
     pub text: String,
     pub notes: String,
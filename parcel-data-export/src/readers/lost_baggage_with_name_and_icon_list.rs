@@ -1,4 +1,5 @@
 use parcel_game_data::Area;
+use serde::{Deserialize, Serialize};
 
 use super::{
     array::Array, baggage_list_item::BaggageListItem,
@@ -6,7 +7,7 @@ use super::{
     RTTITypeHash,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LostBaggageWithNameAndIconListResource {
     base: Resource,
     pub destination: Ref<DeliveryPointInfoResource>,
@@ -50,7 +51,7 @@ impl std::ops::DerefMut for LostBaggageWithNameAndIconListResource {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LostBaggageWithNameAndIconListCollection {
     base: Resource,
     pub area: Area,
@@ -1,8 +1,13 @@
 use std::ops::{Deref, DerefMut};
 
+use serde::{Deserialize, Serialize};
+
 use super::{mission_abstract_point_resource::MissionAbstractPointResource, LoadContext};
 
-#[derive(Debug, Clone)]
+/// The base of the points that missions start and end at, such as delivery points. It has no fields of its own.
+///
+/// The missions themselves are read with the schema, see missions::read_missions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MissionStaticAbstractPointResource {
     base: MissionAbstractPointResource,
 }
@@ -1,20 +1,24 @@
 use std::{
     collections::HashMap,
+    ffi::OsStr,
     fs::File,
     path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
 use anyhow::Context;
 use binary_reader::BinaryReader;
 use int_enum::IntEnum;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use self::{
     baggage_list_item::BaggageListItem,
     commodity_list_item::CommodityListItem,
-    core_file::CoreFile,
+    core_file::{CoreFile, ObjectProblemKind, RawObject},
     delivery_point_info_resource::DeliveryPointInfoResource,
     equipment_list_item::EquipmentListItem,
+    file_cache::FileCache,
     localized_text_resource::LocalizedTextResource,
     lost_baggage_with_name_and_icon_list::{
         LostBaggageWithNameAndIconListCollection, LostBaggageWithNameAndIconListResource,
@@ -28,8 +32,10 @@ pub mod baggage_list_item;
 pub mod commodity_list_item;
 pub mod core_file;
 pub mod core_object;
+pub mod crc32c;
 pub mod delivery_point_info_resource;
 pub mod equipment_list_item;
+pub mod file_cache;
 pub mod game_list_item_base;
 pub mod game_list_item_base_with_icon;
 pub mod localized_text_resource;
@@ -41,6 +47,7 @@ pub mod reference;
 pub mod resource;
 pub mod rtti_object;
 pub mod rtti_ref_object;
+pub mod schema;
 pub mod string;
 pub mod weapon_list_item;
 
@@ -66,7 +73,7 @@ pub enum RTTITypeHash {
     LostBaggageWithNameAndIconListCollection = 0x55CFF394707F7123,
 }
 
-#[derive(Debug, Clone, enum_as_inner::EnumAsInner)]
+#[derive(Debug, Clone, enum_as_inner::EnumAsInner, Serialize, Deserialize)]
 pub enum RTTIType {
     RawMaterialListItem(RawMaterialListItem),
     CommodityListItem(CommodityListItem),
@@ -97,7 +104,19 @@ impl RTTIType {
 pub struct LoadContext {
     base_directory: PathBuf,
     files: HashMap<String, CoreFile>,
+    /// Files that were loaded without decoding the objects, see `load_raw_file`
+    raw_files: HashMap<String, Vec<RawObject>>,
     load_queue: Vec<String>,
+    cache: Option<FileCache>,
+    /// Strings in the object that is being read whose checksum didn't match, see `record_checksum_mismatch`
+    checksum_mismatches: Vec<ChecksumMismatch>,
+}
+
+/// A checksum that was stored in a file and the checksum of the data it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChecksumMismatch {
+    pub expected: u32,
+    pub actual: u32,
 }
 
 impl LoadContext {
@@ -105,10 +124,31 @@ impl LoadContext {
         Self {
             base_directory: data_directory,
             files: HashMap::new(),
+            raw_files: HashMap::new(),
             load_queue: Vec::new(),
+            cache: None,
+            checksum_mismatches: Vec::new(),
         }
     }
 
+    /// Records that the checksum of a string in the object that is being read didn't match.
+    ///
+    /// The string is still used, since the checksum variant hasn't been verified against every file yet.
+    /// The mismatches are added to the problems of the file, which are printed by `--verify`.
+    pub fn record_checksum_mismatch(&mut self, mismatch: ChecksumMismatch) {
+        self.checksum_mismatches.push(mismatch);
+    }
+
+    /// Returns the checksum mismatches that were recorded since the last call.
+    pub fn take_checksum_mismatches(&mut self) -> Vec<ChecksumMismatch> {
+        std::mem::take(&mut self.checksum_mismatches)
+    }
+
+    /// Decoded files are read from and saved to the cache, instead of decoding files again if they haven't changed.
+    pub fn set_cache(&mut self, cache: FileCache) {
+        self.cache = Some(cache);
+    }
+
     pub fn get_absolute_path(&self, path: &Path) -> PathBuf {
         self.base_directory.join(path)
     }
@@ -127,13 +167,7 @@ impl LoadContext {
     }
 
     pub fn load_file(&mut self, path: &Path) -> Result<&CoreFile, anyhow::Error> {
-        let mut path = path.to_owned();
-
-        if path.extension().is_none() {
-            path = path.with_extension("core");
-        }
-
-        let path_str = path.to_string_lossy().into_owned();
+        let path_str = get_path_str(path);
 
         // check if file is already loaded
         if self.files.contains_key(&path_str) {
@@ -143,39 +177,166 @@ impl LoadContext {
                 .expect("File should always be found"));
         }
 
+        let file = self.read_file(path)?;
+        log_problems(&path_str, &file);
+        self.files.insert(path_str.clone(), file);
+
+        let result = self
+            .files
+            .get(&path_str)
+            .expect("File should always be found");
+
+        Ok(result)
+    }
+
+    /// Loads the files that aren't loaded yet on multiple threads.
+    ///
+    /// Files that fail to load are skipped, the error is returned when they are loaded with `load_file`.
+    pub fn preload_files(&mut self, paths: &[PathBuf]) {
+        let path_strs = paths
+            .iter()
+            .map(|path| get_path_str(path))
+            .filter(|path_str| !self.files.contains_key(path_str))
+            .collect::<Vec<_>>();
+        let num_threads = std::thread::available_parallelism()
+            .map(|num| num.get())
+            .unwrap_or(1)
+            .min(path_strs.len());
+        let next_index = AtomicUsize::new(0);
+        let base_directory = &self.base_directory;
+        let cache = &self.cache;
+
+        let loaded_files = std::thread::scope(|scope| {
+            let threads = (0..num_threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        // Decoding a file doesn't load other files, so every thread can use its own context
+                        let mut context = LoadContext::new(base_directory.clone());
+                        context.cache = cache.clone();
+                        let mut loaded_files = Vec::new();
+
+                        while let Some(path_str) =
+                            path_strs.get(next_index.fetch_add(1, Ordering::Relaxed))
+                        {
+                            if let Ok(file) = context.read_file(Path::new(path_str)) {
+                                loaded_files.push((path_str.clone(), file));
+                            }
+                        }
+
+                        loaded_files
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            threads
+                .into_iter()
+                .flat_map(|thread| thread.join().expect("Loading thread should never panic"))
+                .collect::<Vec<_>>()
+        });
+
+        for (path_str, file) in loaded_files {
+            log_problems(&path_str, &file);
+            self.files.insert(path_str, file);
+        }
+    }
+
+    /// Reads the file without keeping it loaded. Problems with the objects in the file are not logged.
+    ///
+    /// The file is read from the cache if it's cached and hasn't changed, otherwise it's decoded and saved to the cache.
+    pub fn read_file(&mut self, path: &Path) -> Result<CoreFile, anyhow::Error> {
+        let path_str = get_path_str(path);
+        let file_path = self.base_directory.join(&path_str);
+        let metadata = std::fs::metadata(&file_path)
+            .with_context(|| format!("Loading file: {file_path:?}"))?;
+
+        if let Some(file) = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.get(&path_str, &metadata))
+        {
+            return Ok(file);
+        }
+
         self.load_queue.push(path_str.clone());
 
-        let load_result = || -> anyhow::Result<_> {
-            let file_path = self.base_directory.join(path);
+        let read_result = || -> anyhow::Result<_> {
             let mut file = File::open(file_path.clone())
                 .with_context(|| format!("Loading file: {file_path:?}"))?;
-            let file = CoreFile::from_file(&mut file, self)?;
 
-            self.files.insert(path_str.clone(), file);
-            Ok(())
+            CoreFile::from_file(&mut file, self)
         }();
 
         self.load_queue.pop(); // always pop current file regardless of success
 
-        load_result?;
+        let file = read_result?;
 
-        let result = self
-            .files
-            .get(&path_str)
-            .expect("File should always be found");
+        if let Some(cache) = &self.cache {
+            if let Err(err) = cache.insert(&path_str, &metadata, &file) {
+                println!("Could not cache {path_str}: {err}");
+            }
+        }
 
-        Ok(result)
+        Ok(file)
+    }
+
+    /// Loads the objects in the file without decoding them, if the file is not loaded already.
+    pub fn load_raw_file(&mut self, path: &Path) -> Result<&[RawObject], anyhow::Error> {
+        let path_str = get_path_str(path);
+
+        if !self.raw_files.contains_key(&path_str) {
+            let file_path = self.base_directory.join(&path_str);
+            let mut file = File::open(file_path.clone())
+                .with_context(|| format!("Loading file: {file_path:?}"))?;
+            let objects = core_file::read_raw_objects(&mut file)?;
+
+            self.raw_files.insert(path_str.clone(), objects);
+        }
+
+        Ok(self
+            .raw_files
+            .get(&path_str)
+            .expect("File should always be found"))
     }
 
     pub fn get_file(&self, path: &Path) -> Option<&CoreFile> {
-        let mut path = path.to_owned();
+        self.files.get(&get_path_str(path))
+    }
+}
+
+fn log_problems(path_str: &str, file: &CoreFile) {
+    for problem in file.problems() {
+        if let ObjectProblemKind::ReadFailed { error, read_offset } = &problem.kind {
+            println!(
+                "Could not read object {} from {path_str}: {error} (offset after read: {read_offset})",
+                problem.type_name()
+            );
+        }
+    }
+}
 
-        if path.extension().is_none() {
-            path = path.with_extension("core");
+/// Adds the paths of the .core files in the directory and its subdirectories to `file_paths`.
+pub fn find_core_files(dir: &Path, file_paths: &mut Vec<PathBuf>) -> Result<(), anyhow::Error> {
+    for entry in dir.read_dir()? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            find_core_files(&entry.path(), file_paths)?;
+        } else if file_type.is_file() && entry.path().extension() == Some(OsStr::new("core")) {
+            file_paths.push(entry.path());
         }
+    }
 
-        let path_str = path.to_string_lossy().into_owned();
+    Ok(())
+}
+
+/// Returns the path as a string with the '.core' extension added if it doesn't have one.
+pub fn get_path_str(path: &Path) -> String {
+    let mut path = path.to_owned();
 
-        self.files.get(&path_str)
+    if path.extension().is_none() {
+        path = path.with_extension("core");
     }
+
+    path.to_string_lossy().into_owned()
 }
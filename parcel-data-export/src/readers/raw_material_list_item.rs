@@ -1,10 +1,11 @@
 use std::ops::{Deref, DerefMut};
 
 use parcel_game_data::RawMaterialType;
+use serde::{Deserialize, Serialize};
 
 use super::{game_list_item_base_with_icon::GameListItemBaseWithIcon, LoadContext};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RawMaterialListItem {
     base: GameListItemBaseWithIcon,
     pub raw_material_type: RawMaterialType,
@@ -1,10 +1,12 @@
 use std::{marker::PhantomData, ops::Deref, path::PathBuf};
 
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::{string::DSString, LoadContext, RTTIType, Read, ReadRTTIType};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct Ref<T: Sized + Read + ReadRTTIType> {
     inner: Option<(Uuid, PathBuf)>,
     _data_type: PhantomData<T>,
@@ -99,7 +101,7 @@ impl<T: Sized + Read + ReadRTTIType> super::Read for Ref<T> {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnresolvedRef {
     pub uuid: Option<Uuid>,
     pub path: Option<String>,
@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use super::LoadContext;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RTTIObject;
 
 impl super::Read for RTTIObject {
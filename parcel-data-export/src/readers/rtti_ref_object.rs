@@ -1,10 +1,11 @@
 use std::ops::{Deref, DerefMut};
 
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::{rtti_object::RTTIObject, LoadContext};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RTTIRefObject {
     base: RTTIObject,
     pub object_uuid: Uuid,
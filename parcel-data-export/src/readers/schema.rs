@@ -0,0 +1,412 @@
+//! Type layouts that are declared in a json file instead of in code, so that new types can be read without writing a reader for them.
+//!
+//! The file contains a map of type names to their layout, for example:
+//!
+//! ```json
+//! {
+//!     "types": {
+//!         "RTTIRefObject": { "fields": [{ "name": "ObjectUUID", "type": "uuid" }] },
+//!         "BaggageListItem": {
+//!             "hash": "0x72CB5ED4F1C815EE",
+//!             "base": "GameListItemBase",
+//!             "fields": [{ "name": "Contents", "type": "ref" }, { "name": "Amount", "type": "u32" }]
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! The fields of the base type are read before the fields of the type itself. Types without a hash can only be used as a base or field type.
+//!
+//! Field types can be one of the primitives `bool`, `u8`, `i8`, `u16`, `i16`, `u32`, `i32`, `u64`, `i64`, `f32` and `f64`,
+//! `string` (a string with a checksum), `shortString` (a string with a 16 bit length and no checksum), `uuid`, `ref` (a link or reference to another object),
+//! `array<T>` (an array with a 32 bit length), `T[N]` (N values without a length), or the name of another type in the file to read it inline.
+//!
+//! `schema.json` in the crate directory contains the layouts of the types that have a reader, and can be used as a starting point.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    str::FromStr,
+};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct SchemaFile {
+    types: BTreeMap<String, TypeDefinition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TypeDefinition {
+    /// The RTTI type hash as hex, optionally prefixed with 0x
+    hash: Option<String>,
+    base: Option<String>,
+    #[serde(default)]
+    fields: Vec<FieldDefinition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FieldDefinition {
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldType {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    F32,
+    F64,
+    String,
+    ShortString,
+    Uuid,
+    Ref,
+    Array(Box<FieldType>),
+    FixedArray(Box<FieldType>, usize),
+    /// Another type in the schema that's read inline
+    Type(String),
+}
+
+impl FromStr for FieldType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Some(element_type) = s.strip_prefix("array<").and_then(|s| s.strip_suffix('>')) {
+            return Ok(Self::Array(Box::new(element_type.parse()?)));
+        }
+
+        if let Some((element_type, len)) = s.strip_suffix(']').and_then(|s| s.rsplit_once('[')) {
+            let len = len
+                .parse()
+                .with_context(|| format!("Invalid array length in {s}"))?;
+
+            return Ok(Self::FixedArray(Box::new(element_type.parse()?), len));
+        }
+
+        Ok(match s {
+            "bool" => Self::Bool,
+            "u8" => Self::U8,
+            "i8" => Self::I8,
+            "u16" => Self::U16,
+            "i16" => Self::I16,
+            "u32" => Self::U32,
+            "i32" => Self::I32,
+            "u64" => Self::U64,
+            "i64" => Self::I64,
+            "f32" => Self::F32,
+            "f64" => Self::F64,
+            "string" => Self::String,
+            "shortString" => Self::ShortString,
+            "uuid" => Self::Uuid,
+            "ref" => Self::Ref,
+            "" => anyhow::bail!("Field type is empty"),
+            name => Self::Type(name.to_owned()),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TypeLayout {
+    pub name: String,
+    pub hash: Option<u64>,
+    pub base: Option<String>,
+    pub fields: Vec<(String, FieldType)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    types: HashMap<String, TypeLayout>,
+    /// The value is the name of the type with the hash
+    type_names: HashMap<u64, String>,
+}
+
+impl Schema {
+    pub fn load(file_path: &Path) -> Result<Self, anyhow::Error> {
+        let bytes = std::fs::read(file_path)
+            .with_context(|| format!("Loading schema file: {file_path:?}"))?;
+        let file: SchemaFile = serde_json::from_slice(&bytes)?;
+
+        Self::from_definitions(file.types)
+    }
+
+    fn from_definitions(
+        definitions: BTreeMap<String, TypeDefinition>,
+    ) -> Result<Self, anyhow::Error> {
+        let mut schema = Self::default();
+
+        for (name, definition) in definitions {
+            let hash = definition
+                .hash
+                .as_deref()
+                .map(|hash| {
+                    u64::from_str_radix(hash.trim_start_matches("0x"), 16)
+                        .with_context(|| format!("Invalid hash for type {name}: {hash}"))
+                })
+                .transpose()?;
+            let fields = definition
+                .fields
+                .into_iter()
+                .map(|field| {
+                    let ty = field
+                        .ty
+                        .parse()
+                        .with_context(|| format!("Invalid type for field {name}.{}", field.name))?;
+
+                    Ok((field.name, ty))
+                })
+                .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+            if let Some(hash) = hash {
+                if let Some(other) = schema.type_names.insert(hash, name.clone()) {
+                    anyhow::bail!("Types {other} and {name} have the same hash");
+                }
+            }
+
+            schema.types.insert(
+                name.clone(),
+                TypeLayout {
+                    name,
+                    hash,
+                    base: definition.base,
+                    fields,
+                },
+            );
+        }
+
+        schema.validate()?;
+
+        Ok(schema)
+    }
+
+    /// Checks that every base and field type exists, and that no type contains itself.
+    ///
+    /// A type can only refer to itself through a variable length array or a ref, since embedding it directly or in a fixed size array would make it infinitely large.
+    fn validate(&self) -> Result<(), anyhow::Error> {
+        for layout in self.types.values() {
+            let mut visited = vec![layout.name.as_str()];
+            let mut base = layout.base.as_deref();
+
+            while let Some(base_name) = base {
+                if visited.contains(&base_name) {
+                    anyhow::bail!("Type {} inherits from itself", layout.name);
+                }
+
+                visited.push(base_name);
+                base = self
+                    .get(base_name)
+                    .with_context(|| format!("Unknown base type {base_name} of {}", layout.name))?
+                    .base
+                    .as_deref();
+            }
+        }
+
+        // Bases are checked first since all_fields requires them to exist
+        for layout in self.types.values() {
+            for (field_name, ty) in &layout.fields {
+                self.validate_field_type(ty, &mut vec![layout.name.as_str()])
+                    .with_context(|| format!("Invalid field {}.{field_name}", layout.name))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_field_type<'a>(
+        &'a self,
+        ty: &'a FieldType,
+        containing_types: &mut Vec<&'a str>,
+    ) -> Result<(), anyhow::Error> {
+        match ty {
+            // The elements are stored separately, so the fields of the element type are validated on their own
+            FieldType::Array(element_type) => self.validate_type_exists(element_type),
+            FieldType::FixedArray(element_type, _) => {
+                self.validate_field_type(element_type, containing_types)
+            }
+            FieldType::Type(name) => {
+                if containing_types.contains(&name.as_str()) {
+                    anyhow::bail!("Type {name} contains itself");
+                }
+
+                let layout = self
+                    .get(name)
+                    .with_context(|| format!("Unknown type {name}"))?;
+
+                containing_types.push(name);
+
+                for (_, field_type) in self.all_fields(layout) {
+                    self.validate_field_type(field_type, containing_types)?;
+                }
+
+                containing_types.pop();
+
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn validate_type_exists(&self, ty: &FieldType) -> Result<(), anyhow::Error> {
+        match ty {
+            FieldType::Array(element_type) | FieldType::FixedArray(element_type, _) => {
+                self.validate_type_exists(element_type)
+            }
+            FieldType::Type(name) => self
+                .get(name)
+                .map(|_| ())
+                .with_context(|| format!("Unknown type {name}")),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&TypeLayout> {
+        self.types.get(name)
+    }
+
+    pub fn get_by_hash(&self, hash: u64) -> Option<&TypeLayout> {
+        self.type_names.get(&hash).and_then(|name| self.get(name))
+    }
+
+    /// Returns the fields of the type including the fields of its base types, in the order they are stored.
+    pub fn all_fields(&self, layout: &TypeLayout) -> Vec<&(String, FieldType)> {
+        let mut chain = vec![layout];
+
+        while let Some(base) = chain.last().and_then(|layout| layout.base.as_deref()) {
+            chain.push(self.get(base).expect("Base type should always exist"));
+        }
+
+        chain
+            .into_iter()
+            .rev()
+            .flat_map(|layout| layout.fields.iter())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema_from_json(json: serde_json::Value) -> Result<Schema, anyhow::Error> {
+        let file: SchemaFile = serde_json::from_value(json)?;
+
+        Schema::from_definitions(file.types)
+    }
+
+    #[test]
+    fn parses_field_types() {
+        assert_eq!("u32".parse::<FieldType>().unwrap(), FieldType::U32);
+        assert_eq!(
+            "array<ref>".parse::<FieldType>().unwrap(),
+            FieldType::Array(Box::new(FieldType::Ref))
+        );
+        assert_eq!(
+            "f32[3]".parse::<FieldType>().unwrap(),
+            FieldType::FixedArray(Box::new(FieldType::F32), 3)
+        );
+        assert_eq!(
+            "array<Vec3[2]>".parse::<FieldType>().unwrap(),
+            FieldType::Array(Box::new(FieldType::FixedArray(
+                Box::new(FieldType::Type("Vec3".into())),
+                2
+            )))
+        );
+        assert!("".parse::<FieldType>().is_err());
+        assert!("u8[x]".parse::<FieldType>().is_err());
+    }
+
+    #[test]
+    fn reads_base_fields_first() {
+        let schema = schema_from_json(serde_json::json!({
+            "types": {
+                "Base": { "fields": [{ "name": "Uuid", "type": "uuid" }] },
+                "Item": {
+                    "hash": "0x72CB5ED4F1C815EE",
+                    "base": "Base",
+                    "fields": [{ "name": "Amount", "type": "u32" }]
+                }
+            }
+        }))
+        .unwrap();
+
+        let layout = schema.get_by_hash(0x72CB5ED4F1C815EE).unwrap();
+        let fields = schema
+            .all_fields(layout)
+            .into_iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(layout.name, "Item");
+        assert_eq!(fields, ["Uuid", "Amount"]);
+    }
+
+    #[test]
+    fn rejects_unknown_types() {
+        let error = schema_from_json(serde_json::json!({
+            "types": { "Item": { "base": "Missing" } }
+        }))
+        .unwrap_err();
+        assert!(format!("{error:#}").contains("Unknown base type Missing of Item"));
+
+        let error = schema_from_json(serde_json::json!({
+            "types": { "Item": { "fields": [{ "name": "Value", "type": "array<Missing>" }] } }
+        }))
+        .unwrap_err();
+        assert!(format!("{error:#}").contains("Unknown type Missing"));
+    }
+
+    #[test]
+    fn rejects_cycles() {
+        let error = schema_from_json(serde_json::json!({
+            "types": {
+                "A": { "base": "B" },
+                "B": { "base": "A" }
+            }
+        }))
+        .unwrap_err();
+        assert!(format!("{error:#}").contains("inherits from itself"));
+
+        // Containment through a base type and a fixed size array is still a cycle
+        let error = schema_from_json(serde_json::json!({
+            "types": {
+                "Base": { "fields": [{ "name": "Child", "type": "Node[2]" }] },
+                "Node": { "base": "Base" }
+            }
+        }))
+        .unwrap_err();
+        assert!(format!("{error:#}").contains("Node contains itself"));
+    }
+
+    #[test]
+    fn allows_self_references_in_arrays_and_refs() {
+        let schema = schema_from_json(serde_json::json!({
+            "types": {
+                "Node": {
+                    "fields": [
+                        { "name": "Children", "type": "array<Node>" },
+                        { "name": "Pairs", "type": "array<Node[2]>" },
+                        { "name": "Parent", "type": "ref" }
+                    ]
+                },
+                "Tree": { "fields": [{ "name": "Root", "type": "Node" }] }
+            }
+        }));
+        assert!(schema.is_ok(), "{:#}", schema.unwrap_err());
+
+        let error = schema_from_json(serde_json::json!({
+            "types": { "Node": { "fields": [{ "name": "Children", "type": "array<Missing>" }] } }
+        }))
+        .unwrap_err();
+        assert!(format!("{error:#}").contains("Unknown type Missing"));
+    }
+}
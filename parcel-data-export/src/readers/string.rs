@@ -1,6 +1,6 @@
 use std::ops::{Deref, DerefMut};
 
-use super::LoadContext;
+use super::{crc32c, ChecksumMismatch, LoadContext};
 
 #[derive(Debug, Clone)]
 pub struct DSString(String);
@@ -8,16 +8,23 @@ pub struct DSString(String);
 impl super::Read for DSString {
     fn read(
         reader: &mut binary_reader::BinaryReader,
-        _: &mut LoadContext,
+        context: &mut LoadContext,
     ) -> Result<Self, anyhow::Error> {
         let len = reader.read_u32()?;
 
         if len > 0 {
-            let _crc = reader.read_u32();
+            let expected_crc = reader.read_u32()?;
             let utf8_bytes = reader.read_bytes(len as usize)?.to_vec();
-            let str = String::from_utf8(utf8_bytes)?;
+            let crc = crc32c::checksum(&utf8_bytes);
+
+            if crc != expected_crc {
+                context.record_checksum_mismatch(ChecksumMismatch {
+                    expected: expected_crc,
+                    actual: crc,
+                });
+            }
 
-            // todo: validate crc32c (uses a custom implementation)
+            let str = String::from_utf8(utf8_bytes)?;
 
             Ok(Self(str))
         } else {
@@ -45,3 +52,53 @@ impl From<DSString> for String {
         val.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use binary_reader::{BinaryReader, Endian};
+
+    use super::*;
+    use crate::readers::Read;
+
+    fn read_string(data: &[u8], context: &mut LoadContext) -> DSString {
+        let mut reader = BinaryReader::from_u8(data);
+        reader.set_endian(Endian::Little);
+
+        DSString::read(&mut reader, context).unwrap()
+    }
+
+    fn string_bytes(value: &str, crc: u32) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend((value.len() as u32).to_le_bytes());
+        data.extend(crc.to_le_bytes());
+        data.extend(value.as_bytes());
+        data
+    }
+
+    #[test]
+    fn reads_strings_with_matching_checksums() {
+        let mut context = LoadContext::new(PathBuf::new());
+        let data = string_bytes("Porter", crc32c::checksum(b"Porter"));
+
+        assert_eq!(&*read_string(&data, &mut context), "Porter");
+        assert!(context.take_checksum_mismatches().is_empty());
+    }
+
+    #[test]
+    fn records_checksum_mismatches_without_failing() {
+        let mut context = LoadContext::new(PathBuf::new());
+        let data = string_bytes("Porter", 0x12345678);
+
+        assert_eq!(&*read_string(&data, &mut context), "Porter");
+        assert_eq!(
+            context.take_checksum_mismatches(),
+            [ChecksumMismatch {
+                expected: 0x12345678,
+                actual: crc32c::checksum(b"Porter"),
+            }]
+        );
+        assert!(context.take_checksum_mismatches().is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 use std::ops::{Deref, DerefMut};
 
 use parcel_game_data::{WeaponCategory, WeaponType};
+use serde::{Deserialize, Serialize};
 
 use super::game_list_item_base_with_icon::GameListItemBaseWithIcon;
 
@@ -31,7 +32,7 @@ impl super::Read for WeaponCategory {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeaponListItem {
     pub base: GameListItemBaseWithIcon,
     pub weapon_type: WeaponType,
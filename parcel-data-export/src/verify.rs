@@ -0,0 +1,85 @@
+use std::{collections::HashMap, path::Path};
+
+use crate::readers::{core_file::ObjectProblemKind, find_core_files, LoadContext};
+
+/// Reads every .core file in the data directory and prints the objects that are corrupt or couldn't be read,
+/// followed by a summary of the unknown RTTI types ordered by how often they occur.
+///
+/// Returns an error if any file or object is corrupt.
+pub fn verify(load_context: &mut LoadContext) -> Result<(), anyhow::Error> {
+    let mut file_paths = Vec::new();
+    find_core_files(
+        &load_context.get_absolute_path(Path::new("")),
+        &mut file_paths,
+    )?;
+    file_paths.sort();
+
+    let mut num_corrupt_files = 0;
+    let mut num_corrupt_objects = 0;
+    let mut num_not_fully_read_objects = 0;
+    let mut num_checksum_mismatches = 0;
+    let mut unknown_types = HashMap::<u64, usize>::new();
+
+    for file_path in &file_paths {
+        let relative_path = load_context.get_relative_path(file_path)?.to_owned();
+        let path_str = relative_path.to_string_lossy();
+
+        let file = match load_context.read_file(&relative_path) {
+            Ok(file) => file,
+            Err(err) => {
+                println!("Corrupt file {path_str}: {err:#}");
+                num_corrupt_files += 1;
+                continue;
+            }
+        };
+
+        for problem in file.problems() {
+            let type_name = problem.type_name();
+            let offset = problem.offset;
+
+            match &problem.kind {
+                ObjectProblemKind::UnknownType => {
+                    println!("Unknown object {type_name} in {path_str} at offset 0x{offset:X}");
+                    *unknown_types.entry(problem.type_hash).or_default() += 1;
+                }
+                ObjectProblemKind::ReadFailed { error, read_offset } => {
+                    println!("Corrupt object {type_name} in {path_str} at offset 0x{offset:X}: {error} (offset after read: 0x{read_offset:X})");
+                    num_corrupt_objects += 1;
+                }
+                ObjectProblemKind::NotFullyRead { num_unread_bytes } => {
+                    println!("Object {type_name} in {path_str} at offset 0x{offset:X} has {num_unread_bytes} unread bytes");
+                    num_not_fully_read_objects += 1;
+                }
+                ObjectProblemKind::StringChecksumMismatch { expected, actual } => {
+                    println!("Object {type_name} in {path_str} at offset 0x{offset:X} has a string with checksum 0x{actual:08X} instead of 0x{expected:08X}");
+                    num_checksum_mismatches += 1;
+                }
+            }
+        }
+    }
+
+    let mut unknown_types = unknown_types.into_iter().collect::<Vec<_>>();
+    unknown_types.sort_by(|(hash_a, count_a), (hash_b, count_b)| {
+        count_b.cmp(count_a).then(hash_a.cmp(hash_b))
+    });
+
+    println!();
+    println!("Verified {} files", file_paths.len());
+    println!("Corrupt files: {num_corrupt_files}");
+    println!("Corrupt objects: {num_corrupt_objects}");
+    println!("Objects with unread data: {num_not_fully_read_objects}");
+    println!("String checksum mismatches: {num_checksum_mismatches}");
+    println!("Unknown RTTI types: {}", unknown_types.len());
+
+    for (hash, count) in &unknown_types {
+        println!("  0x{hash:016X}: {count}");
+    }
+
+    if num_corrupt_files > 0 || num_corrupt_objects > 0 {
+        anyhow::bail!(
+            "Found {num_corrupt_files} corrupt files and {num_corrupt_objects} corrupt objects"
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "ts")]
+use typescript_type_def::TypeDef;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Area, ConstructionPointType, Language};
+
+/// A place in the world where a structure, such as a bridge or a watch tower, can be built or is already placed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct ConstructionPoint {
+    /// The locator id of the construction point
+    pub construction_id: i32,
+    /// Empty if the game has no name for the construction point, which is the case for most structures
+    pub names: BTreeMap<Language, String>,
+    pub metadata: ConstructionPointMetaData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct ConstructionPointMetaData {
+    pub order_in_list: u32,
+    pub construction_type: ConstructionPointType,
+    pub area: Area,
+    pub location: (f64, f64, f64),
+}
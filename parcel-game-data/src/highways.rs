@@ -0,0 +1,40 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "ts")]
+use typescript_type_def::TypeDef;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Area, Language};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct HighwaySegment {
+    /// The id the game uses for the segment when devoting resources to it
+    pub construction_id: i32,
+    pub names: BTreeMap<Language, String>,
+    pub metadata: HighwaySegmentMetaData,
+    /// The number of resources needed to finish the segment, keyed by resource id.
+    /// Empty if the requirements are unknown.
+    #[serde(default)]
+    pub required_resources: BTreeMap<i16, i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct HighwaySegmentMetaData {
+    pub order_in_list: u32,
+    pub area: Area,
+    pub location: (f64, f64, f64),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct HighwayResource {
+    /// The id the game uses for the resource when devoting it to a highway segment
+    pub resource_id: i16,
+    pub names: BTreeMap<Language, String>,
+}
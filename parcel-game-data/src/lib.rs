@@ -1,11 +1,17 @@
 mod baggages;
+mod construction_points;
+mod highways;
 mod language;
+mod missions;
 mod qpid_areas;
 
 use std::collections::HashMap;
 
 pub use baggages::*;
+pub use construction_points::*;
+pub use highways::*;
 pub use language::*;
+pub use missions::*;
 pub use qpid_areas::*;
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +26,21 @@ pub struct ObjectMetaData {
 pub struct GameData {
     pub baggages: HashMap<u32, Baggage>,
     pub qpid_areas: HashMap<i32, QpidArea>,
+    /// The lost cargo that the game expects to be delivered to each delivery point, keyed by the qpid id of the delivery point.
+    /// The values are baggage name hashes that match keys in `baggages`.
+    #[serde(default)]
+    pub lost_baggages: HashMap<i32, Vec<u32>>,
+    #[serde(default)]
+    pub highway_segments: HashMap<i32, HighwaySegment>,
+    #[serde(default)]
+    pub highway_resources: HashMap<i16, HighwayResource>,
+    /// The construction points of structures, keyed by construction id.
+    /// Road rebuilders are not included since they are in `highway_segments`.
+    #[serde(default)]
+    pub construction_points: HashMap<i32, ConstructionPoint>,
+    /// The missions that are defined in the game, keyed by mission static id.
+    #[serde(default)]
+    pub missions: HashMap<i64, MissionDefinition>,
 }
 
 impl GameData {
@@ -36,4 +57,62 @@ impl GameData {
             .map(|a| a.names.get(&language))
             .flatten()
     }
+
+    /// Returns the name hashes of the lost cargo that belongs to the delivery point.
+    pub fn lost_baggages_for_destination(&self, qpid_id: i32) -> &[u32] {
+        self.lost_baggages
+            .get(&qpid_id)
+            .map(|name_hashes| name_hashes.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the qpid ids of the delivery points that the lost cargo belongs to, in ascending order.
+    /// Most lost cargo belongs to a single delivery point.
+    pub fn lost_baggage_destinations(&self, name_hash: u32) -> Vec<i32> {
+        let mut qpid_ids = self
+            .lost_baggages
+            .iter()
+            .filter(|(_, name_hashes)| name_hashes.contains(&name_hash))
+            .map(|(qpid_id, _)| *qpid_id)
+            .collect::<Vec<_>>();
+        qpid_ids.sort_unstable();
+
+        qpid_ids
+    }
+
+    pub fn highway_segment_name(
+        &self,
+        construction_id: i32,
+        language: Language,
+    ) -> Option<&String> {
+        self.highway_segments
+            .get(&construction_id)
+            .map(|s| s.names.get(&language))
+            .flatten()
+    }
+
+    pub fn highway_resource_name(&self, resource_id: i16, language: Language) -> Option<&String> {
+        self.highway_resources
+            .get(&resource_id)
+            .map(|r| r.names.get(&language))
+            .flatten()
+    }
+
+    pub fn construction_point_name(
+        &self,
+        construction_id: i32,
+        language: Language,
+    ) -> Option<&String> {
+        self.construction_points
+            .get(&construction_id)
+            .map(|c| c.names.get(&language))
+            .flatten()
+    }
+
+    pub fn mission_name(&self, mission_static_id: i64, language: Language) -> Option<&String> {
+        self.missions
+            .get(&mission_static_id)
+            .map(|m| m.names.get(&language))
+            .flatten()
+    }
 }
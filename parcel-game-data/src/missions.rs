@@ -0,0 +1,18 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "ts")]
+use typescript_type_def::TypeDef;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Language;
+
+/// A mission that's defined in the game, as opposed to one created by a player.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TypeDef))]
+#[serde(rename_all = "camelCase")]
+pub struct MissionDefinition {
+    /// The id the game uses for the mission when adding it to the server
+    pub mission_static_id: i64,
+    pub names: BTreeMap<Language, String>,
+}
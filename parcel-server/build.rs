@@ -1,7 +1,8 @@
 use std::{fs::File, path::Path, process::Command};
 
 use parcel_common::api_types::frontend::{
-    accounts::*, auth::*, baggages::*, qpid_objects::*, settings::*,
+    accounts::*, audit_log::*, auth::*, baggages::*, bans::*, highways::*, missions::*,
+    qpid_objects::*, rankings::*, sessions::*, settings::*, statistics::*,
 };
 use parcel_game_data::QpidArea;
 use typescript_type_def::{write_definition_file, DefinitionFileOptions};
@@ -39,6 +40,25 @@ type Api = (
         QpidArea,
         QpidObject,
         Baggage,
+        RankingScheduleListItem,
+        CreateRankingScheduleRequest,
+        RankingRecordListItem,
+        QpidObjectDetails,
+        SetObjectDeletedRequest,
+        SetObjectCreatorRequest,
+        SetObjectsDeletedResponse,
+    ),
+    (
+        MissionDetails,
+        SetMissionProgressStateRequest,
+        RetentionReport,
+        BanListItem,
+        CreateBanRequest,
+        GameSessionListItem,
+        KickSessionsResponse,
+        ListAuditLogResponse,
+        Statistics,
+        HighwayConstruction,
     ),
 );
 
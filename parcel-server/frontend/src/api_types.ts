@@ -1,6 +1,6 @@
 // AUTO-GENERATED by typescript-type-def
 
-export type Provider = ("steam" | "epic");
+export type Provider = ("steam" | "epic" | "local");
 export type AuthRequest = {
     "provider": Provider;
 };
@@ -14,7 +14,7 @@ export type InitAuthResponse = {
 export type CheckAuthRequest = {
     "callbackToken": string;
 };
-export type FrontendPermissions = ("manageAccounts" | "manageServerSettings");
+export type FrontendPermissions = ("manageAccounts" | "manageServerSettings" | "manageObjects" | "manageMissions");
 export type AuthAccountInfo = {
     "name": string;
     "avatarUrl": (string | null);
@@ -55,10 +55,30 @@ export type LostCargoListItem = {
     "category": string;
     "location": string;
     "endLocation": string;
+
+    /**
+     * The delivery point that the game data says the cargo belongs to.
+     * None if the cargo isn't in the game's lost cargo table.
+     */
+    "expectedDestination": (string | null);
     "creator": string;
 };
+
+/**
+ * Lost cargo that the game expects to be delivered to a delivery point, but isn't in the world.
+ */
+export type MissingLostCargoListItem = {
+    "name": string;
+    "category": string;
+    "destination": string;
+};
 export type ListLostCargoResponse = {
     "baggages": (LostCargoListItem)[];
+
+    /**
+     * Empty if the game data doesn't include the lost cargo table
+     */
+    "missing": (MissingLostCargoListItem)[];
 };
 export type WastedCargoListItem = {
     "name": string;
@@ -129,6 +149,51 @@ export type CreateFrontendAccountRequest = (({
     "provider": Provider;
     "providerId": string;
 }));
+export type U32 = number;
+
+/**
+ * Rules that are periodically applied to keep the world from filling up.
+ *
+ * Every rule is disabled when its value is null.
+ */
+export type RetentionSettings = {
+
+    /**
+     * If false, the rules are never applied automatically.
+     */
+    "enabled": boolean;
+
+    /**
+     * Delete objects without any likes that haven't been updated in this many days.
+     */
+    "deleteUnlikedObjectsAfterDays": (U32 | null);
+
+    /**
+     * Delete delivered missions this many days after they were registered.
+     */
+    "deleteDeliveredMissionsAfterDays": (U32 | null);
+
+    /**
+     * Delete lost cargo that nobody has picked up this many days after it was registered.
+     */
+    "deleteLostCargoAfterDays": (U32 | null);
+
+    /**
+     * Delete wasted baggages (broken or used items) after this many days.
+     */
+    "deleteWastedBaggagesAfterDays": (U32 | null);
+
+    /**
+     * Maximum number of objects in each qpid area. The least liked objects, and then the oldest ones,
+     * are deleted first.
+     */
+    "maxObjectsPerQpidArea": (U32 | null);
+
+    /**
+     * Delete audit log entries after this many days.
+     */
+    "deleteAuditLogAfterDays": (U32 | null);
+};
 export type SettingsValues = {
 
     /**
@@ -144,13 +209,17 @@ export type SettingsValues = {
      * for the user.
      */
     "allowFrontendLogin": boolean;
+
+    /**
+     * Rules for automatically removing old content from the world.
+     */
+    "retention": RetentionSettings;
 };
 export type WhitelistEntry = {
     "providerId": string;
     "nameReference": (string | null);
 };
 export type Language = ("unknown" | "en-us" | "fr" | "es" | "de" | "it" | "nl" | "pt" | "zh-CHT" | "ko" | "ru" | "pl" | "da" | "fi" | "no" | "sv" | "ja" | "es-419" | "latampor" | "tr" | "ar" | "zh-CN" | "en-uk" | "el" | "cs" | "hu");
-export type U32 = number;
 export type ConstructionPointType = ("deliveryBase" | "preppersShelter" | "stageSafetyHouse" | "playerSafetyHouse" | "netSafetyHouse" | "stagePost" | "playerPost" | "netPost" | "stageWatchTower" | "playerWatchTower" | "netWatchTower" | "_Reserved0" | "_Reserved1" | "_Reserved2" | "stageCharger" | "playerCharger" | "netCharger" | "stageRainShelter" | "playerRainShelter" | "netRainShelter" | "mulePost" | "stageZipline" | "playerZipline" | "netZipline" | "stageLadder" | "playerLadder" | "netLadder" | "stageFieldRope" | "playerFieldRope" | "netFieldRope" | "stageBridge30m" | "playerBridge30m" | "netBridge30m" | "stageBridge45m" | "playerBridge45m" | "netBridge45m" | "roadRebuilder" | "_Reserved3" | "_Reserved4" | "_Reserved5" | "_Reserved6" | "_Reserved7" | "_Reserved8" | "_Reserved9" | "_Reserved10" | "_Reserved11");
 export type Area = ("area00" | "area01" | "area02" | "area03" | "area04" | "warrior01" | "warrior02" | "warrior03" | "beach01" | "empty" | "frange01" | "nm01" | "nm02" | "nm04" | "_Reserved0" | "_Reserved1" | "_Reserved2" | "_Reserved3" | "_Reserved4" | "_Reserved5" | "_Reserved6" | "_Reserved7" | "_Reserved8" | "_Reserved9" | "a" | "b" | "c" | "d" | "e");
 export type F64 = number;
@@ -183,6 +252,7 @@ export type QpidObject = {
      * For all other object types this is always true.
      */
     "isLost": boolean;
+    "isDeleted": boolean;
     "creator": GameAccountSummary;
 };
 export type ContentsType = ("commodity" | "weapon" | "equipment" | "special" | "rawMaterial");
@@ -200,3 +270,430 @@ export type Baggage = {
     "isBroken": boolean;
     "creator": GameAccountSummary;
 };
+export type RankingCategory = ("deliveries" | "likes" | "highwayResources");
+export type RankingScheduleListItem = {
+    "id": I32;
+    "category": RankingCategory;
+    "startDate": string;
+    "endDate": string;
+
+    /**
+     * The date the rewards were computed, if the schedule has ended
+     */
+    "closedAt": (string | null);
+    "numParticipants": I64;
+};
+export type CreateRankingScheduleRequest = {
+    "category": RankingCategory;
+
+    /**
+     * The start date in RFC 3339 format
+     */
+    "startDate": string;
+
+    /**
+     * The end date in RFC 3339 format
+     */
+    "endDate": string;
+};
+export type I16 = number;
+export type RankingRecordListItem = {
+    "account": GameAccountSummary;
+    "score": I64;
+    "tryCount": I32;
+    "clearCount": I32;
+
+    /**
+     * Only set once the schedule has been closed
+     */
+    "rank": (I32 | null);
+
+    /**
+     * Only set once the schedule has been closed
+     */
+    "rewardTier": (I16 | null);
+};
+export type QpidObjectComment = {
+    "writer": string;
+    "likes": I64;
+    "parentIndex": I16;
+    "isDeleted": boolean;
+    "referenceObject": string;
+
+    /**
+     * The ids of the phrases in the order they're displayed
+     */
+    "phrases": (I32)[];
+};
+export type QpidObjectMaterialsContribution = {
+
+    /**
+     * None if the materials were contributed by the creator when the object was built
+     */
+    "contributor": (GameAccountSummary | null);
+    "materials": (I32)[];
+
+    /**
+     * Only set for construction materials
+     */
+    "repairMaterials": ((I32)[] | null);
+    "time": string;
+};
+export type QpidObjectRopeInfo = {
+    "pitch": I32;
+    "heading": I32;
+    "length": I32;
+};
+export type QpidObjectStoneInfo = {
+    "restingCount": I32;
+};
+export type QpidObjectBridgeInfo = {
+    "angle": I32;
+};
+export type QpidObjectParkingInfo = {
+    "locationId": I32;
+    "dynamicLocationId": string;
+    "currentQpidId": I32;
+    "isParking": boolean;
+};
+export type QpidObjectVehicleInfo = {
+    "locationId": I32;
+    "dynamicLocationId": string;
+    "currentQpidId": I32;
+    "isParking": boolean;
+    "isLost": boolean;
+    "isRace": boolean;
+    "customizeType": I32;
+    "customizeColor": I32;
+};
+export type QpidObjectExtraInfo = {
+    "alternativeQpidId": I32;
+};
+export type QpidObjectCustomizeInfo = {
+    "customizeParam": I32;
+    "customizeColor": I32;
+};
+export type QpidObjectDetails = {
+    "object": QpidObject;
+    "subType": string;
+    "likes": I64;
+    "updatedTime": string;
+    "tags": (string)[];
+    "comment": (QpidObjectComment | null);
+    "constructionMaterials": (QpidObjectMaterialsContribution)[];
+    "recycleMaterials": (QpidObjectMaterialsContribution)[];
+    "ropeInfo": (QpidObjectRopeInfo | null);
+    "stoneInfo": (QpidObjectStoneInfo | null);
+    "bridgeInfo": (QpidObjectBridgeInfo | null);
+    "parkingInfo": (QpidObjectParkingInfo | null);
+    "vehicleInfo": (QpidObjectVehicleInfo | null);
+    "extraInfo": (QpidObjectExtraInfo | null);
+    "customizeInfo": (QpidObjectCustomizeInfo | null);
+};
+export type SetObjectDeletedRequest = {
+    "isDeleted": boolean;
+};
+export type SetObjectCreatorRequest = {
+
+    /**
+     * The game account id of the new creator
+     */
+    "accountId": string;
+};
+export type Usize = number;
+export type SetObjectsDeletedResponse = {
+    "numAffectedObjects": Usize;
+};
+export type MissionType = ("Delivery" | "Collect" | "LostObject" | "Supply" | "Special" | "Free");
+export type OnlineMissionType = ("Unknown_online_type" | "Online_supply" | "Private" | "Dynamic" | "Static" | "Shared_last_stranding");
+export type ProgressState = ("Invalid" | "Available" | "Ready" | "Progress" | "Failed" | "Success" | "Cancel" | "Not_available" | "Returned" | "Used" | "Missing" | "Consign" | "Complete_automation");
+export type MissionSupplyInfo = {
+    "itemHash": I64;
+    "itemName": (string | null);
+    "amount": I32;
+};
+export type MissionDynamicLocationInfo = {
+    "locationId": string;
+    "x": I32;
+    "y": I32;
+    "z": I32;
+};
+export type MissionDynamicMissionInfo = {
+    "clientNameHash": I32;
+    "rewardNameHash": I32;
+};
+export type MissionCatapultShellInfo = {
+    "localId": I32;
+    "x": I32;
+    "y": I32;
+    "z": I32;
+};
+export type MissionBaggageAmmoInfo = {
+    "ammoId": string;
+    "clipCount": I16;
+    "count": I16;
+};
+export type MissionBaggage = {
+    "id": I64;
+    "nameHash": I32;
+    "name": (string | null);
+    "amount": I32;
+    "userIndex": I32;
+    "x": I32;
+    "y": I32;
+    "z": I32;
+    "isReturned": boolean;
+    "ammoInfo": (MissionBaggageAmmoInfo | null);
+};
+export type MissionDetails = {
+    "id": string;
+    "creator": GameAccountSummary;
+    "worker": (GameAccountSummary | null);
+    "qpidId": I32;
+    "qpidStartLocation": I32;
+    "qpidEndLocation": I32;
+    "qpidDeliveredLocation": (I32 | null);
+    "missionStaticId": I64;
+
+    /**
+     * Null if the mission isn't defined in the game data
+     */
+    "missionName": (string | null);
+    "missionType": MissionType;
+    "onlineMissionType": OnlineMissionType;
+    "progressState": ProgressState;
+    "registeredTime": string;
+    "expirationTime": string;
+    "supplyInfo": (MissionSupplyInfo | null);
+    "dynamicStartInfo": (MissionDynamicLocationInfo | null);
+    "dynamicEndInfo": (MissionDynamicLocationInfo | null);
+    "dynamicDeliveredInfo": (MissionDynamicLocationInfo | null);
+    "dynamicMissionInfo": (MissionDynamicMissionInfo | null);
+    "catapultShellInfo": (MissionCatapultShellInfo | null);
+    "baggages": (MissionBaggage)[];
+
+    /**
+     * The accounts that have interacted with the mission
+     */
+    "relations": (GameAccountSummary)[];
+};
+export type SetMissionProgressStateRequest = {
+    "progressState": ProgressState;
+
+    /**
+     * If true the current worker is removed from the mission.
+     */
+    "clearWorker": boolean;
+};
+export type RetentionReport = {
+    "unlikedObjects": Usize;
+    "objectsOverAreaLimit": Usize;
+    "deliveredMissions": Usize;
+    "lostCargo": Usize;
+    "wastedBaggages": Usize;
+    "auditLogEntries": Usize;
+};
+export type BanListItem = {
+    "id": I64;
+
+    /**
+     * The banned game account, if the ban was issued for an account or the provider id belongs to one
+     */
+    "account": (GameAccountSummary | null);
+    "provider": Provider;
+    "providerId": string;
+    "reason": string;
+
+    /**
+     * The id of the frontend account that issued the ban
+     */
+    "issuedBy": (I64 | null);
+    "createdAt": string;
+
+    /**
+     * Not set for permanent bans
+     */
+    "expiresAt": (string | null);
+    "liftedAt": (string | null);
+    "liftedBy": (I64 | null);
+    "isActive": boolean;
+};
+export type BanTarget = (({
+    "type": "account";
+} & {
+    "accountId": string;
+}) | ({
+    "type": "provider";
+} & {
+    "provider": Provider;
+    "providerId": string;
+}));
+export type CreateBanRequest = {
+    "target": BanTarget;
+    "reason": string;
+
+    /**
+     * The date the ban expires in RFC 3339 format, or null for a permanent ban
+     */
+    "expiresAt": (string | null);
+};
+export type GameSessionListItem = {
+    "account": GameAccountSummary;
+    "provider": Provider;
+    "providerId": string;
+    "loggedInAt": string;
+
+    /**
+     * The last time the player made a request, accurate to about a minute
+     */
+    "lastSeenAt": string;
+};
+export type KickSessionsResponse = {
+    "numKicked": Usize;
+};
+export type AuditAction = ("setAccountPermissions" | "createCredentials" | "resetPassword" | "createFrontendAccount" | "setServerSettings" | "setWhitelist" | "deleteObject" | "createBan" | "liftBan" | "kickGameSessions" | "setMissionProgressState" | "resetMission" | "deleteMission" | "setObjectDeleted" | "setObjectCreator" | "setObjectsDeletedByCreator" | "applyRetentionRules");
+export type AuditLogEntry = {
+    "id": I64;
+    "createdAt": string;
+
+    /**
+     * Set if the action was made through the frontend
+     */
+    "frontendActorId": (I64 | null);
+
+    /**
+     * Set if the action was made by a player in game
+     */
+    "gameActor": (GameAccountSummary | null);
+    "action": AuditAction;
+
+    /**
+     * The id of the account, object, etc that was changed, if the action has one
+     */
+    "targetId": (string | null);
+
+    /**
+     * The value before the change as JSON, if it's known
+     */
+    "before": (string | null);
+
+    /**
+     * The value after the change as JSON, if it's known
+     */
+    "after": (string | null);
+};
+export type ListAuditLogResponse = {
+    "entries": (AuditLogEntry)[];
+
+    /**
+     * The total number of entries that match the filter
+     */
+    "total": I64;
+};
+export type ObjectCountStatistic = {
+    "objectType": QpidObjectType;
+
+    /**
+     * 0 = eastern region, 1 = central region, 2 = western region
+     */
+    "areaId": I32;
+    "count": I64;
+};
+export type LikesPerDayStatistic = {
+
+    /**
+     * The date in YYYY-MM-DD format (UTC)
+     */
+    "date": string;
+
+    /**
+     * For server wide statistics every like counts as both given and received
+     */
+    "likesGiven": I64;
+    "likesReceived": I64;
+};
+export type DevotedResourcesStatistic = {
+
+    /**
+     * The id of the highway segment
+     */
+    "constructionId": I32;
+    "resourceId": I16;
+    "numResources": I64;
+};
+export type MissionStatistics = {
+    "created": I64;
+
+    /**
+     * Missions that were delivered by the account, or by anyone for server wide statistics
+     */
+    "completed": I64;
+};
+export type ProfileStatistics = {
+    "deliveredBaggage": I64;
+    "deliveredWeight": I64;
+    "distanceTraveled": I64;
+    "numLikesReceivedNpc": I64;
+    "numLikesReceivedOnline": I64;
+    "legendCount": I64;
+    "superLegendCount": I64;
+    "ssLegendCount": I64;
+};
+export type Statistics = {
+
+    /**
+     * Objects that haven't been deleted, by type and area
+     */
+    "objects": (ObjectCountStatistic)[];
+    "likesPerDay": (LikesPerDayStatistic)[];
+    "devotedResources": (DevotedResourcesStatistic)[];
+    "missions": MissionStatistics;
+
+    /**
+     * Lifetime totals from the player profiles, which are not affected by the date range.
+     * Null if the account doesn't have a profile yet.
+     */
+    "profile": (ProfileStatistics | null);
+};
+export type HighwayResourceProgress = {
+    "resourceId": I16;
+    "name": (string | null);
+    "numResources": I64;
+
+    /**
+     * Null if the resource isn't required or the requirements are unknown
+     */
+    "requiredResources": (I64 | null);
+};
+export type HighwayContributor = {
+    "account": GameAccountSummary;
+
+    /**
+     * The total number of resources of every type the account devoted to the segment
+     */
+    "numResources": I64;
+};
+export type HighwayConstruction = {
+
+    /**
+     * The id of the highway segment
+     */
+    "constructionId": I32;
+
+    /**
+     * Null if the segment isn't in the game data
+     */
+    "name": (string | null);
+    "area": (Area | null);
+    "resources": (HighwayResourceProgress)[];
+
+    /**
+     * The accounts that devoted the most resources, sorted by the number of devoted resources
+     */
+    "topContributors": (HighwayContributor)[];
+
+    /**
+     * Null if the resources required to finish the segment are unknown
+     */
+    "completionPercentage": (F64 | null);
+};
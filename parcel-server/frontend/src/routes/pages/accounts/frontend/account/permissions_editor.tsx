@@ -13,6 +13,8 @@ import { ApiResponse } from "../../../../../services";
 const allPermissions: FrontendPermissions[] = [
   "manageAccounts",
   "manageServerSettings",
+  "manageObjects",
+  "manageMissions",
 ];
 const permissionNames = Object.fromEntries(
   allPermissions.map((permission) => [
@@ -2,12 +2,15 @@ import * as React from "react";
 import * as Tabs from "../../../components/tabs";
 import FrontendAccountsTable from "./frontend_accounts_table";
 import GameAccountsTable from "./game_accounts_table";
+import OnlinePlayersTable from "./online_players_table";
 import { useState } from "react";
 import {
   FrontendAccountListItem,
   GameAccountListItem,
+  GameSessionListItem,
 } from "../../../api_types";
 import { getAccounts } from "../../../services/accounts_service";
+import { getGameSessions } from "../../../services/sessions_service";
 
 const Accounts = () => {
   const [frontendAccounts, setFrontendAccounts] = useState<
@@ -16,6 +19,9 @@ const Accounts = () => {
   const [gameAccounts, setGameAccounts] = useState<
     GameAccountListItem[] | null | undefined
   >(undefined);
+  const [gameSessions, setGameSessions] = useState<
+    GameSessionListItem[] | null | undefined
+  >(undefined);
 
   React.useEffect(() => {
     (async () => {
@@ -41,12 +47,25 @@ const Accounts = () => {
     })();
   }, [gameAccounts]);
 
+  React.useEffect(() => {
+    (async () => {
+      if (gameSessions == null) {
+        const response = await getGameSessions();
+
+        if (response.data != null) {
+          setGameSessions(response.data);
+        }
+      }
+    })();
+  }, [gameSessions]);
+
   return (
     <>
       <Tabs.Root defaultValue="frontend">
         <Tabs.List>
           <Tabs.Trigger value="frontend">Frontend accounts</Tabs.Trigger>
           <Tabs.Trigger value="game">Game accounts</Tabs.Trigger>
+          <Tabs.Trigger value="online">Online players</Tabs.Trigger>
         </Tabs.List>
         <Tabs.Content value="frontend" forceMount>
           <FrontendAccountsTable accounts={frontendAccounts} />
@@ -54,6 +73,12 @@ const Accounts = () => {
         <Tabs.Content value="game" forceMount>
           <GameAccountsTable accounts={gameAccounts} />
         </Tabs.Content>
+        <Tabs.Content value="online" forceMount>
+          <OnlinePlayersTable
+            sessions={gameSessions}
+            onKicked={() => setGameSessions(undefined)}
+          />
+        </Tabs.Content>
       </Tabs.Root>
     </>
   );
@@ -0,0 +1,111 @@
+import { ColDef, ICellRendererParams } from "ag-grid-community";
+import { AgGridReact } from "ag-grid-react";
+import * as React from "react";
+import { useState } from "react";
+import { SignOut } from "@phosphor-icons/react";
+import { GameSessionListItem } from "../../../api_types";
+import { formatDate } from "../../../utils/table_value_formatters/date";
+import { TableButtons, TableWrapper } from "./table_base";
+import {
+  kickAllGameSessions,
+  kickGameSession,
+} from "../../../services/sessions_service";
+
+interface Props {
+  sessions: GameSessionListItem[] | null | undefined;
+  onKicked: () => void;
+}
+
+const OnlinePlayersTable = ({ sessions, onKicked }: Props) => {
+  const [error, setError] = useState<string | null>(null);
+
+  const Buttons = (props: ICellRendererParams<GameSessionListItem>) => {
+    if (props.data == null) {
+      return null;
+    }
+
+    async function onKick() {
+      if (!confirm(`Log out ${props.data.account.name}?`)) {
+        return;
+      }
+
+      setError(null);
+      const response = await kickGameSession(props.data.account.id);
+
+      if (response.error != null) {
+        setError(response.error);
+      }
+
+      onKicked();
+    }
+
+    return (
+      <TableButtons>
+        <a title="Log out" onClick={onKick}>
+          <SignOut weight="regular" />
+        </a>
+      </TableButtons>
+    );
+  };
+
+  async function onKickAll() {
+    if (!confirm("Log out every player?")) {
+      return;
+    }
+
+    setError(null);
+    const response = await kickAllGameSessions();
+
+    if (response.error != null) {
+      setError(response.error);
+    }
+
+    onKicked();
+  }
+
+  const [defaultColDef] = useState<ColDef>({
+    sortable: true,
+    filter: true,
+    resizable: false,
+    flex: 1,
+  });
+
+  const columnDefs: ColDef<GameSessionListItem>[] = [
+    { field: "account.name", headerName: "Name" },
+    { field: "account.id", headerName: "Game id" },
+    { field: "provider" },
+    { field: "loggedInAt", valueFormatter: formatDate },
+    { field: "lastSeenAt", valueFormatter: formatDate, sort: "desc" },
+    {
+      cellRenderer: Buttons,
+      maxWidth: 55,
+      filter: false,
+      sortable: false,
+      suppressMovable: true,
+    },
+  ];
+
+  return (
+    <>
+      <div>
+        <button type="button" onClick={onKickAll}>
+          Log out all players
+        </button>{" "}
+        {error != null && <span className="error">{error}</span>}
+      </div>
+      <TableWrapper>
+        <AgGridReact
+          columnDefs={columnDefs}
+          defaultColDef={defaultColDef}
+          rowData={sessions}
+          domLayout="autoHeight"
+          pagination={true}
+          suppressCellFocus={true}
+          enableCellTextSelection={true}
+        />
+      </TableWrapper>
+    </>
+  );
+};
+
+export default OnlinePlayersTable;
@@ -8,11 +8,13 @@ import {
 } from "../../../services/baggages_service";
 import {
   LostCargoListItem,
+  MissingLostCargoListItem,
   SharedCargoListItem,
   WastedCargoListItem,
 } from "../../../api_types";
 import * as Tabs from "../../../components/tabs";
 import LostCargoTable from "./lost_cargo_table";
+import MissingLostCargoTable from "./missing_lost_cargo_table";
 import WastedCargoTable from "./wasted_cargo_table";
 
 const Items = () => {
@@ -24,6 +26,10 @@ const Items = () => {
     LostCargoListItem[] | undefined | null
   >();
 
+  const [missingLostItems, setMissingLostItems] = useState<
+    MissingLostCargoListItem[] | undefined | null
+  >();
+
   const [wastedItems, setWastedItems] = useState<
     WastedCargoListItem[] | undefined | null
   >();
@@ -47,9 +53,11 @@ const Items = () => {
 
         if (response.data != null) {
           setLostItems(response.data.baggages);
+          setMissingLostItems(response.data.missing);
         } else {
           console.error(response.statusCode, response.error);
           setLostItems(null);
+          setMissingLostItems(null);
         }
       }
 
@@ -72,6 +80,9 @@ const Items = () => {
         <Tabs.List>
           <Tabs.Trigger value="sharedCargo">Shared cargo</Tabs.Trigger>
           <Tabs.Trigger value="lostCargo">Lost cargo</Tabs.Trigger>
+          <Tabs.Trigger value="missingLostCargo">
+            Missing lost cargo
+          </Tabs.Trigger>
           <Tabs.Trigger value="wastedCargo">Wasted cargo</Tabs.Trigger>
         </Tabs.List>
         <Tabs.Content value="sharedCargo" forceMount>
@@ -80,6 +91,9 @@ const Items = () => {
         <Tabs.Content value="lostCargo" forceMount>
           <LostCargoTable items={lostItems} />
         </Tabs.Content>
+        <Tabs.Content value="missingLostCargo" forceMount>
+          <MissingLostCargoTable items={missingLostItems} />
+        </Tabs.Content>
         <Tabs.Content value="wastedCargo" forceMount>
           <WastedCargoTable items={wastedItems} />
         </Tabs.Content>
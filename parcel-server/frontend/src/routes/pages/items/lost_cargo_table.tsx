@@ -22,6 +22,7 @@ const LostCargoTable = ({ items }: Props) => {
     { field: "category" },
     { field: "location", headerName: "Area" },
     { field: "endLocation", headerName: "Destination" },
+    { field: "expectedDestination", headerName: "Expected destination" },
     { field: "creator", headerName: "Player" },
   ]);
 
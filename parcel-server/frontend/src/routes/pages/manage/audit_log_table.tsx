@@ -0,0 +1,175 @@
+import * as React from "react";
+import { AgGridReact } from "ag-grid-react";
+import { ColDef, ValueGetterParams } from "ag-grid-community";
+import { styled } from "styled-components";
+import { AuditAction, AuditLogEntry } from "../../../api_types";
+import { formatDate } from "../../../utils/table_value_formatters/date";
+import {
+  AuditLogFilter,
+  getAuditLog,
+} from "../../../services/audit_log_service";
+
+const Wrapper = styled.div.attrs({
+  className: "ag-theme-alpine-dark",
+})`
+  margin-top: 1.5rem;
+`;
+
+const PageSize = 50;
+
+const actions: { value: AuditAction; label: string }[] = [
+  { value: "setAccountPermissions", label: "Set account permissions" },
+  { value: "createCredentials", label: "Create credentials" },
+  { value: "resetPassword", label: "Reset password" },
+  { value: "createFrontendAccount", label: "Create frontend account" },
+  { value: "setServerSettings", label: "Set server settings" },
+  { value: "setWhitelist", label: "Set whitelist" },
+  { value: "deleteObject", label: "Delete object" },
+  { value: "createBan", label: "Create ban" },
+  { value: "liftBan", label: "Lift ban" },
+  { value: "kickGameSessions", label: "Kick game sessions" },
+  { value: "setMissionProgressState", label: "Set mission progress state" },
+  { value: "resetMission", label: "Reset mission" },
+  { value: "deleteMission", label: "Delete mission" },
+  { value: "setObjectDeleted", label: "Set object deleted" },
+  { value: "setObjectCreator", label: "Set object creator" },
+  {
+    value: "setObjectsDeletedByCreator",
+    label: "Set objects deleted by creator",
+  },
+  { value: "applyRetentionRules", label: "Apply retention rules" },
+];
+
+function getActor(params: ValueGetterParams<AuditLogEntry>): string {
+  const entry = params.data;
+
+  if (entry?.gameActor != null) {
+    return `${entry.gameActor.name} (${entry.gameActor.id})`;
+  }
+
+  if (entry?.frontendActorId != null) {
+    return `Frontend account ${entry.frontendActorId}`;
+  }
+
+  return "";
+}
+
+const AuditLogTable = () => {
+  const [filter, setFilter] = React.useState<AuditLogFilter>({ page: 0 });
+  const [entries, setEntries] = React.useState<AuditLogEntry[] | null>(null);
+  const [total, setTotal] = React.useState(0);
+  const [error, setError] = React.useState<string | null>(null);
+
+  const colDefs: ColDef<AuditLogEntry>[] = [
+    { field: "createdAt", valueFormatter: formatDate },
+    { headerName: "Actor", valueGetter: getActor },
+    { field: "action" },
+    { field: "targetId" },
+    { field: "before", flex: 2 },
+    { field: "after", flex: 2 },
+  ];
+
+  React.useEffect(() => {
+    (async () => {
+      setError(null);
+      const response = await getAuditLog({ ...filter, pageSize: PageSize });
+
+      if (response.data != null) {
+        setEntries(response.data.entries);
+        setTotal(response.data.total);
+      } else {
+        setError(response.error);
+      }
+    })();
+  }, [filter]);
+
+  const page = filter.page ?? 0;
+  const numPages = Math.max(1, Math.ceil(total / PageSize));
+
+  return (
+    <>
+      <div>
+        <select
+          value={filter.action ?? ""}
+          onChange={(ev) =>
+            setFilter({
+              ...filter,
+              action: (ev.target.value || undefined) as AuditAction,
+              page: 0,
+            })
+          }
+        >
+          <option value="">All actions</option>
+          {actions.map((action) => (
+            <option key={action.value} value={action.value}>
+              {action.label}
+            </option>
+          ))}
+        </select>{" "}
+        <input
+          type="text"
+          placeholder="Target id"
+          value={filter.targetId ?? ""}
+          onChange={(ev) =>
+            setFilter({ ...filter, targetId: ev.target.value, page: 0 })
+          }
+        />{" "}
+        <input
+          type="date"
+          title="From"
+          onChange={(ev) =>
+            setFilter({
+              ...filter,
+              from: ev.target.value
+                ? new Date(ev.target.value).toISOString()
+                : undefined,
+              page: 0,
+            })
+          }
+        />{" "}
+        <input
+          type="date"
+          title="To"
+          onChange={(ev) =>
+            setFilter({
+              ...filter,
+              to: ev.target.value
+                ? new Date(ev.target.value).toISOString()
+                : undefined,
+              page: 0,
+            })
+          }
+        />
+      </div>
+      {error != null && <span className="error">{error}</span>}
+      <Wrapper>
+        <AgGridReact
+          defaultColDef={{ flex: 1, resizable: true }}
+          columnDefs={colDefs}
+          rowData={entries}
+          domLayout="autoHeight"
+          enableCellTextSelection
+        />
+      </Wrapper>
+      <div>
+        <button
+          type="button"
+          disabled={page <= 0}
+          onClick={() => setFilter({ ...filter, page: page - 1 })}
+        >
+          Previous
+        </button>{" "}
+        Page {page + 1} of {numPages}{" "}
+        <button
+          type="button"
+          disabled={page + 1 >= numPages}
+          onClick={() => setFilter({ ...filter, page: page + 1 })}
+        >
+          Next
+        </button>
+      </div>
+    </>
+  );
+};
+
+export default AuditLogTable;
@@ -4,7 +4,11 @@ import * as Form from "../../../components/form";
 import * as Tabs from "../../../components/tabs";
 import SaveButton from "../../../components/save_button";
 import { ApiResponse } from "../../../services";
-import { SettingsValues, WhitelistEntry } from "../../../api_types";
+import {
+  RetentionSettings,
+  SettingsValues,
+  WhitelistEntry,
+} from "../../../api_types";
 import {
   getServerSettings,
   getWhitelist,
@@ -12,6 +16,8 @@ import {
   setWhitelist as setWhitelistService,
 } from "../../../services/settings_service";
 import WhitelistTable from "./whitelist_table";
+import RetentionEditor from "./retention_editor";
+import AuditLogTable from "./audit_log_table";
 
 const Settings = () => {
   const [settingsError, setSettingsError] = React.useState<string | null>(null);
@@ -65,6 +71,13 @@ const Settings = () => {
     });
   }
 
+  function setRetention(value: RetentionSettings) {
+    setSettings({
+      ...settings,
+      retention: value,
+    });
+  }
+
   React.useEffect(() => {
     (async () => {
       if (settings === undefined) {
@@ -100,6 +113,8 @@ const Settings = () => {
           <Tabs.List>
             <Tabs.Trigger value="settings">Settings</Tabs.Trigger>
             <Tabs.Trigger value="whitelist">Whitelist</Tabs.Trigger>
+            <Tabs.Trigger value="retention">Retention</Tabs.Trigger>
+            <Tabs.Trigger value="auditLog">Audit log</Tabs.Trigger>
           </Tabs.List>
           <Tabs.Content value="settings" $padded>
             <Form.Root>
@@ -180,6 +195,23 @@ const Settings = () => {
               <span className="error">{whitelistError}</span>
             )}
           </Tabs.Content>
+          <Tabs.Content value="retention" $padded>
+            <Form.Root>
+              <RetentionEditor
+                rules={settings.retention}
+                setRules={setRetention}
+              />
+              <SaveButton isForm saveAction={saveSettings}>
+                Save
+              </SaveButton>
+              {settingsError != null && (
+                <span className="error">{settingsError}</span>
+              )}
+            </Form.Root>
+          </Tabs.Content>
+          <Tabs.Content value="auditLog" $padded>
+            <AuditLogTable />
+          </Tabs.Content>
         </Tabs.Root>
       )}
     </>
@@ -0,0 +1,152 @@
+import * as React from "react";
+import * as Form from "../../../components/form";
+import { RetentionReport, RetentionSettings } from "../../../api_types";
+import {
+  applyRetentionRules,
+  previewRetentionRules,
+} from "../../../services/settings_service";
+
+interface Props {
+  rules: RetentionSettings;
+  setRules: (rules: RetentionSettings) => void;
+}
+
+type DaysRule = Exclude<keyof RetentionSettings, "enabled">;
+
+const numberRules: { key: DaysRule; label: string; description: string }[] = [
+  {
+    key: "deleteUnlikedObjectsAfterDays",
+    label: "Delete unliked objects after (days)",
+    description:
+      "Objects without any likes that haven't been updated in this many days are deleted.",
+  },
+  {
+    key: "maxObjectsPerQpidArea",
+    label: "Max objects per area",
+    description:
+      "The least liked objects in an area, and then the oldest ones, are deleted when there are more objects than this.",
+  },
+  {
+    key: "deleteDeliveredMissionsAfterDays",
+    label: "Delete delivered missions after (days)",
+    description:
+      "Delivered missions are deleted this many days after they were created.",
+  },
+  {
+    key: "deleteLostCargoAfterDays",
+    label: "Delete lost cargo after (days)",
+    description:
+      "Lost cargo that nobody has picked up is deleted this many days after it was dropped. Cargo in shared lockers is not affected.",
+  },
+  {
+    key: "deleteWastedBaggagesAfterDays",
+    label: "Delete wasted cargo after (days)",
+    description: "Broken or used items are deleted after this many days.",
+  },
+  {
+    key: "deleteAuditLogAfterDays",
+    label: "Delete audit log entries after (days)",
+    description:
+      "Records of administrative actions and deleted objects are kept for this many days.",
+  },
+];
+
+const RetentionEditor = ({ rules, setRules }: Props) => {
+  const [report, setReport] = React.useState<RetentionReport | null>(null);
+  const [reportTitle, setReportTitle] = React.useState<string>("");
+  const [error, setError] = React.useState<string | null>(null);
+
+  function setRule(key: DaysRule, value: string) {
+    const number = parseInt(value);
+
+    setRules({
+      ...rules,
+      [key]: isNaN(number) || number < 0 ? null : number,
+    });
+  }
+
+  async function preview() {
+    setError(null);
+    const response = await previewRetentionRules(rules);
+
+    if (response.data != null) {
+      setReport(response.data);
+      setReportTitle("The following would be deleted if the rules ran now:");
+    } else {
+      setError(response.error);
+    }
+  }
+
+  async function apply() {
+    if (
+      !confirm(
+        "Apply the saved rules now? Unsaved changes are not used. Deleted missions and wasted cargo can not be restored."
+      )
+    ) {
+      return;
+    }
+
+    setError(null);
+    const response = await applyRetentionRules();
+
+    if (response.data != null) {
+      setReport(response.data);
+      setReportTitle("The following was deleted:");
+    } else {
+      setError(response.error);
+    }
+  }
+
+  return (
+    <>
+      <Form.Field name="retentionEnabled">
+        <Form.Label>Automatically apply retention rules</Form.Label>
+        <Form.SubLabel>
+          If checked, the rules below are applied once every hour. Leave a rule
+          empty to disable it.
+        </Form.SubLabel>
+        <Form.Control
+          type="checkbox"
+          checked={rules.enabled}
+          onChange={(ev) => setRules({ ...rules, enabled: ev.target.checked })}
+        />
+      </Form.Field>
+      {numberRules.map((rule) => (
+        <Form.Field name={rule.key} key={rule.key}>
+          <Form.Label>{rule.label}</Form.Label>
+          <Form.SubLabel>{rule.description}</Form.SubLabel>
+          <Form.Control
+            type="number"
+            min={0}
+            value={rules[rule.key] ?? ""}
+            onChange={(ev) => setRule(rule.key, ev.target.value)}
+          />
+        </Form.Field>
+      ))}
+      <div>
+        <button type="button" onClick={preview}>
+          Preview
+        </button>{" "}
+        <button type="button" onClick={apply}>
+          Apply now
+        </button>
+      </div>
+      {error != null && <span className="error">{error}</span>}
+      {report != null && (
+        <div>
+          <p>{reportTitle}</p>
+          <ul>
+            <li>Unliked objects: {report.unlikedObjects}</li>
+            <li>Objects over the area limit: {report.objectsOverAreaLimit}</li>
+            <li>Delivered missions: {report.deliveredMissions}</li>
+            <li>Lost cargo: {report.lostCargo}</li>
+            <li>Wasted cargo: {report.wastedBaggages}</li>
+            <li>Audit log entries: {report.auditLogEntries}</li>
+          </ul>
+        </div>
+      )}
+    </>
+  );
+};
+
+export default RetentionEditor;
@@ -33,6 +33,10 @@ export function permissionToReadableString(
       return "Manage accounts";
     case "manageServerSettings":
       return "Manage server settings";
+    case "manageObjects":
+      return "Manage objects";
+    case "manageMissions":
+      return "Manage missions";
     default:
       return permission ?? "Unknown";
   }
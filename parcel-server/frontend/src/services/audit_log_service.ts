@@ -0,0 +1,29 @@
+import { ApiResponse, callApi } from ".";
+import { AuditAction, ListAuditLogResponse } from "../api_types";
+
+export interface AuditLogFilter {
+  action?: AuditAction;
+  frontendActorId?: number;
+  gameActorId?: string;
+  targetId?: string;
+  /** RFC 3339 date */
+  from?: string;
+  /** RFC 3339 date */
+  to?: string;
+  page?: number;
+  pageSize?: number;
+}
+
+export function getAuditLog(
+  filter: AuditLogFilter
+): Promise<ApiResponse<ListAuditLogResponse>> {
+  const query = new URLSearchParams();
+
+  for (const [key, value] of Object.entries(filter)) {
+    if (value != null && value !== "") {
+      query.set(key, value.toString());
+    }
+  }
+
+  return callApi(`auditLog?${query}`, "GET");
+}
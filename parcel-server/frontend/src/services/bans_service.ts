@@ -0,0 +1,18 @@
+import { ApiResponse, callApi } from ".";
+import { BanListItem, CreateBanRequest } from "../api_types";
+
+export function getBans(
+  includeInactive: boolean
+): Promise<ApiResponse<BanListItem[]>> {
+  return callApi(`bans?includeInactive=${includeInactive}`, "GET");
+}
+
+export function createBan(
+  request: CreateBanRequest
+): Promise<ApiResponse<BanListItem>> {
+  return callApi("bans", "POST", request);
+}
+
+export function liftBan(id: number): Promise<ApiResponse<void>> {
+  return callApi(`bans/${id}`, "DELETE");
+}
@@ -0,0 +1,8 @@
+import { ApiResponse, callApi } from ".";
+import { HighwayConstruction } from "../api_types";
+
+export function listHighwayConstructions(): Promise<
+  ApiResponse<HighwayConstruction[]>
+> {
+  return callApi("highways", "GET");
+}
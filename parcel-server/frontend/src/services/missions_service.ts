@@ -0,0 +1,30 @@
+import { ApiResponse, callApi } from ".";
+import {
+  MissionDetails,
+  ProgressState,
+  SetMissionProgressStateRequest,
+} from "../api_types";
+
+export function getMission(id: string): Promise<ApiResponse<MissionDetails>> {
+  return callApi(`missions/${id}`, "GET");
+}
+
+export function setMissionProgressState(
+  id: string,
+  progressState: ProgressState,
+  clearWorker: boolean = false
+): Promise<ApiResponse<MissionDetails>> {
+  const request: SetMissionProgressStateRequest = {
+    progressState,
+    clearWorker,
+  };
+  return callApi(`missions/${id}/progressState`, "PUT", request);
+}
+
+export function resetMission(id: string): Promise<ApiResponse<MissionDetails>> {
+  return callApi(`missions/${id}/reset`, "POST");
+}
+
+export function deleteMission(id: string): Promise<ApiResponse<void>> {
+  return callApi(`missions/${id}`, "DELETE");
+}
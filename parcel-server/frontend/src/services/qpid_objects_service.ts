@@ -1,6 +1,49 @@
 import { ApiResponse, callApi } from ".";
-import { Area, QpidObject } from "../api_types";
+import {
+  Area,
+  QpidObject,
+  QpidObjectDetails,
+  SetObjectCreatorRequest,
+  SetObjectDeletedRequest,
+  SetObjectsDeletedResponse,
+} from "../api_types";
 
-export function getQpidObjects(area: Area): Promise<ApiResponse<QpidObject[]>> {
-  return callApi(`qpidObjects/${area}`, "GET");
+export function getQpidObjects(
+  area: Area,
+  includeDeleted: boolean = false
+): Promise<ApiResponse<QpidObject[]>> {
+  return callApi(
+    `qpidObjects/${area}${includeDeleted ? "?includeDeleted=true" : ""}`,
+    "GET"
+  );
+}
+
+export function getQpidObject(
+  id: string
+): Promise<ApiResponse<QpidObjectDetails>> {
+  return callApi(`qpidObjects/object/${id}`, "GET");
+}
+
+export function setQpidObjectDeleted(
+  id: string,
+  isDeleted: boolean
+): Promise<ApiResponse<QpidObjectDetails>> {
+  const request: SetObjectDeletedRequest = { isDeleted };
+  return callApi(`qpidObjects/object/${id}/deleted`, "PUT", request);
+}
+
+export function setQpidObjectCreator(
+  id: string,
+  accountId: string
+): Promise<ApiResponse<QpidObjectDetails>> {
+  const request: SetObjectCreatorRequest = { accountId };
+  return callApi(`qpidObjects/object/${id}/creator`, "PUT", request);
+}
+
+export function setQpidObjectsDeletedByCreator(
+  accountId: string,
+  isDeleted: boolean
+): Promise<ApiResponse<SetObjectsDeletedResponse>> {
+  const request: SetObjectDeletedRequest = { isDeleted };
+  return callApi(`qpidObjects/creator/${accountId}/deleted`, "PUT", request);
 }
@@ -0,0 +1,28 @@
+import { ApiResponse, callApi } from ".";
+import {
+  CreateRankingScheduleRequest,
+  RankingRecordListItem,
+  RankingScheduleListItem,
+} from "../api_types";
+
+export function getRankingSchedules(): Promise<
+  ApiResponse<RankingScheduleListItem[]>
+> {
+  return callApi("rankings/schedules", "GET");
+}
+
+export function createRankingSchedule(
+  request: CreateRankingScheduleRequest
+): Promise<ApiResponse<RankingScheduleListItem>> {
+  return callApi("rankings/schedules", "POST", request);
+}
+
+export function deleteRankingSchedule(id: number): Promise<ApiResponse<void>> {
+  return callApi(`rankings/schedules/${id}`, "DELETE");
+}
+
+export function getRankingRecords(
+  scheduleId: number
+): Promise<ApiResponse<RankingRecordListItem[]>> {
+  return callApi(`rankings/schedules/${scheduleId}/records`, "GET");
+}
@@ -0,0 +1,18 @@
+import { ApiResponse, callApi } from ".";
+import { GameSessionListItem, KickSessionsResponse } from "../api_types";
+
+export function getGameSessions(): Promise<ApiResponse<GameSessionListItem[]>> {
+  return callApi("sessions", "GET");
+}
+
+export function kickGameSession(
+  accountId: string
+): Promise<ApiResponse<KickSessionsResponse>> {
+  return callApi(`sessions/${encodeURIComponent(accountId)}`, "DELETE");
+}
+
+export function kickAllGameSessions(): Promise<
+  ApiResponse<KickSessionsResponse>
+> {
+  return callApi("sessions", "DELETE");
+}
@@ -1,5 +1,10 @@
 import { ApiResponse, callApi } from ".";
-import { SettingsValues, WhitelistEntry } from "../api_types";
+import {
+  RetentionReport,
+  RetentionSettings,
+  SettingsValues,
+  WhitelistEntry,
+} from "../api_types";
 
 export async function getServerSettings(): Promise<
   ApiResponse<SettingsValues>
@@ -13,6 +18,18 @@ export async function setServerSettings(
   return await callApi("settings/server", "PUT", values);
 }
 
+export async function previewRetentionRules(
+  rules: RetentionSettings
+): Promise<ApiResponse<RetentionReport>> {
+  return await callApi("settings/retention/preview", "POST", rules);
+}
+
+export async function applyRetentionRules(): Promise<
+  ApiResponse<RetentionReport>
+> {
+  return await callApi("settings/retention/apply", "POST");
+}
+
 export async function getWhitelist(): Promise<ApiResponse<WhitelistEntry[]>> {
   return await callApi("settings/whitelist", "GET");
 }
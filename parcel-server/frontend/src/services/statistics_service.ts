@@ -0,0 +1,41 @@
+import { ApiResponse, callApi } from ".";
+import { Statistics } from "../api_types";
+
+export interface DateRange {
+  /** RFC 3339 date */
+  from?: string;
+  /** RFC 3339 date */
+  to?: string;
+}
+
+function dateRangeQuery(range: DateRange): string {
+  const query = new URLSearchParams();
+
+  if (range.from) {
+    query.set("from", range.from);
+  }
+
+  if (range.to) {
+    query.set("to", range.to);
+  }
+
+  return query.toString();
+}
+
+export function getServerStatistics(
+  range: DateRange
+): Promise<ApiResponse<Statistics>> {
+  return callApi(`statistics?${dateRangeQuery(range)}`, "GET");
+}
+
+export function getAccountStatistics(
+  accountId: string,
+  range: DateRange
+): Promise<ApiResponse<Statistics>> {
+  return callApi(
+    `statistics/accounts/${encodeURIComponent(accountId)}?${dateRangeQuery(
+      range
+    )}`,
+    "GET"
+  );
+}
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS ranking_records,
+ranking_schedules;
\ No newline at end of file
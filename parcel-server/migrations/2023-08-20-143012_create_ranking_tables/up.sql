@@ -0,0 +1,25 @@
+CREATE TABLE ranking_schedules (
+    id INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+    category INTEGER NOT NULL,
+    start_date TIMESTAMP NOT NULL,
+    end_date TIMESTAMP NOT NULL,
+    closed_at TIMESTAMP
+);
+
+CREATE INDEX ranking_schedules_start_date_end_date_idx ON ranking_schedules (start_date, end_date);
+
+CREATE TABLE ranking_records (
+    id BIGINT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+    schedule_id INTEGER NOT NULL REFERENCES ranking_schedules(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    account_id VARCHAR NOT NULL REFERENCES accounts(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    score BIGINT NOT NULL DEFAULT 0,
+    try_count INTEGER NOT NULL DEFAULT 0,
+    clear_count INTEGER NOT NULL DEFAULT 0,
+    rank INTEGER,
+    reward_tier SMALLINT,
+    updated_at TIMESTAMP NOT NULL
+);
+
+CREATE UNIQUE INDEX ranking_records_schedule_id_account_id_idx ON ranking_records (schedule_id, account_id);
+
+CREATE INDEX ranking_records_schedule_id_score_idx ON ranking_records (schedule_id, score DESC);
\ No newline at end of file
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS roads_qpid_end_id_data_version_created_at_idx,
+roads_creator_id_idx,
+roads_location_start_id_idx,
+roads_location_end_id_idx;
\ No newline at end of file
@@ -0,0 +1,7 @@
+CREATE INDEX roads_qpid_end_id_data_version_created_at_idx ON roads (qpid_end_id, data_version, created_at DESC);
+
+CREATE INDEX roads_creator_id_idx ON roads (creator_id);
+
+CREATE INDEX roads_location_start_id_idx ON roads (location_start_id);
+
+CREATE INDEX roads_location_end_id_idx ON roads (location_end_id);
\ No newline at end of file
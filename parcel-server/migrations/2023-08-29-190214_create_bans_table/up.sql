@@ -0,0 +1,16 @@
+CREATE TABLE bans (
+    id BIGINT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+    account_id VARCHAR REFERENCES accounts(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    provider INTEGER NOT NULL,
+    provider_id VARCHAR NOT NULL,
+    reason VARCHAR NOT NULL,
+    issued_by BIGINT REFERENCES frontend_accounts(id) ON DELETE SET NULL ON UPDATE CASCADE,
+    created_at TIMESTAMP NOT NULL,
+    expires_at TIMESTAMP,
+    lifted_at TIMESTAMP,
+    lifted_by BIGINT REFERENCES frontend_accounts(id) ON DELETE SET NULL ON UPDATE CASCADE
+);
+
+CREATE INDEX bans_account_id_idx ON bans (account_id);
+
+CREATE INDEX bans_provider_provider_id_idx ON bans (provider, provider_id);
\ No newline at end of file
@@ -0,0 +1,16 @@
+CREATE TABLE audit_log (
+    id BIGINT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+    created_at TIMESTAMP NOT NULL,
+    frontend_actor_id BIGINT REFERENCES frontend_accounts(id) ON DELETE SET NULL ON UPDATE CASCADE,
+    game_actor_id VARCHAR REFERENCES accounts(id) ON DELETE SET NULL ON UPDATE CASCADE,
+    action INTEGER NOT NULL,
+    target_id VARCHAR,
+    before JSONB,
+    after JSONB
+);
+
+CREATE INDEX audit_log_created_at_idx ON audit_log (created_at);
+
+CREATE INDEX audit_log_action_idx ON audit_log (action);
+
+CREATE INDEX audit_log_target_id_idx ON audit_log (target_id);
\ No newline at end of file
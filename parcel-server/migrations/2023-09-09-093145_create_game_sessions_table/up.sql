@@ -0,0 +1,14 @@
+CREATE TABLE game_sessions (
+    token VARCHAR PRIMARY KEY,
+    provider INTEGER NOT NULL,
+    provider_id VARCHAR NOT NULL,
+    account_id VARCHAR NOT NULL REFERENCES accounts(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    logged_in_at TIMESTAMP NOT NULL,
+    last_seen_at TIMESTAMP NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    data JSONB NOT NULL
+);
+
+CREATE INDEX game_sessions_provider_idx ON game_sessions (provider, provider_id);
+
+CREATE INDEX game_sessions_expires_at_idx ON game_sessions (expires_at);
\ No newline at end of file
@@ -0,0 +1,7 @@
+CREATE TABLE shared_values (
+    key VARCHAR PRIMARY KEY,
+    value JSONB NOT NULL,
+    expires_at TIMESTAMP
+);
+
+CREATE INDEX shared_values_expires_at_idx ON shared_values (expires_at);
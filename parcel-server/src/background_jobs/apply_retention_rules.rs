@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use chrono::{Days, NaiveDateTime, Utc};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use parcel_common::api_types::frontend::{
+    audit_log::AuditAction,
+    settings::{RetentionReport, RetentionSettings},
+};
+
+use crate::{
+    data::database::{Database, DatabaseConnection},
+    db::{
+        models::audit_log::{to_audit_value, NewAuditLogEntry},
+        QueryError,
+    },
+    ServerSettings,
+};
+
+pub async fn apply_retention_rules(
+    database: Arc<Database>,
+    settings: Arc<ServerSettings>,
+) -> Result<(), anyhow::Error> {
+    let rules = settings.read().await.retention.clone();
+
+    if !rules.enabled {
+        return Ok(());
+    }
+
+    let conn = database.connect().await?;
+    let report = RetentionCandidates::find(&conn, &rules)
+        .await?
+        .remove(&conn, None)
+        .await?;
+
+    log_report(&report);
+
+    Ok(())
+}
+
+pub fn log_report(report: &RetentionReport) {
+    if report.is_empty() {
+        return;
+    }
+
+    log::info!(
+        "Retention rules removed {} unliked objects, {} objects over the area limit, {} delivered missions, {} lost cargo, {} wasted baggages and {} audit log entries",
+        report.unliked_objects,
+        report.objects_over_area_limit,
+        report.delivered_missions,
+        report.lost_cargo,
+        report.wasted_baggages,
+        report.audit_log_entries
+    );
+}
+
+/// The ids of everything that the retention rules would remove.
+#[derive(Debug, Default)]
+pub struct RetentionCandidates {
+    pub unliked_objects: Vec<String>,
+    pub objects_over_area_limit: Vec<String>,
+    pub delivered_missions: Vec<String>,
+    pub lost_cargo: Vec<String>,
+    pub wasted_baggages: Vec<String>,
+    pub audit_log_entries: Vec<i64>,
+}
+
+impl RetentionCandidates {
+    pub async fn find(
+        conn: &DatabaseConnection<'_>,
+        rules: &RetentionSettings,
+    ) -> Result<Self, QueryError> {
+        let mut result = Self::default();
+        let objects = conn.qpid_objects();
+        let missions = conn.missions();
+
+        if let Some(date) = days_ago(rules.delete_unliked_objects_after_days) {
+            result.unliked_objects = objects.find_unliked_object_ids(&date).await?;
+        }
+
+        if let Some(limit) = rules.max_objects_per_qpid_area {
+            // Unliked objects are removed first so they don't count towards the limit
+            result.objects_over_area_limit = objects
+                .find_object_ids_over_area_limit(limit as usize, &result.unliked_objects)
+                .await?;
+        }
+
+        if let Some(date) = days_ago(rules.delete_delivered_missions_after_days) {
+            result.delivered_missions = missions.find_delivered_mission_ids(&date).await?;
+        }
+
+        if let Some(date) = days_ago(rules.delete_lost_cargo_after_days) {
+            result.lost_cargo = missions.find_lost_cargo_ids(&date).await?;
+        }
+
+        if let Some(date) = days_ago(rules.delete_wasted_baggages_after_days) {
+            result.wasted_baggages = conn
+                .wasted_baggages()
+                .find_ids_created_before(&date)
+                .await?;
+        }
+
+        if let Some(date) = days_ago(rules.delete_audit_log_after_days) {
+            result.audit_log_entries = conn.audit_log().find_ids_created_before(&date).await?;
+        }
+
+        Ok(result)
+    }
+
+    /// Returns what would be removed without changing anything.
+    pub fn report(&self) -> RetentionReport {
+        RetentionReport {
+            unliked_objects: self.unliked_objects.len(),
+            objects_over_area_limit: self.objects_over_area_limit.len(),
+            delivered_missions: self.delivered_missions.len(),
+            lost_cargo: self.lost_cargo.len(),
+            wasted_baggages: self.wasted_baggages.len(),
+            audit_log_entries: self.audit_log_entries.len(),
+        }
+    }
+
+    /// Removes the candidates and returns what was actually removed.
+    ///
+    /// Objects are marked as deleted the same way as when a player deletes them, everything else is permanently deleted.
+    /// The counts are recorded in the audit log, with the frontend account as the actor if the rules were applied from the frontend.
+    pub async fn remove(
+        self,
+        conn: &DatabaseConnection<'_>,
+        frontend_actor_id: Option<i64>,
+    ) -> Result<RetentionReport, QueryError> {
+        conn.transaction(|conn| {
+            async move {
+                let objects = conn.qpid_objects();
+                let missions = conn.missions();
+
+                let report = RetentionReport {
+                    unliked_objects: objects.set_deleted_by_ids(&self.unliked_objects).await?,
+                    objects_over_area_limit: objects
+                        .set_deleted_by_ids(&self.objects_over_area_limit)
+                        .await?,
+                    delivered_missions: missions
+                        .delete_missions_by_ids(&self.delivered_missions)
+                        .await?,
+                    lost_cargo: missions.delete_missions_by_ids(&self.lost_cargo).await?,
+                    wasted_baggages: conn
+                        .wasted_baggages()
+                        .delete_by_ids(&self.wasted_baggages)
+                        .await?,
+                    audit_log_entries: conn
+                        .audit_log()
+                        .delete_by_ids(&self.audit_log_entries)
+                        .await?,
+                };
+
+                conn.audit_log()
+                    .record(&NewAuditLogEntry {
+                        frontend_actor_id,
+                        game_actor_id: None,
+                        action: AuditAction::ApplyRetentionRules,
+                        target_id: None,
+                        before: None,
+                        after: to_audit_value(&report),
+                    })
+                    .await?;
+
+                Ok(report)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+}
+
+fn days_ago(days: Option<u32>) -> Option<NaiveDateTime> {
+    Utc::now()
+        .naive_utc()
+        .checked_sub_days(Days::new(days?.into()))
+}
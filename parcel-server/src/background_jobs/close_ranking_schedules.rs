@@ -0,0 +1,10 @@
+use std::sync::Arc;
+
+use crate::data::database::Database;
+
+pub async fn close_ranking_schedules(database: Arc<Database>) -> Result<(), anyhow::Error> {
+    let conn = database.connect().await?;
+    conn.rankings().close_ended_schedules().await?;
+
+    Ok(())
+}
@@ -1,10 +1,16 @@
 use std::sync::Arc;
 
+use chrono::Utc;
+
 use crate::data::database::Database;
 
+/// Deletes expired frontend and game sessions, and expired shared values such as revoked frontend sessions.
 pub async fn delete_expired_sessions(database: Arc<Database>) -> Result<(), anyhow::Error> {
     let conn = database.connect().await?;
     conn.frontend_accounts().delete_expired_sessions().await?;
+    let now = Utc::now().naive_utc();
+    conn.game_sessions().delete_expired(&now).await?;
+    conn.shared_values().delete_expired(&now).await?;
 
     Ok(())
 }
@@ -1,3 +1,5 @@
+pub mod apply_retention_rules;
+mod close_ranking_schedules;
 mod delete_expired_sessions;
 
 use std::{sync::Arc, time::Duration};
@@ -5,16 +7,20 @@ use std::{sync::Arc, time::Duration};
 use futures_util::FutureExt;
 use tokio_cron_scheduler::{Job, JobScheduler, JobSchedulerError};
 
-use crate::data::database::Database;
+use crate::{data::database::Database, metrics, ServerSettings};
 
-pub async fn create_scheduler(database: Arc<Database>) -> Result<JobScheduler, JobSchedulerError> {
+pub async fn create_scheduler(
+    database: Arc<Database>,
+    server_settings: Arc<ServerSettings>,
+) -> Result<JobScheduler, JobSchedulerError> {
     let scheduler = JobScheduler::new().await?;
 
+    let db = database.clone();
     scheduler
         .add(Job::new_repeated_async(
             Duration::from_secs(60 * 10),
             move |_uuid, _lock| {
-                let db_clone = database.clone();
+                let db_clone = db.clone();
                 async move {
                     log_result(
                         "DeleteExpiredSessions",
@@ -26,14 +32,51 @@ pub async fn create_scheduler(database: Arc<Database>) -> Result<JobScheduler, J
         )?)
         .await?;
 
+    let db = database.clone();
+    scheduler
+        .add(Job::new_repeated_async(
+            Duration::from_secs(60 * 60),
+            move |_uuid, _lock| {
+                let db_clone = db.clone();
+                let settings_clone = server_settings.clone();
+                async move {
+                    log_result(
+                        "ApplyRetentionRules",
+                        apply_retention_rules::apply_retention_rules(db_clone, settings_clone)
+                            .await,
+                    );
+                }
+                .boxed()
+            },
+        )?)
+        .await?;
+
+    scheduler
+        .add(Job::new_repeated_async(
+            Duration::from_secs(60 * 10),
+            move |_uuid, _lock| {
+                let db_clone = database.clone();
+                async move {
+                    log_result(
+                        "CloseRankingSchedules",
+                        close_ranking_schedules::close_ranking_schedules(db_clone).await,
+                    );
+                }
+                .boxed()
+            },
+        )?)
+        .await?;
+
     Ok(scheduler)
 }
 
-/// Takes a result and logs an error if it failed
+/// Takes a result and logs an error if it failed. The outcome is also recorded in the metrics.
 fn log_result<T, E>(job_name: &str, result: Result<T, E>)
 where
     E: std::fmt::Display + std::fmt::Debug,
 {
+    metrics::count_background_job(job_name, result.is_ok());
+
     if let Err(err) = &result {
         log::error!("{job_name} failed: {err}");
     }
@@ -238,6 +238,28 @@ impl<'db> Accounts<'db> {
     }
 }
 
+/// Accounts whose objects and roads are preferred over those of other accounts.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PrioritizedCreators<'a> {
+    /// Accounts the game asked for, these are preferred over all other accounts.
+    pub priority_ids: &'a [&'a str],
+    /// Accounts the player has a strand contract with.
+    pub contract_ids: &'a [&'a str],
+}
+
+impl PrioritizedCreators<'_> {
+    /// Returns how preferred the creator is, lower is better.
+    pub fn rank(&self, creator_id: &str) -> u8 {
+        if self.priority_ids.contains(&creator_id) {
+            0
+        } else if self.contract_ids.contains(&creator_id) {
+            1
+        } else {
+            2
+        }
+    }
+}
+
 /// Generates a 32 character long account id.
 ///
 /// The first few characters are always zygo_**** (where **** is STATIC_BYTES encoded as base64), followed by random bytes encoded as base64, up to a total of 20 bytes (not including zygo_).
@@ -246,7 +268,7 @@ impl<'db> Accounts<'db> {
 ///
 /// When this value does not "conform" to some format some things don't work in the game, such as displaying player names associated with this id.
 /// I haven't been able to figure out what exactly it is but this seems to work from the somewhat limited testing i've done.
-fn generate_account_id() -> String {
+pub fn generate_account_id() -> String {
     const STATIC_BYTES: [u8; 8] = [0xd8, 0x9c, 0x20, 0xf6, 0x97, 0xe0, 0xe6, 0x86];
     let mut id = vec![0; 20];
     id[..8].copy_from_slice(&STATIC_BYTES);
@@ -0,0 +1,126 @@
+use chrono::{NaiveDateTime, Utc};
+use diesel::{pg::Pg, prelude::*};
+use diesel_async::RunQueryDsl;
+use parcel_common::api_types::frontend::audit_log::AuditAction;
+
+use crate::db::{
+    models::audit_log::{AuditLogEntry, NewAuditLogEntry},
+    schema::audit_log,
+    QueryError,
+};
+
+use super::DatabaseConnection;
+
+/// Filters for listing audit log entries. Every filter that is set has to match.
+#[derive(Debug, Default)]
+pub struct AuditLogFilter<'a> {
+    pub action: Option<AuditAction>,
+    pub frontend_actor_id: Option<i64>,
+    pub game_actor_id: Option<&'a str>,
+    pub target_id: Option<&'a str>,
+    pub from: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+}
+
+impl<'a> AuditLogFilter<'a> {
+    fn apply(&self, mut query: audit_log::BoxedQuery<'a, Pg>) -> audit_log::BoxedQuery<'a, Pg> {
+        use crate::db::schema::audit_log::dsl;
+
+        if let Some(action) = self.action {
+            query = query.filter(dsl::action.eq(action));
+        }
+
+        if let Some(frontend_actor_id) = self.frontend_actor_id {
+            query = query.filter(dsl::frontend_actor_id.eq(frontend_actor_id));
+        }
+
+        if let Some(game_actor_id) = self.game_actor_id {
+            query = query.filter(dsl::game_actor_id.eq(game_actor_id));
+        }
+
+        if let Some(target_id) = self.target_id {
+            query = query.filter(dsl::target_id.eq(target_id));
+        }
+
+        if let Some(from) = self.from {
+            query = query.filter(dsl::created_at.ge(from));
+        }
+
+        if let Some(to) = self.to {
+            query = query.filter(dsl::created_at.lt(to));
+        }
+
+        query
+    }
+}
+
+pub struct AuditLog<'db> {
+    connection: &'db DatabaseConnection<'db>,
+}
+
+impl<'db> AuditLog<'db> {
+    pub fn new(connection: &'db DatabaseConnection) -> Self {
+        Self { connection }
+    }
+
+    pub async fn record(&self, entry: &NewAuditLogEntry<'_>) -> Result<(), QueryError> {
+        use crate::db::schema::audit_log::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        diesel::insert_into(dsl::audit_log)
+            .values((entry, dsl::created_at.eq(Utc::now().naive_utc())))
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns a page of the entries that match the filter, newest first, and the total number of matching entries.
+    pub async fn list(
+        &self,
+        filter: &AuditLogFilter<'_>,
+        offset: i64,
+        limit: i64,
+    ) -> Result<(Vec<AuditLogEntry>, i64), QueryError> {
+        use crate::db::schema::audit_log::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        let total = filter
+            .apply(dsl::audit_log.into_boxed())
+            .count()
+            .get_result(conn)
+            .await?;
+        let entries = filter
+            .apply(dsl::audit_log.into_boxed())
+            .order_by((dsl::created_at.desc(), dsl::id.desc()))
+            .offset(offset)
+            .limit(limit)
+            .get_results(conn)
+            .await?;
+
+        Ok((entries, total))
+    }
+
+    pub async fn find_ids_created_before(
+        &self,
+        date: &NaiveDateTime,
+    ) -> Result<Vec<i64>, QueryError> {
+        use crate::db::schema::audit_log::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::audit_log
+            .select(dsl::id)
+            .filter(dsl::created_at.lt(date))
+            .get_results(conn)
+            .await?)
+    }
+
+    pub async fn delete_by_ids(&self, ids: &[i64]) -> Result<usize, QueryError> {
+        use crate::db::schema::audit_log::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(diesel::delete(dsl::audit_log.filter(dsl::id.eq_any(ids)))
+            .execute(conn)
+            .await?)
+    }
+}
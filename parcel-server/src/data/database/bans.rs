@@ -0,0 +1,107 @@
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use parcel_common::api_types::auth::Provider;
+
+use crate::db::{
+    models::ban::{Ban, NewBan},
+    QueryError,
+};
+
+use super::DatabaseConnection;
+
+pub struct Bans<'db> {
+    connection: &'db DatabaseConnection<'db>,
+}
+
+impl<'db> Bans<'db> {
+    pub fn new(connection: &'db DatabaseConnection) -> Self {
+        Self { connection }
+    }
+
+    pub async fn create(&self, ban: &NewBan<'_>) -> Result<Ban, QueryError> {
+        use crate::db::schema::bans::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(diesel::insert_into(dsl::bans)
+            .values(ban)
+            .get_result(conn)
+            .await?)
+    }
+
+    pub async fn get_by_id(&self, id: i64) -> Result<Option<Ban>, QueryError> {
+        use crate::db::schema::bans::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::bans.find(id).first(conn).await.optional()?)
+    }
+
+    /// Returns the active ban that affects the account or provider id, if there is one.
+    ///
+    /// Permanent bans are returned before timed ones, and then the ban that expires last.
+    pub async fn find_active_ban(
+        &self,
+        account_id: Option<&str>,
+        provider: Provider,
+        provider_id: &str,
+    ) -> Result<Option<Ban>, QueryError> {
+        use crate::db::schema::bans::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+        let now = Utc::now().naive_utc();
+
+        let mut query = dsl::bans
+            .filter(dsl::lifted_at.is_null())
+            .filter(dsl::expires_at.is_null().or(dsl::expires_at.gt(now)))
+            .order_by(dsl::expires_at.desc().nulls_first())
+            .into_boxed();
+
+        let provider_filter = dsl::provider
+            .eq(provider)
+            .and(dsl::provider_id.eq(provider_id));
+
+        query = match account_id {
+            Some(account_id) => query.filter(provider_filter.or(dsl::account_id.eq(account_id))),
+            None => query.filter(provider_filter),
+        };
+
+        Ok(query.first(conn).await.optional()?)
+    }
+
+    /// Returns all bans, newest first. Lifted and expired bans are only included if `include_inactive` is true.
+    pub async fn list(&self, include_inactive: bool) -> Result<Vec<Ban>, QueryError> {
+        use crate::db::schema::bans::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        let mut query = dsl::bans.order_by(dsl::created_at.desc()).into_boxed();
+
+        if !include_inactive {
+            query = query.filter(dsl::lifted_at.is_null()).filter(
+                dsl::expires_at
+                    .is_null()
+                    .or(dsl::expires_at.gt(Utc::now().naive_utc())),
+            );
+        }
+
+        Ok(query.get_results(conn).await?)
+    }
+
+    /// Lifts the ban so that it no longer applies.
+    ///
+    /// Returns None if the ban doesn't exist or if it has already been lifted.
+    pub async fn lift(
+        &self,
+        id: i64,
+        lifted_by: Option<i64>,
+        lifted_at: &NaiveDateTime,
+    ) -> Result<Option<Ban>, QueryError> {
+        use crate::db::schema::bans::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(diesel::update(dsl::bans.find(id))
+            .filter(dsl::lifted_at.is_null())
+            .set((dsl::lifted_at.eq(lifted_at), dsl::lifted_by.eq(lifted_by)))
+            .get_result(conn)
+            .await
+            .optional()?)
+    }
+}
@@ -0,0 +1,170 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use parcel_common::api_types::auth::Provider;
+
+use crate::db::{models::game_session::GameSession, QueryError};
+
+use super::DatabaseConnection;
+
+/// Game sessions. Sessions that have expired are ignored by every query except `delete_expired`.
+pub struct GameSessions<'db> {
+    connection: &'db DatabaseConnection<'db>,
+}
+
+impl<'db> GameSessions<'db> {
+    pub fn new(connection: &'db DatabaseConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Adds the session, or replaces it if a session with the same token exists.
+    pub async fn save(&self, session: &GameSession) -> Result<(), QueryError> {
+        use crate::db::schema::game_sessions::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        diesel::insert_into(dsl::game_sessions)
+            .values(session)
+            .on_conflict(dsl::token)
+            .do_update()
+            .set(session)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_by_token(
+        &self,
+        token: &str,
+        now: &NaiveDateTime,
+    ) -> Result<Option<GameSession>, QueryError> {
+        use crate::db::schema::game_sessions::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::game_sessions
+            .find(token)
+            .filter(dsl::expires_at.gt(now))
+            .first(conn)
+            .await
+            .optional()?)
+    }
+
+    /// Returns the token of the newest session that belongs to the provider id.
+    pub async fn find_token_by_provider_id(
+        &self,
+        provider: Provider,
+        provider_id: &str,
+        now: &NaiveDateTime,
+    ) -> Result<Option<String>, QueryError> {
+        use crate::db::schema::game_sessions::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::game_sessions
+            .select(dsl::token)
+            .filter(dsl::provider.eq(provider))
+            .filter(dsl::provider_id.eq(provider_id))
+            .filter(dsl::expires_at.gt(now))
+            .order_by(dsl::logged_in_at.desc())
+            .first(conn)
+            .await
+            .optional()?)
+    }
+
+    pub async fn list(&self, now: &NaiveDateTime) -> Result<Vec<GameSession>, QueryError> {
+        use crate::db::schema::game_sessions::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::game_sessions
+            .filter(dsl::expires_at.gt(now))
+            .order_by(dsl::logged_in_at.asc())
+            .get_results(conn)
+            .await?)
+    }
+
+    pub async fn count(&self, now: &NaiveDateTime) -> Result<i64, QueryError> {
+        use crate::db::schema::game_sessions::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::game_sessions
+            .filter(dsl::expires_at.gt(now))
+            .count()
+            .get_result(conn)
+            .await?)
+    }
+
+    /// Updates the last seen date and expiry date of the session, if it hasn't expired.
+    pub async fn update_last_seen(
+        &self,
+        token: &str,
+        last_seen_at: &NaiveDateTime,
+        expires_at: &NaiveDateTime,
+    ) -> Result<(), QueryError> {
+        use crate::db::schema::game_sessions::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        diesel::update(dsl::game_sessions.find(token))
+            .filter(dsl::expires_at.gt(last_seen_at))
+            .set((
+                dsl::last_seen_at.eq(last_seen_at),
+                dsl::expires_at.eq(expires_at),
+            ))
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Deletes the session and returns true if it existed.
+    pub async fn delete(&self, token: &str) -> Result<bool, QueryError> {
+        use crate::db::schema::game_sessions::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        let num_deleted = diesel::delete(dsl::game_sessions.find(token))
+            .execute(conn)
+            .await?;
+
+        Ok(num_deleted > 0)
+    }
+
+    /// Deletes every session of the account that hasn't expired and returns the tokens of the deleted sessions.
+    pub async fn delete_by_account(
+        &self,
+        account_id: &str,
+        now: &NaiveDateTime,
+    ) -> Result<Vec<String>, QueryError> {
+        use crate::db::schema::game_sessions::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(diesel::delete(
+            dsl::game_sessions
+                .filter(dsl::account_id.eq(account_id))
+                .filter(dsl::expires_at.gt(now)),
+        )
+        .returning(dsl::token)
+        .get_results(conn)
+        .await?)
+    }
+
+    /// Deletes every session that hasn't expired and returns the number of deleted sessions.
+    pub async fn delete_all(&self, now: &NaiveDateTime) -> Result<usize, QueryError> {
+        use crate::db::schema::game_sessions::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(
+            diesel::delete(dsl::game_sessions.filter(dsl::expires_at.gt(now)))
+                .execute(conn)
+                .await?,
+        )
+    }
+
+    pub async fn delete_expired(&self, now: &NaiveDateTime) -> Result<usize, QueryError> {
+        use crate::db::schema::game_sessions::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(
+            diesel::delete(dsl::game_sessions.filter(dsl::expires_at.le(now)))
+                .execute(conn)
+                .await?,
+        )
+    }
+}
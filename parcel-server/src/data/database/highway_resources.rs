@@ -1,7 +1,12 @@
 use std::collections::HashMap;
 
 use chrono::{DateTime, Utc};
-use diesel::{dsl::not, prelude::*};
+use diesel::{
+    dsl::not,
+    prelude::*,
+    sql_query,
+    sql_types::{BigInt, Integer, Text},
+};
 use diesel_async::{
     scoped_futures::ScopedFutureExt, AsyncConnection, AsyncPgConnection, RunQueryDsl,
 };
@@ -18,6 +23,17 @@ use crate::db::{
 
 use super::DatabaseConnection;
 
+/// The total number of resources an account devoted to a highway construction.
+#[derive(Debug, QueryableByName)]
+pub struct ConstructionContributor {
+    #[diesel(sql_type = Integer)]
+    pub construction_id: i32,
+    #[diesel(sql_type = Text)]
+    pub account_id: String,
+    #[diesel(sql_type = BigInt)]
+    pub num_resources: i64,
+}
+
 pub struct HighwayResources<'db> {
     connection: &'db DatabaseConnection<'db>,
 }
@@ -142,6 +158,47 @@ impl<'db> HighwayResources<'db> {
         Ok(resources)
     }
 
+    /// Returns the total resources of every construction and resource type.
+    pub async fn list_total_resources(&self) -> Result<Vec<TotalHighwayResources>, QueryError> {
+        use crate::db::schema::total_highway_resources::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        let resources = dsl::total_highway_resources
+            .order((dsl::construction_id, dsl::resource_id))
+            .get_results(conn)
+            .await?;
+
+        Ok(resources)
+    }
+
+    /// Returns the accounts that devoted the most resources of any type to each construction.
+    ///
+    /// The result is sorted by construction id and then by the number of devoted resources in descending order.
+    pub async fn get_top_contributors(
+        &self,
+        limit_per_construction: i64,
+    ) -> Result<Vec<ConstructionContributor>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(sql_query(
+            "SELECT construction_id, account_id, num_resources \
+            FROM ( \
+                SELECT construction_id, account_id, SUM(num_resources)::bigint AS num_resources, \
+                    ROW_NUMBER() OVER ( \
+                        PARTITION BY construction_id \
+                        ORDER BY SUM(num_resources) DESC, account_id \
+                    ) AS rank \
+                FROM devoted_highway_resources \
+                GROUP BY construction_id, account_id \
+            ) AS contributors \
+            WHERE rank <= $1 \
+            ORDER BY construction_id, rank",
+        )
+        .bind::<BigInt, _>(limit_per_construction)
+        .get_results(conn)
+        .await?)
+    }
+
     async fn add_total_resources(
         &self,
         conn: &mut AsyncPgConnection,
@@ -1,7 +1,12 @@
 use std::collections::HashMap;
 
-use chrono::{Days, Utc};
-use diesel::{dsl::not, prelude::*};
+use chrono::{Days, NaiveDateTime, Utc};
+use diesel::{
+    dsl::not,
+    prelude::*,
+    sql_query,
+    sql_types::{Array, BigInt, Integer, Text},
+};
 use diesel_async::{
     scoped_futures::ScopedFutureExt, AsyncConnection, AsyncPgConnection, RunQueryDsl,
 };
@@ -33,6 +38,21 @@ use crate::db::{
 
 use super::DatabaseConnection;
 
+/// A mission that matches a search, without any of the mission's data.
+#[derive(Debug, QueryableByName)]
+pub struct MissionCandidate {
+    #[diesel(sql_type = Text)]
+    pub id: String,
+    #[diesel(sql_type = Integer)]
+    pub qpid_id: i32,
+    #[diesel(sql_type = Text)]
+    pub creator_id: String,
+    #[diesel(sql_type = Integer)]
+    pub online_mission_type: OnlineMissionType,
+    #[diesel(sql_type = BigInt)]
+    pub num_baggages: i64,
+}
+
 pub struct Missions<'db> {
     connection: &'db DatabaseConnection<'db>,
 }
@@ -232,6 +252,110 @@ impl<'db> Missions<'db> {
         .await?)
     }
 
+    /// Deletes the mission regardless of who created it. Returns false if the mission doesn't exist.
+    pub async fn delete_mission_by_id(&self, mission_id: &str) -> Result<bool, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+        let affected_rows = diesel::delete(dsl::missions.filter(dsl::id.eq(mission_id)))
+            .execute(conn)
+            .await?;
+
+        Ok(affected_rows != 0)
+    }
+
+    /// Sets the progress state of the mission and optionally removes the current worker.
+    pub async fn set_progress_state(
+        &self,
+        mission_id: &str,
+        progress_state: ProgressState,
+        clear_worker: bool,
+    ) -> Result<Option<Mission>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+        let mission = diesel::update(dsl::missions)
+            .filter(dsl::id.eq(mission_id))
+            .set(&ChangeMission {
+                progress_state: Some(progress_state),
+                worker_id: clear_worker.then_some(None),
+                ..Default::default()
+            })
+            .get_result(conn)
+            .await
+            .optional()?;
+
+        Ok(mission)
+    }
+
+    /// Returns a mission that's in progress back to the available state and removes the worker.
+    ///
+    /// Returns None if the mission doesn't exist or if it's not in progress.
+    pub async fn reset_progress(&self, mission_id: &str) -> Result<Option<Mission>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+        let mission = diesel::update(dsl::missions)
+            .filter(dsl::id.eq(mission_id))
+            .filter(dsl::progress_state.eq(ProgressState::Progress))
+            .set(&ChangeMission {
+                progress_state: Some(ProgressState::Available),
+                worker_id: Some(None),
+                ..Default::default()
+            })
+            .get_result(conn)
+            .await
+            .optional()?;
+
+        Ok(mission)
+    }
+
+    /// Returns the ids of delivered missions that were registered before the specified date.
+    pub async fn find_delivered_mission_ids(
+        &self,
+        registered_before: &NaiveDateTime,
+    ) -> Result<Vec<String>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::missions
+            .filter(dsl::progress_state.eq(ProgressState::Success))
+            .filter(dsl::registered_time.lt(registered_before))
+            .select(dsl::id)
+            .get_results(conn)
+            .await?)
+    }
+
+    /// Returns the ids of lost cargo that nobody has picked up and that was registered before the specified date.
+    ///
+    /// Cargo in shared lockers is not included.
+    pub async fn find_lost_cargo_ids(
+        &self,
+        registered_before: &NaiveDateTime,
+    ) -> Result<Vec<String>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::missions
+            .filter(
+                dsl::online_mission_type
+                    .eq_any([OnlineMissionType::Private, OnlineMissionType::Dynamic]),
+            )
+            .filter(dsl::mission_type.eq(MissionType::LostObject))
+            .filter(dsl::progress_state.eq_any([ProgressState::Available, ProgressState::Ready]))
+            .filter(dsl::qpid_end_location.ne(-1))
+            .filter(dsl::registered_time.lt(registered_before))
+            .select(dsl::id)
+            .get_results(conn)
+            .await?)
+    }
+
+    /// Deletes the missions regardless of who created them. Returns the number of deleted missions.
+    pub async fn delete_missions_by_ids(
+        &self,
+        mission_ids: &[String],
+    ) -> Result<usize, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(
+            diesel::delete(dsl::missions.filter(dsl::id.eq_any(mission_ids)))
+                .execute(conn)
+                .await?,
+        )
+    }
+
     pub async fn find_missions(
         &self,
         online_types: &[OnlineMissionType],
@@ -256,6 +380,56 @@ impl<'db> Missions<'db> {
         Ok(query.get_results(conn).await?)
     }
 
+    /// Finds missions the same way as `find_missions`, but only returns what's needed to decide which missions to send to a player.
+    ///
+    /// At most `limit_per_creator` missions are returned from each creator in each qpid, preferring the most recently registered ones.
+    /// The result is ordered by qpid, creator and then registration time.
+    pub async fn find_mission_candidates(
+        &self,
+        online_types: &[OnlineMissionType],
+        mission_types: &[MissionType],
+        exclude_accounts: &[&str],
+        progress_states: &[ProgressState],
+        qpid_ids: &[i32],
+        limit_per_creator: i64,
+    ) -> Result<Vec<MissionCandidate>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(sql_query(
+            "SELECT c.id, c.qpid_id, c.creator_id, c.online_mission_type, \
+                (SELECT COUNT(*) FROM mission_baggages b WHERE b.mission_id = c.id) AS num_baggages \
+            FROM ( \
+                SELECT m.id, m.qpid_id, m.creator_id, m.online_mission_type, \
+                    ROW_NUMBER() OVER (PARTITION BY m.qpid_id, m.creator_id ORDER BY m.registered_time DESC, m.id) AS creator_rank \
+                FROM missions m \
+                WHERE m.online_mission_type = ANY($1) \
+                    AND m.mission_type = ANY($2) \
+                    AND m.progress_state = ANY($3) \
+                    AND m.qpid_id = ANY($4) \
+                    AND NOT (m.creator_id = ANY($5)) \
+            ) c \
+            WHERE c.creator_rank <= $6 \
+            ORDER BY c.qpid_id, c.creator_id, c.creator_rank",
+        )
+        .bind::<Array<Integer>, _>(online_types)
+        .bind::<Array<Integer>, _>(mission_types)
+        .bind::<Array<Integer>, _>(progress_states)
+        .bind::<Array<Integer>, _>(qpid_ids)
+        .bind::<Array<Text>, _>(exclude_accounts)
+        .bind::<BigInt, _>(limit_per_creator)
+        .get_results(conn)
+        .await?)
+    }
+
+    pub async fn get_by_ids(&self, mission_ids: &[String]) -> Result<Vec<Mission>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::missions
+            .filter(dsl::id.eq_any(mission_ids))
+            .get_results(conn)
+            .await?)
+    }
+
     pub async fn get_ordered_missions(&self, account_id: &str) -> Result<Vec<Mission>, QueryError> {
         let conn = &mut *self.connection.get_pg_connection().await;
 
@@ -598,7 +772,7 @@ impl IntoDsApiType for DbMission {
 
 /// Generates a 23 character long mission id.
 /// The first character will always be 'm'.
-fn generate_mission_id() -> String {
+pub fn generate_mission_id() -> String {
     const CHARS: &[u8] = b"aAbBcCdDeEfFgGhHiIjJkKlLmMnNoOpPqQrRsStTuUvVwWxXyYzZ0123456789";
     let mut result = String::with_capacity(23);
     result.push('m');
@@ -1,14 +1,21 @@
 pub mod accounts;
+pub mod audit_log;
+pub mod bans;
 pub mod frontend_accounts;
+pub mod game_sessions;
 pub mod highway_resources;
 pub mod likes;
 pub mod missions;
 pub mod player_profiles;
 pub mod qpid_objects;
+pub mod rankings;
 pub mod roads;
+pub mod shared_values;
+pub mod statistics;
 pub mod wasted_baggages;
+pub mod world_snapshots;
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
 use diesel::{result::Error as DieselError, ConnectionResult};
 use diesel_async::{
@@ -17,12 +24,14 @@ use diesel_async::{
 };
 use futures_util::lock::{Mutex, MutexLockFuture};
 
-use crate::db::QueryError;
+use crate::{db::QueryError, metrics};
 
 use self::{
-    accounts::Accounts, frontend_accounts::FrontendAccounts, highway_resources::HighwayResources,
-    likes::Likes, missions::Missions, player_profiles::PlayerProfiles, qpid_objects::QpidObjects,
-    roads::Roads, wasted_baggages::WastedBaggages,
+    accounts::Accounts, audit_log::AuditLog, bans::Bans, frontend_accounts::FrontendAccounts,
+    game_sessions::GameSessions, highway_resources::HighwayResources, likes::Likes,
+    missions::Missions, player_profiles::PlayerProfiles, qpid_objects::QpidObjects,
+    rankings::Rankings, roads::Roads, shared_values::SharedValues, statistics::Statistics,
+    wasted_baggages::WastedBaggages, world_snapshots::WorldSnapshots,
 };
 
 pub struct Database {
@@ -37,7 +46,11 @@ impl Database {
     }
 
     pub async fn connect(&self) -> ConnectionResult<DatabaseConnection> {
-        let conn = AsyncPgConnection::establish(&self.database_url).await?;
+        let start = Instant::now();
+        let conn = AsyncPgConnection::establish(&self.database_url).await;
+        metrics::observe_database_connect(start.elapsed());
+
+        let conn = conn?;
         Ok(DatabaseConnection::new(self, conn))
     }
 }
@@ -92,10 +105,22 @@ impl<'db> DatabaseConnection<'db> {
         Accounts::new(self)
     }
 
+    pub fn audit_log(&self) -> AuditLog {
+        AuditLog::new(self)
+    }
+
+    pub fn bans(&self) -> Bans {
+        Bans::new(self)
+    }
+
     pub fn frontend_accounts(&self) -> FrontendAccounts {
         FrontendAccounts::new(self)
     }
 
+    pub fn game_sessions(&self) -> GameSessions {
+        GameSessions::new(self)
+    }
+
     pub fn player_profiles(&self) -> PlayerProfiles {
         PlayerProfiles::new(self)
     }
@@ -123,4 +148,20 @@ impl<'db> DatabaseConnection<'db> {
     pub fn roads(&self) -> Roads {
         Roads::new(self)
     }
+
+    pub fn rankings(&self) -> Rankings {
+        Rankings::new(self)
+    }
+
+    pub fn shared_values(&self) -> SharedValues {
+        SharedValues::new(self)
+    }
+
+    pub fn statistics(&self) -> Statistics {
+        Statistics::new(self)
+    }
+
+    pub fn world_snapshots(&self) -> WorldSnapshots {
+        WorldSnapshots::new(self)
+    }
 }
@@ -1,7 +1,12 @@
 use std::collections::HashMap;
 
-use chrono::Utc;
-use diesel::{dsl::not, prelude::*};
+use chrono::{NaiveDateTime, Utc};
+use diesel::{
+    dsl::not,
+    prelude::*,
+    sql_query,
+    sql_types::{Array, BigInt, Integer, Text},
+};
 use diesel_async::{
     scoped_futures::ScopedFutureExt, AsyncConnection, AsyncPgConnection, RunQueryDsl,
 };
@@ -31,7 +36,7 @@ use crate::db::{
     QueryError,
 };
 
-use super::DatabaseConnection;
+use super::{accounts::PrioritizedCreators, DatabaseConnection};
 
 #[derive(Debug)]
 pub struct DbQpidObject {
@@ -91,6 +96,12 @@ impl TryIntoDsApiType for DbQpidObject {
     }
 }
 
+#[derive(QueryableByName)]
+struct ObjectId {
+    #[diesel(sql_type = Text)]
+    id: String,
+}
+
 pub enum ChangeInfo<'a> {
     Stone(&'a ChangeStoneInfo),
     Parking(&'a ChangeParkingInfo<'a>),
@@ -100,6 +111,141 @@ pub enum ChangeInfo<'a> {
     // bridge and comment is intentionally excluded
 }
 
+/// Objects of the same type that are closer to each other than this (in meters) are considered duplicates.
+const OVERLAP_DISTANCE: f64 = 1.5;
+
+/// How many more objects of each type than the game asks for are loaded, so that objects that overlap with a better ranked object can be replaced.
+const OVERLAP_MARGIN: i64 = 32;
+
+/// Decides which objects are returned when there are more objects in a qpid area than the game asks for.
+pub struct ObjectSelection<'a> {
+    pub creators: PrioritizedCreators<'a>,
+    /// Max number of objects of each type. Types that aren't included are only limited by `limit`.
+    pub type_limits: &'a HashMap<ObjectType, i32>,
+    /// Max number of objects in total.
+    pub limit: usize,
+}
+
+impl ObjectSelection<'_> {
+    /// Picks objects from a list that's sorted by likes and then recency.
+    ///
+    /// Objects that overlap with a better ranked object of the same type are left out.
+    fn select(&self, mut objects: Vec<QpidObject>) -> Vec<QpidObject> {
+        // The sort is stable so objects with the same relationship stay sorted by likes and recency
+        objects.sort_by_key(|object| self.creators.rank(&object.creator_id));
+
+        let mut result = Vec::new();
+        let mut type_counts = HashMap::<ObjectType, i32>::new();
+        let mut placed = PlacedObjects::default();
+
+        for object in objects {
+            if result.len() >= self.limit {
+                break;
+            }
+
+            let type_count = type_counts.entry(object.object_type.clone()).or_default();
+
+            if let Some(max) = self.type_limits.get(&object.object_type) {
+                if *type_count >= *max {
+                    continue;
+                }
+            }
+
+            if !placed.try_place(&object) {
+                continue;
+            }
+
+            *type_count += 1;
+            result.push(object);
+        }
+
+        result
+    }
+
+    /// Returns the types that have a limit and how many objects of each of them to load, and how many objects to load of the other types.
+    fn load_limits(&self) -> (Vec<&ObjectType>, Vec<i64>, i64) {
+        let (types, limits) = self
+            .type_limits
+            .iter()
+            .map(|(object_type, max)| {
+                let limit = if *max > 0 {
+                    *max as i64 + OVERLAP_MARGIN
+                } else {
+                    0
+                };
+
+                (object_type, limit)
+            })
+            .unzip();
+
+        (types, limits, self.limit as i64 + OVERLAP_MARGIN)
+    }
+}
+
+/// Positions of selected objects grouped by type and a grid of `OVERLAP_DISTANCE` sized cells.
+#[derive(Default)]
+struct PlacedObjects {
+    cells: HashMap<(ObjectType, String, [i64; 3]), Vec<[f64; 3]>>,
+}
+
+impl PlacedObjects {
+    /// Returns false without placing the object if it overlaps with an already placed object.
+    fn try_place(&mut self, object: &QpidObject) -> bool {
+        // Vehicles move around and are often parked next to each other
+        if matches!(
+            object.object_type,
+            ObjectType::Vehicle | ObjectType::LongRangeVehicle
+        ) {
+            return true;
+        }
+
+        let divisor = 10f64.powi(object.exponent);
+        let position = [
+            object.pos_x as f64 / divisor,
+            object.pos_y as f64 / divisor,
+            object.pos_z as f64 / divisor,
+        ];
+        let cell = position.map(|value| (value / OVERLAP_DISTANCE).floor() as i64);
+
+        for x in -1..=1 {
+            for y in -1..=1 {
+                for z in -1..=1 {
+                    let key = (
+                        object.object_type.clone(),
+                        object.sub_type.clone(),
+                        [cell[0] + x, cell[1] + y, cell[2] + z],
+                    );
+
+                    let overlaps = self.cells.get(&key).is_some_and(|positions| {
+                        positions
+                            .iter()
+                            .any(|other| distance(&position, other) < OVERLAP_DISTANCE)
+                    });
+
+                    if overlaps {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        self.cells
+            .entry((object.object_type.clone(), object.sub_type.clone(), cell))
+            .or_default()
+            .push(position);
+
+        true
+    }
+}
+
+fn distance(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
 pub struct QpidObjects<'db> {
     connection: &'db DatabaseConnection<'db>,
 }
@@ -336,6 +482,138 @@ impl<'db> QpidObjects<'db> {
             .map_err(|err| err.into())
     }
 
+    /// Sets whether the object is deleted, regardless of who created it.
+    ///
+    /// Returns None if the object does not exist.
+    pub async fn set_deleted(
+        &self,
+        object_id: &str,
+        is_deleted: bool,
+    ) -> Result<Option<QpidObject>, QueryError> {
+        use crate::db::schema::qpid_objects::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(diesel::update(dsl::qpid_objects.find(object_id))
+            .set((
+                dsl::is_deleted.eq(is_deleted),
+                dsl::updated_time.eq(Utc::now().naive_utc()),
+            ))
+            .get_result(conn)
+            .await
+            .optional()?)
+    }
+
+    /// Sets whether every object created by the account is deleted.
+    ///
+    /// Returns the number of objects that were changed.
+    pub async fn set_deleted_by_creator(
+        &self,
+        creator_id: &str,
+        is_deleted: bool,
+    ) -> Result<usize, QueryError> {
+        use crate::db::schema::qpid_objects::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(diesel::update(dsl::qpid_objects)
+            .filter(dsl::creator_id.eq(creator_id))
+            .filter(dsl::is_deleted.ne(is_deleted))
+            .set((
+                dsl::is_deleted.eq(is_deleted),
+                dsl::updated_time.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)
+            .await?)
+    }
+
+    /// Changes the creator of the object.
+    ///
+    /// Returns None if the object does not exist.
+    pub async fn set_creator(
+        &self,
+        object_id: &str,
+        creator_id: &str,
+    ) -> Result<Option<QpidObject>, QueryError> {
+        use crate::db::schema::qpid_objects::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(diesel::update(dsl::qpid_objects.find(object_id))
+            .set((
+                dsl::creator_id.eq(creator_id),
+                dsl::updated_time.eq(Utc::now().naive_utc()),
+            ))
+            .get_result(conn)
+            .await
+            .optional()?)
+    }
+
+    /// Returns the ids of objects without any likes that haven't been updated since the specified date.
+    pub async fn find_unliked_object_ids(
+        &self,
+        updated_before: &NaiveDateTime,
+    ) -> Result<Vec<String>, QueryError> {
+        use crate::db::schema::qpid_objects::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::qpid_objects
+            .filter(dsl::is_deleted.eq(false))
+            .filter(dsl::likes.eq(0))
+            .filter(dsl::updated_time.lt(updated_before))
+            .select(dsl::id)
+            .get_results(conn)
+            .await?)
+    }
+
+    /// Returns the ids of objects that exceed the limit of objects in their qpid area.
+    ///
+    /// The most liked objects are kept, and the most recently updated ones if they have the same amount of likes.
+    /// Objects in `exclude_ids` are treated as already deleted.
+    pub async fn find_object_ids_over_area_limit(
+        &self,
+        limit: usize,
+        exclude_ids: &[String],
+    ) -> Result<Vec<String>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        let objects = sql_query(
+            "SELECT r.id \
+            FROM ( \
+                SELECT q.id, \
+                    ROW_NUMBER() OVER ( \
+                        PARTITION BY q.area_id, q.qpid_id \
+                        ORDER BY q.likes DESC, q.updated_time DESC, q.id \
+                    ) AS area_rank \
+                FROM qpid_objects q \
+                WHERE NOT q.is_deleted \
+                    AND NOT (q.id = ANY($1)) \
+            ) r \
+            WHERE r.area_rank > $2",
+        )
+        .bind::<Array<Text>, _>(exclude_ids)
+        .bind::<BigInt, _>(limit as i64)
+        .get_results::<ObjectId>(conn)
+        .await?;
+
+        Ok(objects.into_iter().map(|object| object.id).collect())
+    }
+
+    /// Marks the objects as deleted, regardless of who created them.
+    ///
+    /// Returns the number of objects that were changed.
+    pub async fn set_deleted_by_ids(&self, object_ids: &[String]) -> Result<usize, QueryError> {
+        use crate::db::schema::qpid_objects::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(diesel::update(dsl::qpid_objects)
+            .filter(dsl::id.eq_any(object_ids))
+            .filter(dsl::is_deleted.eq(false))
+            .set((
+                dsl::is_deleted.eq(true),
+                dsl::updated_time.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)
+            .await?)
+    }
+
     pub async fn mark_deleted_for_account(
         &self,
         object_id: &str,
@@ -438,71 +716,76 @@ impl<'db> QpidObjects<'db> {
         Ok(())
     }
 
+    /// Finds the objects in the qpid areas and picks which ones to return according to `selection`.
+    ///
+    /// The result is ordered by relationship to the creator, then likes and then how recently the object was updated,
+    /// so the same objects are returned every time as long as nothing changes.
     pub async fn find_objects(
         &self,
         area_hashes: &[AreaHash],
         qpid_ids: &[i32],
-        priority_ids: Option<&[&str]>,
-        limit: i64,
+        selection: &ObjectSelection<'_>,
         exclude_account_ids: &[&str],
     ) -> Result<Vec<QpidObject>, QueryError> {
-        use crate::db::schema::qpid_objects::dsl;
-
-        if limit <= 0 {
+        if selection.limit == 0 {
             return Ok(Vec::new());
         }
 
         let conn = &mut *self.connection.get_pg_connection().await;
-
-        let mut result_objects = Vec::with_capacity(limit as usize);
-        let priority_ids = priority_ids.unwrap_or_default();
-
-        // Find objects created by accounts in priority_ids
-        if !priority_ids.is_empty() {
-            let objects = dsl::qpid_objects
-                .filter(dsl::qpid_id.eq_any(qpid_ids))
-                .filter(dsl::area_id.eq_any(area_hashes))
-                .filter(dsl::creator_id.eq_any(priority_ids))
-                .filter(not(dsl::creator_id.eq_any(exclude_account_ids)))
-                .filter(not(dsl::is_deleted))
-                .limit(limit)
-                .get_results::<QpidObject>(conn)
-                .await?;
-
-            result_objects.extend(objects);
-        }
-
-        // If we're not at the limit yet, find missions not created by accounts in priority_ids
-        if result_objects.len() < limit as usize {
-            let objects = dsl::qpid_objects
-                .filter(dsl::qpid_id.eq_any(qpid_ids))
-                .filter(dsl::area_id.eq_any(area_hashes))
-                .filter(not(dsl::creator_id.eq_any(priority_ids)))
-                .filter(not(dsl::creator_id.eq_any(exclude_account_ids)))
-                .filter(not(dsl::is_deleted))
-                .limit(limit - result_objects.len() as i64)
-                .get_results::<QpidObject>(conn)
-                .await?;
-
-            result_objects.extend(objects);
-        }
-
-        result_objects.shrink_to_fit();
-        Ok(result_objects)
+        let (limited_types, type_limits, other_types_limit) = selection.load_limits();
+
+        // Only the best ranked objects of each type are loaded, ranked the same way as in ObjectSelection::select
+        let objects = sql_query(
+            "SELECT o.id, o.creator_id, o.exponent, o.likes, o.pos_x, o.pos_y, o.pos_z, o.rot_x, o.rot_y, o.rot_z, \
+                o.grid_x, o.grid_y, o.area_id, o.qpid_id, o.sub_type, o.updated_time, o.object_type, o.is_deleted \
+            FROM ( \
+                SELECT q.*, \
+                    ROW_NUMBER() OVER ( \
+                        PARTITION BY q.object_type \
+                        ORDER BY CASE WHEN q.creator_id = ANY($4) THEN 0 WHEN q.creator_id = ANY($5) THEN 1 ELSE 2 END, \
+                            q.likes DESC, q.updated_time DESC, q.id \
+                    ) AS type_rank \
+                FROM qpid_objects q \
+                WHERE q.qpid_id = ANY($1) \
+                    AND q.area_id = ANY($2) \
+                    AND NOT (q.creator_id = ANY($3)) \
+                    AND NOT q.is_deleted \
+            ) o \
+            LEFT JOIN UNNEST($6, $7) AS l(object_type, max_count) ON l.object_type = o.object_type \
+            WHERE o.type_rank <= COALESCE(l.max_count, $8) \
+            ORDER BY o.likes DESC, o.updated_time DESC, o.id",
+        )
+        .bind::<Array<Integer>, _>(qpid_ids)
+        .bind::<Array<Integer>, _>(area_hashes)
+        .bind::<Array<Text>, _>(exclude_account_ids)
+        .bind::<Array<Text>, _>(selection.creators.priority_ids)
+        .bind::<Array<Text>, _>(selection.creators.contract_ids)
+        .bind::<Array<Text>, _>(limited_types)
+        .bind::<Array<BigInt>, _>(type_limits)
+        .bind::<BigInt, _>(other_types_limit)
+        .get_results::<QpidObject>(conn)
+        .await?;
+
+        Ok(selection.select(objects))
     }
 
     pub async fn find_objects_by_area(
         &self,
         area_hash: AreaHash,
+        include_deleted: bool,
     ) -> Result<Vec<QpidObject>, QueryError> {
         use crate::db::schema::qpid_objects::dsl;
         let conn = &mut *self.connection.get_pg_connection().await;
 
-        let objects = dsl::qpid_objects
+        let mut query = dsl::qpid_objects
             .filter(dsl::area_id.eq(area_hash))
-            .filter(not(dsl::is_deleted))
-            .get_results(conn)
-            .await?;
+            .into_boxed();
+
+        if !include_deleted {
+            query = query.filter(not(dsl::is_deleted));
+        }
+
+        let objects = query.get_results(conn).await?;
 
         Ok(objects)
     }
@@ -825,7 +1108,7 @@ impl<'db> QpidObjects<'db> {
 
 /// Generates a 13 character long object id.
 /// The first character will always match the object type.
-fn generate_object_id(obj_type: &ObjectType) -> String {
+pub fn generate_object_id(obj_type: &ObjectType) -> String {
     const CHARS: &[u8] = b"aAbBcCdDeEfFgGhHiIjJkKlLmMnNoOpPqQrRsStTuUvVwWxXyYzZ0123456789";
     let mut result = String::with_capacity(13);
     let object_tag = serde_json::to_string(&obj_type).unwrap();
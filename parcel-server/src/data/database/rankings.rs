@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::{dsl::count_star, prelude::*, upsert::excluded};
+use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection, RunQueryDsl};
+use parcel_common::api_types::rank::RankingCategory;
+
+use crate::db::{
+    models::ranking::{NewRankingRecord, NewRankingSchedule, RankingRecord, RankingSchedule},
+    QueryError,
+};
+
+use super::DatabaseConnection;
+
+/// The number of reward tiers the game expects in `GetPlayerRankingRecordsResponse.rewards`.
+pub const NUM_REWARD_TIERS: usize = 11;
+
+/// The highest rank percentile (rank / number of participants) that qualifies for each reward tier.
+///
+/// The best tier comes first, so the top 1% of players get tier 0 and everyone else that participated gets at least tier 10.
+const REWARD_TIER_PERCENTILES: [f64; NUM_REWARD_TIERS] =
+    [0.01, 0.05, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 1.0];
+
+pub struct Rankings<'db> {
+    connection: &'db DatabaseConnection<'db>,
+}
+
+impl<'db> Rankings<'db> {
+    pub fn new(connection: &'db DatabaseConnection) -> Self {
+        Self { connection }
+    }
+
+    pub async fn create_schedule(
+        &self,
+        category: RankingCategory,
+        start_date: &NaiveDateTime,
+        end_date: &NaiveDateTime,
+    ) -> Result<RankingSchedule, QueryError> {
+        use crate::db::schema::ranking_schedules::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        let schedule = diesel::insert_into(dsl::ranking_schedules)
+            .values(&NewRankingSchedule {
+                category,
+                start_date,
+                end_date,
+            })
+            .get_result(conn)
+            .await?;
+
+        Ok(schedule)
+    }
+
+    /// Deletes the schedule with the specified id along with all of its records.
+    ///
+    /// Returns false if the schedule does not exist.
+    pub async fn delete_schedule(&self, schedule_id: i32) -> Result<bool, QueryError> {
+        use crate::db::schema::ranking_schedules::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        let affected_rows = diesel::delete(dsl::ranking_schedules.find(schedule_id))
+            .execute(conn)
+            .await?;
+
+        Ok(affected_rows > 0)
+    }
+
+    pub async fn get_schedule(
+        &self,
+        schedule_id: i32,
+    ) -> Result<Option<RankingSchedule>, QueryError> {
+        use crate::db::schema::ranking_schedules::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::ranking_schedules
+            .find(schedule_id)
+            .first(conn)
+            .await
+            .optional()?)
+    }
+
+    pub async fn get_all_schedules(&self) -> Result<Vec<RankingSchedule>, QueryError> {
+        use crate::db::schema::ranking_schedules::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::ranking_schedules
+            .order_by(dsl::start_date.desc())
+            .get_results(conn)
+            .await?)
+    }
+
+    /// Returns the most recently started schedules, including the ones that have already ended.
+    pub async fn get_started_schedules(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<RankingSchedule>, QueryError> {
+        use crate::db::schema::ranking_schedules::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::ranking_schedules
+            .filter(dsl::start_date.le(Utc::now().naive_utc()))
+            .order_by(dsl::start_date.desc())
+            .limit(limit)
+            .get_results(conn)
+            .await?)
+    }
+
+    /// Adds to the score and counters of the account's record in every currently active schedule of the given category.
+    ///
+    /// Records are created on demand, so an account only shows up in a ranking once it has done something that counts towards it.
+    pub async fn add_progress(
+        &self,
+        account_id: &str,
+        category: RankingCategory,
+        score: i64,
+        try_count: i32,
+        clear_count: i32,
+    ) -> Result<(), QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+        let now = Utc::now().naive_utc();
+
+        let schedule_ids: Vec<i32> = {
+            use crate::db::schema::ranking_schedules::dsl;
+
+            dsl::ranking_schedules
+                .filter(dsl::category.eq(category))
+                .filter(dsl::start_date.le(&now))
+                .filter(dsl::end_date.gt(&now))
+                .filter(dsl::closed_at.is_null())
+                .select(dsl::id)
+                .get_results(conn)
+                .await?
+        };
+
+        if schedule_ids.is_empty() {
+            return Ok(());
+        }
+
+        use crate::db::schema::ranking_records::dsl;
+
+        diesel::insert_into(dsl::ranking_records)
+            .values(
+                &schedule_ids
+                    .iter()
+                    .map(|schedule_id| NewRankingRecord {
+                        schedule_id: *schedule_id,
+                        account_id,
+                        score,
+                        try_count,
+                        clear_count,
+                        updated_at: &now,
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .on_conflict((dsl::schedule_id, dsl::account_id))
+            .do_update()
+            .set((
+                dsl::score.eq(dsl::score + excluded(dsl::score)),
+                dsl::try_count.eq(dsl::try_count + excluded(dsl::try_count)),
+                dsl::clear_count.eq(dsl::clear_count + excluded(dsl::clear_count)),
+                dsl::updated_at.eq(excluded(dsl::updated_at)),
+            ))
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns all records in the schedule, ordered from best to worst.
+    pub async fn get_records(&self, schedule_id: i32) -> Result<Vec<RankingRecord>, QueryError> {
+        use crate::db::schema::ranking_records::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::ranking_records
+            .filter(dsl::schedule_id.eq(schedule_id))
+            .order_by((dsl::score.desc(), dsl::updated_at.asc()))
+            .get_results(conn)
+            .await?)
+    }
+
+    pub async fn get_account_records(
+        &self,
+        account_id: &str,
+        schedule_ids: &[i32],
+    ) -> Result<Vec<RankingRecord>, QueryError> {
+        use crate::db::schema::ranking_records::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::ranking_records
+            .filter(dsl::account_id.eq(account_id))
+            .filter(dsl::schedule_id.eq_any(schedule_ids))
+            .get_results(conn)
+            .await?)
+    }
+
+    pub async fn count_participants(
+        &self,
+        schedule_ids: &[i32],
+    ) -> Result<HashMap<i32, i64>, QueryError> {
+        use crate::db::schema::ranking_records::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::ranking_records
+            .filter(dsl::schedule_id.eq_any(schedule_ids))
+            .group_by(dsl::schedule_id)
+            .select((dsl::schedule_id, count_star()))
+            .get_results::<(i32, i64)>(conn)
+            .await?
+            .into_iter()
+            .collect())
+    }
+
+    /// Returns the number of rewards the account has received for each reward tier in all closed schedules.
+    pub async fn get_reward_counts(
+        &self,
+        account_id: &str,
+    ) -> Result<[i32; NUM_REWARD_TIERS], QueryError> {
+        use crate::db::schema::ranking_records::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        let tiers: Vec<Option<i16>> = dsl::ranking_records
+            .filter(dsl::account_id.eq(account_id))
+            .filter(dsl::reward_tier.is_not_null())
+            .select(dsl::reward_tier)
+            .get_results(conn)
+            .await?;
+
+        let mut result = [0; NUM_REWARD_TIERS];
+
+        for tier in tiers.into_iter().flatten() {
+            if let Some(count) = result.get_mut(tier as usize) {
+                *count += 1;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Closes all schedules that have ended and computes the final rank and reward tier of every record in them.
+    ///
+    /// Returns the number of schedules that were closed.
+    pub async fn close_ended_schedules(&self) -> Result<usize, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        conn.transaction(|conn| {
+            async move {
+                let now = Utc::now().naive_utc();
+
+                let schedule_ids: Vec<i32> = {
+                    use crate::db::schema::ranking_schedules::dsl;
+
+                    dsl::ranking_schedules
+                        .filter(dsl::end_date.le(&now))
+                        .filter(dsl::closed_at.is_null())
+                        .select(dsl::id)
+                        .for_update()
+                        .get_results(conn)
+                        .await?
+                };
+
+                for schedule_id in &schedule_ids {
+                    use crate::db::schema::ranking_records::dsl;
+
+                    let record_ids: Vec<i64> = dsl::ranking_records
+                        .filter(dsl::schedule_id.eq(schedule_id))
+                        .order_by((dsl::score.desc(), dsl::updated_at.asc()))
+                        .select(dsl::id)
+                        .get_results(conn)
+                        .await?;
+                    let num_participants = record_ids.len();
+
+                    for (index, record_id) in record_ids.into_iter().enumerate() {
+                        let rank = index + 1;
+
+                        diesel::update(dsl::ranking_records.find(record_id))
+                            .set((
+                                dsl::rank.eq(rank as i32),
+                                dsl::reward_tier.eq(get_reward_tier(rank, num_participants) as i16),
+                            ))
+                            .execute(conn)
+                            .await?;
+                    }
+
+                    log::info!(
+                        "Closed ranking schedule {} with {} participant(s)",
+                        schedule_id,
+                        num_participants
+                    );
+                }
+
+                {
+                    use crate::db::schema::ranking_schedules::dsl;
+
+                    diesel::update(dsl::ranking_schedules)
+                        .filter(dsl::id.eq_any(&schedule_ids))
+                        .set(dsl::closed_at.eq(&now))
+                        .execute(conn)
+                        .await?;
+                }
+
+                Ok(schedule_ids.len())
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+}
+
+/// Returns the reward tier for the 1-based rank out of the specified number of participants.
+fn get_reward_tier(rank: usize, num_participants: usize) -> usize {
+    let percentile = rank as f64 / num_participants.max(1) as f64;
+
+    REWARD_TIER_PERCENTILES
+        .iter()
+        .position(|max_percentile| percentile <= *max_percentile)
+        .unwrap_or(NUM_REWARD_TIERS - 1)
+}
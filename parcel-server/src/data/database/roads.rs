@@ -2,8 +2,11 @@ use std::collections::HashMap;
 
 use base64::Engine;
 use diesel::{dsl::not, prelude::*};
-use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection, RunQueryDsl};
+use diesel_async::{
+    scoped_futures::ScopedFutureExt, AsyncConnection, AsyncPgConnection, RunQueryDsl,
+};
 use parcel_common::api_types::{
+    area::AreaHash,
     requests::{create_road::CreateRoadRequest, find_qpid_objects::RoadRequest},
     IntoDsApiType,
 };
@@ -13,7 +16,7 @@ use crate::db::{
     QueryError,
 };
 
-use super::DatabaseConnection;
+use super::{accounts::PrioritizedCreators, DatabaseConnection};
 
 pub struct DbRoad {
     pub road: Road,
@@ -153,63 +156,111 @@ impl<'db> Roads<'db> {
         .await
     }
 
+    /// Finds roads that end in any of the requested qpids.
+    ///
+    /// Roads from prioritized creators are returned first, and within each group of creators the roads
+    /// that start or end at the prioritized location come first. Otherwise the most recently created roads are preferred.
     pub async fn find_roads(
         &self,
         parameters: &RoadRequest,
         exclude_ids: &[&str],
-        _priority_ids: Option<&[&str]>,
+        creators: &PrioritizedCreators<'_>,
     ) -> Result<Vec<DbRoad>, QueryError> {
         use crate::db::schema::roads::dsl;
         let conn = &mut *self.connection.get_pg_connection().await;
-        let mut result = Vec::new();
-
-        // todo: respect priority_ids and parameters.prioritized_location_id
-
-        let mut roads_query = dsl::roads
-            .filter(dsl::qpid_end_id.eq_any(&parameters.end_qpids))
-            .filter(dsl::data_version.eq(parameters.data_version))
-            .filter(not(dsl::creator_id.eq_any(exclude_ids)))
-            .order_by(dsl::created_at.desc())
-            .limit(parameters.count as i64)
-            .into_boxed();
-
-        if let Some(required_location_id) = &parameters.required_location_id {
-            roads_query = roads_query
-                .filter(dsl::location_start_id.eq(required_location_id))
-                .or_filter(dsl::location_end_id.eq(required_location_id));
-        }
 
-        let roads: Vec<Road> = roads_query.get_results::<Road>(conn).await?;
+        let limit = parameters.count.max(0) as usize;
+        let contract_ids = creators
+            .contract_ids
+            .iter()
+            .filter(|id| !creators.priority_ids.contains(id))
+            .copied()
+            .collect::<Vec<_>>();
+        let prioritized_ids = creators
+            .priority_ids
+            .iter()
+            .chain(&contract_ids)
+            .copied()
+            .collect::<Vec<_>>();
+
+        let creator_tiers = [
+            CreatorTier::Include(creators.priority_ids),
+            CreatorTier::Include(&contract_ids),
+            CreatorTier::Exclude(&prioritized_ids),
+        ];
+        let location_tiers = match parameters.prioritized_location_id {
+            -1 => vec![LocationTier::Any],
+            location_id => vec![
+                LocationTier::Prioritized(location_id),
+                LocationTier::Other(location_id),
+            ],
+        };
 
-        {
-            use crate::db::schema::road_via_qpids::dsl;
-            let road_ids = roads.iter().map(|r| &r.id).collect::<Vec<_>>();
+        let mut roads = Vec::<Road>::with_capacity(limit);
 
-            let all_via_qpids: Vec<RoadViaQpid> = dsl::road_via_qpids
-                .filter(dsl::road_id.eq_any(road_ids))
-                .order_by(dsl::sort_order.asc())
-                .get_results::<RoadViaQpid>(conn)
-                .await?;
+        for creator_tier in &creator_tiers {
+            for location_tier in &location_tiers {
+                if roads.len() >= limit {
+                    break;
+                }
 
-            let mut via_qpids = HashMap::<String, Vec<RoadViaQpid>>::new();
-            for via_qpid in all_via_qpids {
-                let vec = via_qpids
-                    .entry(via_qpid.road_id.clone())
-                    .or_insert_with(Vec::new);
-                vec.push(via_qpid);
-            }
+                let mut query = dsl::roads
+                    .filter(dsl::qpid_end_id.eq_any(&parameters.end_qpids))
+                    .filter(dsl::data_version.eq(parameters.data_version))
+                    .filter(not(dsl::creator_id.eq_any(exclude_ids)))
+                    .order_by((dsl::created_at.desc(), dsl::id))
+                    .limit((limit - roads.len()) as i64)
+                    .into_boxed();
+
+                if let Some(required_location_id) = parameters.required_location_id {
+                    query = query.filter(
+                        dsl::location_start_id
+                            .eq(required_location_id)
+                            .or(dsl::location_end_id.eq(required_location_id)),
+                    );
+                }
 
-            roads.into_iter().for_each(|road| {
-                let db_road = DbRoad {
-                    via_qpids: via_qpids.remove(&road.id).unwrap_or_default(),
-                    road,
+                query = match creator_tier {
+                    CreatorTier::Include(ids) if ids.is_empty() => continue,
+                    CreatorTier::Include(ids) => query.filter(dsl::creator_id.eq_any(*ids)),
+                    CreatorTier::Exclude(ids) => query.filter(not(dsl::creator_id.eq_any(*ids))),
                 };
 
-                result.push(db_road);
-            })
+                query = match *location_tier {
+                    LocationTier::Prioritized(location_id) => query.filter(
+                        dsl::location_start_id
+                            .eq(location_id)
+                            .or(dsl::location_end_id.eq(location_id)),
+                    ),
+                    LocationTier::Other(location_id) => query.filter(
+                        dsl::location_start_id
+                            .ne(location_id)
+                            .and(dsl::location_end_id.ne(location_id)),
+                    ),
+                    LocationTier::Any => query,
+                };
+
+                roads.extend(query.get_results::<Road>(conn).await?);
+            }
+        }
+
+        load_via_qpids(conn, roads).await
+    }
+
+    /// Returns every road, or only the roads in the specified area, oldest first.
+    pub async fn list_roads(&self, area_hash: Option<AreaHash>) -> Result<Vec<DbRoad>, QueryError> {
+        use crate::db::schema::roads::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        let mut query = dsl::roads.order_by((dsl::created_at, dsl::id)).into_boxed();
+
+        if let Some(area_hash) = area_hash {
+            query = query.filter(dsl::area_hash.eq(area_hash));
         }
 
-        Ok(result)
+        let roads = query.get_results::<Road>(conn).await?;
+
+        load_via_qpids(conn, roads).await
     }
 
     pub async fn get_road_data(&self, road_id: &str) -> Result<Option<RoadData>, QueryError> {
@@ -220,7 +271,50 @@ impl<'db> Roads<'db> {
     }
 }
 
-fn generate_road_id() -> String {
+async fn load_via_qpids(
+    conn: &mut AsyncPgConnection,
+    roads: Vec<Road>,
+) -> Result<Vec<DbRoad>, QueryError> {
+    use crate::db::schema::road_via_qpids::dsl;
+    let road_ids = roads.iter().map(|r| &r.id).collect::<Vec<_>>();
+
+    let all_via_qpids: Vec<RoadViaQpid> = dsl::road_via_qpids
+        .filter(dsl::road_id.eq_any(road_ids))
+        .order_by(dsl::sort_order.asc())
+        .get_results::<RoadViaQpid>(conn)
+        .await?;
+
+    let mut via_qpids = HashMap::<String, Vec<RoadViaQpid>>::new();
+    for via_qpid in all_via_qpids {
+        let vec = via_qpids
+            .entry(via_qpid.road_id.clone())
+            .or_insert_with(Vec::new);
+        vec.push(via_qpid);
+    }
+
+    Ok(roads
+        .into_iter()
+        .map(|road| DbRoad {
+            via_qpids: via_qpids.remove(&road.id).unwrap_or_default(),
+            road,
+        })
+        .collect())
+}
+
+enum CreatorTier<'a> {
+    Include(&'a [&'a str]),
+    Exclude(&'a [&'a str]),
+}
+
+enum LocationTier {
+    /// Roads that start or end at the location
+    Prioritized(i32),
+    /// Roads that neither start nor end at the location
+    Other(i32),
+    Any,
+}
+
+pub fn generate_road_id() -> String {
     const CHARS: &[u8] = b"aAbBcCdDeEfFgGhHiIjJkKlLmMnNoOpPqQrRsStTuUvVwWxXyYzZ0123456789";
     let mut result = String::with_capacity(23);
     result.push('o');
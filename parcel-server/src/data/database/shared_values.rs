@@ -0,0 +1,135 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+use crate::db::{models::shared_value::SharedValue, QueryError};
+
+use super::DatabaseConnection;
+
+/// Values that are shared between server instances using the same database. Expired values are ignored by every query except `delete_expired`.
+pub struct SharedValues<'db> {
+    connection: &'db DatabaseConnection<'db>,
+}
+
+impl<'db> SharedValues<'db> {
+    pub fn new(connection: &'db DatabaseConnection) -> Self {
+        Self { connection }
+    }
+
+    pub async fn get(
+        &self,
+        key: &str,
+        now: &NaiveDateTime,
+    ) -> Result<Option<serde_json::Value>, QueryError> {
+        use crate::db::schema::shared_values::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::shared_values
+            .find(key)
+            .select(dsl::value)
+            .filter(dsl::expires_at.is_null().or(dsl::expires_at.gt(now)))
+            .first(conn)
+            .await
+            .optional()?)
+    }
+
+    /// Returns every value with a key that starts with the prefix.
+    pub async fn list_by_prefix(
+        &self,
+        prefix: &str,
+        now: &NaiveDateTime,
+    ) -> Result<Vec<(String, serde_json::Value)>, QueryError> {
+        use crate::db::schema::shared_values::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::shared_values
+            .select((dsl::key, dsl::value))
+            .filter(dsl::key.like(format!("{}%", escape_like_pattern(prefix))))
+            .filter(dsl::expires_at.is_null().or(dsl::expires_at.gt(now)))
+            .get_results(conn)
+            .await?)
+    }
+
+    /// Adds the value if there is no value with the same key, otherwise nothing is changed.
+    pub async fn insert_if_absent(&self, value: &SharedValue) -> Result<(), QueryError> {
+        use crate::db::schema::shared_values::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        diesel::insert_into(dsl::shared_values)
+            .values(value)
+            .on_conflict(dsl::key)
+            .do_nothing()
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Adds the value, or replaces it if a value with the same key exists.
+    pub async fn save(&self, value: &SharedValue) -> Result<(), QueryError> {
+        use crate::db::schema::shared_values::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        diesel::insert_into(dsl::shared_values)
+            .values(value)
+            .on_conflict(dsl::key)
+            .do_update()
+            .set(value)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Deletes the value and returns it if it hasn't expired.
+    pub async fn take(
+        &self,
+        key: &str,
+        now: &NaiveDateTime,
+    ) -> Result<Option<serde_json::Value>, QueryError> {
+        use crate::db::schema::shared_values::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(diesel::delete(
+            dsl::shared_values
+                .find(key)
+                .filter(dsl::expires_at.is_null().or(dsl::expires_at.gt(now))),
+        )
+        .returning(dsl::value)
+        .get_result(conn)
+        .await
+        .optional()?)
+    }
+
+    /// Sends a notification to every connection that is listening on the channel.
+    pub async fn notify(&self, channel: &str, payload: &str) -> Result<(), QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        diesel::sql_query("SELECT pg_notify($1, $2)")
+            .bind::<diesel::sql_types::Text, _>(channel)
+            .bind::<diesel::sql_types::Text, _>(payload)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_expired(&self, now: &NaiveDateTime) -> Result<usize, QueryError> {
+        use crate::db::schema::shared_values::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(
+            diesel::delete(dsl::shared_values.filter(dsl::expires_at.le(now)))
+                .execute(conn)
+                .await?,
+        )
+    }
+}
+
+/// Escapes the characters that have a special meaning in LIKE patterns.
+fn escape_like_pattern(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
@@ -0,0 +1,221 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::{
+    sql_query,
+    sql_types::{BigInt, Date, Integer, Nullable, SmallInt, Text, Timestamp},
+    QueryableByName,
+};
+use diesel_async::RunQueryDsl;
+use parcel_common::api_types::{mission::ProgressState, object::ObjectType};
+
+use crate::db::QueryError;
+
+use super::DatabaseConnection;
+
+/// Limits the statistics to a single account and/or a date range. Every filter is optional.
+///
+/// The date range includes `from` and excludes `to`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StatisticsFilter<'a> {
+    pub account_id: Option<&'a str>,
+    pub from: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, QueryableByName)]
+pub struct ObjectCount {
+    #[diesel(sql_type = Text)]
+    pub object_type: ObjectType,
+    #[diesel(sql_type = Text)]
+    pub sub_type: String,
+    #[diesel(sql_type = Integer)]
+    pub area_id: i32,
+    #[diesel(sql_type = BigInt)]
+    pub count: i64,
+}
+
+#[derive(Debug, QueryableByName)]
+pub struct LikesPerDay {
+    #[diesel(sql_type = Date)]
+    pub day: NaiveDate,
+    #[diesel(sql_type = BigInt)]
+    pub likes_given: i64,
+    #[diesel(sql_type = BigInt)]
+    pub likes_received: i64,
+}
+
+#[derive(Debug, QueryableByName)]
+pub struct DevotedResources {
+    #[diesel(sql_type = Integer)]
+    pub construction_id: i32,
+    #[diesel(sql_type = SmallInt)]
+    pub resource_id: i16,
+    #[diesel(sql_type = BigInt)]
+    pub num_resources: i64,
+}
+
+#[derive(Debug, QueryableByName)]
+pub struct MissionCounts {
+    #[diesel(sql_type = BigInt)]
+    pub created: i64,
+    #[diesel(sql_type = BigInt)]
+    pub completed: i64,
+}
+
+/// Totals of the statistics the game keeps in the player profiles.
+#[derive(Debug, QueryableByName)]
+pub struct ProfileTotals {
+    #[diesel(sql_type = BigInt)]
+    pub num_profiles: i64,
+    #[diesel(sql_type = BigInt)]
+    pub delivered_baggage: i64,
+    #[diesel(sql_type = BigInt)]
+    pub delivered_weight: i64,
+    #[diesel(sql_type = BigInt)]
+    pub distance_traveled: i64,
+    #[diesel(sql_type = BigInt)]
+    pub num_likes_received_npc: i64,
+    #[diesel(sql_type = BigInt)]
+    pub num_likes_received_online: i64,
+    #[diesel(sql_type = BigInt)]
+    pub legend_count: i64,
+    #[diesel(sql_type = BigInt)]
+    pub super_legend_count: i64,
+    #[diesel(sql_type = BigInt)]
+    pub ss_legend_count: i64,
+}
+
+pub struct Statistics<'db> {
+    connection: &'db DatabaseConnection<'db>,
+}
+
+impl<'db> Statistics<'db> {
+    pub fn new(connection: &'db DatabaseConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Counts the objects that haven't been deleted by type and area.
+    ///
+    /// Objects don't keep their creation date, so the date range applies to when they were last updated.
+    pub async fn count_objects(
+        &self,
+        filter: &StatisticsFilter<'_>,
+    ) -> Result<Vec<ObjectCount>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(sql_query(
+            "SELECT object_type, sub_type, area_id, COUNT(*) AS count \
+            FROM qpid_objects \
+            WHERE NOT is_deleted \
+                AND ($1::varchar IS NULL OR creator_id = $1) \
+                AND ($2::timestamp IS NULL OR updated_time >= $2) \
+                AND ($3::timestamp IS NULL OR updated_time < $3) \
+            GROUP BY object_type, sub_type, area_id \
+            ORDER BY area_id, object_type, sub_type",
+        )
+        .bind::<Nullable<Text>, _>(filter.account_id)
+        .bind::<Nullable<Timestamp>, _>(filter.from)
+        .bind::<Nullable<Timestamp>, _>(filter.to)
+        .get_results(conn)
+        .await?)
+    }
+
+    /// Sums the likes given and received by the account per day.
+    ///
+    /// Without an account every like counts as both given and received.
+    pub async fn likes_per_day(
+        &self,
+        filter: &StatisticsFilter<'_>,
+    ) -> Result<Vec<LikesPerDay>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(sql_query(
+            "SELECT time::date AS day, \
+                COALESCE(SUM(likes_manual + likes_auto) FILTER (WHERE $1::varchar IS NULL OR from_id = $1), 0)::bigint AS likes_given, \
+                COALESCE(SUM(likes_manual + likes_auto) FILTER (WHERE $1::varchar IS NULL OR to_id = $1), 0)::bigint AS likes_received \
+            FROM likes \
+            WHERE ($1::varchar IS NULL OR from_id = $1 OR to_id = $1) \
+                AND ($2::timestamp IS NULL OR time >= $2) \
+                AND ($3::timestamp IS NULL OR time < $3) \
+            GROUP BY day \
+            ORDER BY day",
+        )
+        .bind::<Nullable<Text>, _>(filter.account_id)
+        .bind::<Nullable<Timestamp>, _>(filter.from)
+        .bind::<Nullable<Timestamp>, _>(filter.to)
+        .get_results(conn)
+        .await?)
+    }
+
+    /// Sums the resources devoted to each highway construction by resource type.
+    pub async fn devoted_resources(
+        &self,
+        filter: &StatisticsFilter<'_>,
+    ) -> Result<Vec<DevotedResources>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(sql_query(
+            "SELECT construction_id, resource_id, SUM(num_resources)::bigint AS num_resources \
+            FROM devoted_highway_resources \
+            WHERE ($1::varchar IS NULL OR account_id = $1) \
+                AND ($2::timestamp IS NULL OR time >= $2) \
+                AND ($3::timestamp IS NULL OR time < $3) \
+            GROUP BY construction_id, resource_id \
+            ORDER BY construction_id, resource_id",
+        )
+        .bind::<Nullable<Text>, _>(filter.account_id)
+        .bind::<Nullable<Timestamp>, _>(filter.from)
+        .bind::<Nullable<Timestamp>, _>(filter.to)
+        .get_results(conn)
+        .await?)
+    }
+
+    /// Counts the missions created by the account, and the ones it delivered.
+    ///
+    /// Missions don't keep the date they were delivered, so the date range applies to when they were registered.
+    pub async fn count_missions(
+        &self,
+        filter: &StatisticsFilter<'_>,
+    ) -> Result<MissionCounts, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(sql_query(
+            "SELECT \
+                COUNT(*) FILTER (WHERE $1::varchar IS NULL OR creator_id = $1) AS created, \
+                COUNT(*) FILTER (WHERE progress_state = $4 AND ($1::varchar IS NULL OR worker_id = $1)) AS completed \
+            FROM missions \
+            WHERE ($2::timestamp IS NULL OR registered_time >= $2) \
+                AND ($3::timestamp IS NULL OR registered_time < $3)",
+        )
+        .bind::<Nullable<Text>, _>(filter.account_id)
+        .bind::<Nullable<Timestamp>, _>(filter.from)
+        .bind::<Nullable<Timestamp>, _>(filter.to)
+        .bind::<Integer, _>(ProgressState::Success)
+        .get_result(conn)
+        .await?)
+    }
+
+    /// Sums the statistics from the player profiles. These are lifetime totals, so the date range doesn't apply.
+    pub async fn profile_totals(
+        &self,
+        account_id: Option<&str>,
+    ) -> Result<ProfileTotals, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(sql_query(
+            "SELECT COUNT(*) AS num_profiles, \
+                COALESCE(SUM(delivered_baggage), 0)::bigint AS delivered_baggage, \
+                COALESCE(SUM(delivered_weight), 0)::bigint AS delivered_weight, \
+                COALESCE(SUM(distance_traveled), 0)::bigint AS distance_traveled, \
+                COALESCE(SUM(num_likes_received_npc), 0)::bigint AS num_likes_received_npc, \
+                COALESCE(SUM(num_likes_received_online), 0)::bigint AS num_likes_received_online, \
+                COALESCE(SUM(legend_count), 0)::bigint AS legend_count, \
+                COALESCE(SUM(super_legend_count), 0)::bigint AS super_legend_count, \
+                COALESCE(SUM(ss_legend_count), 0)::bigint AS ss_legend_count \
+            FROM player_profiles \
+            WHERE $1::varchar IS NULL OR account_id = $1",
+        )
+        .bind::<Nullable<Text>, _>(account_id)
+        .get_result(conn)
+        .await?)
+    }
+}
@@ -91,6 +91,33 @@ impl<'db> WastedBaggages<'db> {
         Ok(baggages)
     }
 
+    /// Returns the ids of wasted baggages that were created before the specified date.
+    pub async fn find_ids_created_before(
+        &self,
+        created_before: &NaiveDateTime,
+    ) -> Result<Vec<String>, QueryError> {
+        use crate::db::schema::wasted_baggages::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(dsl::wasted_baggages
+            .filter(dsl::created_at.lt(created_before))
+            .select(dsl::id)
+            .get_results(conn)
+            .await?)
+    }
+
+    /// Deletes the wasted baggages regardless of who created them. Returns the number of deleted baggages.
+    pub async fn delete_by_ids(&self, baggage_ids: &[String]) -> Result<usize, QueryError> {
+        use crate::db::schema::wasted_baggages::dsl;
+        let conn = &mut *self.connection.get_pg_connection().await;
+
+        Ok(
+            diesel::delete(dsl::wasted_baggages.filter(dsl::id.eq_any(baggage_ids)))
+                .execute(conn)
+                .await?,
+        )
+    }
+
     pub async fn delete_by_requests(
         &self,
         delete_requests: impl Iterator<Item = &DeleteRequest>,
@@ -117,7 +144,7 @@ impl<'db> WastedBaggages<'db> {
     }
 }
 
-fn generate_wasted_baggage_id() -> String {
+pub fn generate_wasted_baggage_id() -> String {
     const CHARS: &[u8] = b"aAbBcCdDeEfFgGhHiIjJkKlLmMnNoOpPqQrRsStTuUvVwWxXyYzZ0123456789";
     let mut result = String::with_capacity(23);
     result.push('w');
@@ -0,0 +1,211 @@
+use std::collections::HashSet;
+
+use diesel::{
+    sql_query,
+    sql_types::{Array, BigInt, Integer, Text},
+    QueryableByName,
+};
+use diesel_async::RunQueryDsl;
+
+use crate::db::QueryError;
+
+use super::DatabaseConnection;
+
+/// Generic table access used for exporting and importing the world.
+///
+/// Rows are passed around as json so that every table can be handled the same way.
+/// Table and column names are never user input, they come from the list of tables in `world_snapshot`.
+pub struct WorldSnapshots<'db> {
+    connection: &'db DatabaseConnection<'db>,
+}
+
+#[derive(QueryableByName)]
+struct JsonRows {
+    #[diesel(sql_type = Text)]
+    rows: String,
+}
+
+#[derive(QueryableByName)]
+struct ColumnName {
+    #[diesel(sql_type = Text)]
+    column_name: String,
+}
+
+#[derive(QueryableByName)]
+struct TextId {
+    #[diesel(sql_type = Text)]
+    id: String,
+}
+
+#[derive(QueryableByName)]
+struct SerialId {
+    #[diesel(sql_type = BigInt)]
+    id: i64,
+}
+
+#[derive(QueryableByName)]
+struct SchemaVersion {
+    #[diesel(sql_type = Text)]
+    version: String,
+}
+
+impl<'db> WorldSnapshots<'db> {
+    pub fn new(connection: &'db DatabaseConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Makes every following query in the current transaction see the same snapshot of the database.
+    ///
+    /// Must be called before any other query in the transaction.
+    pub async fn use_snapshot_isolation(&self) -> Result<(), QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+        sql_query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the version of the latest applied migration.
+    pub async fn schema_version(&self) -> Result<String, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+        let version = sql_query(
+            "SELECT COALESCE(MAX(version), '') AS version FROM __diesel_schema_migrations",
+        )
+        .get_result::<SchemaVersion>(conn)
+        .await?;
+
+        Ok(version.version)
+    }
+
+    /// Returns every row in the table as a json array of objects.
+    pub async fn export_table(&self, table: &str) -> Result<String, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+        let rows = sql_query(format!(
+            "SELECT COALESCE(json_agg(t), '[]'::json)::text AS rows FROM {table} t"
+        ))
+        .get_result::<JsonRows>(conn)
+        .await?;
+
+        Ok(rows.rows)
+    }
+
+    pub async fn table_columns(&self, table: &str) -> Result<Vec<String>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+        let columns = sql_query(
+            "SELECT column_name::text AS column_name FROM information_schema.columns \
+            WHERE table_schema = current_schema() AND table_name = $1 ORDER BY ordinal_position",
+        )
+        .bind::<Text, _>(table)
+        .load::<ColumnName>(conn)
+        .await?;
+
+        Ok(columns.into_iter().map(|c| c.column_name).collect())
+    }
+
+    /// Returns the ids out of the specified ids that already exist in the table.
+    pub async fn existing_ids(
+        &self,
+        table: &str,
+        ids: &[String],
+    ) -> Result<HashSet<String>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+        let ids = sql_query(format!("SELECT id FROM {table} WHERE id = ANY($1)"))
+            .bind::<Array<Text>, _>(ids)
+            .load::<TextId>(conn)
+            .await?;
+
+        Ok(ids.into_iter().map(|id| id.id).collect())
+    }
+
+    /// Returns the indices of the rows in the json array that have the same values in the key columns as a row in the table.
+    pub async fn existing_rows(
+        &self,
+        table: &str,
+        key_columns: &[&str],
+        rows_json: &str,
+    ) -> Result<HashSet<usize>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+        let conditions = key_columns
+            .iter()
+            .map(|column| format!("e.\"{column}\" = r.\"{column}\""))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+        let indices = sql_query(format!(
+            "SELECT (r.ordinality - 1)::int8 AS id FROM json_populate_recordset(NULL::{table}, $1::json) WITH ORDINALITY AS r \
+            WHERE EXISTS (SELECT 1 FROM {table} e WHERE {conditions})"
+        ))
+        .bind::<Text, _>(rows_json)
+        .load::<SerialId>(conn)
+        .await?;
+
+        Ok(indices.into_iter().map(|index| index.id as usize).collect())
+    }
+
+    pub async fn find_account_id_by_provider(
+        &self,
+        provider: i32,
+        provider_id: &str,
+    ) -> Result<Option<String>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+        let ids = sql_query("SELECT id FROM accounts WHERE provider = $1 AND provider_id = $2")
+            .bind::<Integer, _>(provider)
+            .bind::<Text, _>(provider_id)
+            .load::<TextId>(conn)
+            .await?;
+
+        Ok(ids.into_iter().next().map(|id| id.id))
+    }
+
+    /// Inserts the rows from a json array into the table and returns the number of inserted rows.
+    ///
+    /// `on_conflict` is appended to the insert statement as is.
+    pub async fn insert_rows(
+        &self,
+        table: &str,
+        columns: &[String],
+        rows_json: &str,
+        on_conflict: &str,
+    ) -> Result<usize, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+        let columns = quote_columns(columns);
+        let inserted = sql_query(format!(
+            "INSERT INTO {table} ({columns}) SELECT {columns} FROM json_populate_recordset(NULL::{table}, $1::json) {on_conflict}"
+        ))
+        .bind::<Text, _>(rows_json)
+        .execute(conn)
+        .await?;
+
+        Ok(inserted)
+    }
+
+    /// Inserts a single row and returns the id the database generated for it.
+    ///
+    /// Returns None if the row wasn't inserted because of `on_conflict`.
+    pub async fn insert_row_returning_id(
+        &self,
+        table: &str,
+        columns: &[String],
+        row_json: &str,
+        on_conflict: &str,
+    ) -> Result<Option<i64>, QueryError> {
+        let conn = &mut *self.connection.get_pg_connection().await;
+        let columns = quote_columns(columns);
+        let ids = sql_query(format!(
+            "INSERT INTO {table} ({columns}) SELECT {columns} FROM json_populate_record(NULL::{table}, $1::json) {on_conflict} RETURNING id::int8 AS id"
+        ))
+        .bind::<Text, _>(row_json)
+        .load::<SerialId>(conn)
+        .await?;
+
+        Ok(ids.into_iter().next().map(|id| id.id))
+    }
+}
+
+fn quote_columns(columns: &[String]) -> String {
+    columns
+        .iter()
+        .map(|column| format!("\"{column}\""))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
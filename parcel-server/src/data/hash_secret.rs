@@ -7,13 +7,21 @@ use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
 };
 
+use super::shared_state::{get_or_insert_secret, SharedState};
+
 pub struct HashSecret(Vec<u8>);
 
 impl HashSecret {
-    pub async fn load_or_generate_secret() -> Result<Self, anyhow::Error> {
+    /// Loads the secret from the data directory, or generates and saves a new one if it doesn't exist.
+    ///
+    /// If another server instance has already stored its secret in the shared state that secret is used instead,
+    /// so that passwords can be verified on every instance.
+    pub async fn load_or_generate_secret(
+        shared_state: &dyn SharedState,
+    ) -> Result<Self, anyhow::Error> {
         let secret_path = Path::new("data").join("hash_secret");
 
-        if secret_path.try_exists()? {
+        let secret_bytes = if secret_path.try_exists()? {
             log::info!("Loading hash secret from file");
 
             let mut secret_bytes = Vec::with_capacity(64);
@@ -26,7 +34,7 @@ impl HashSecret {
                 anyhow::bail!("Invalid hash secret length, expected 64 bytes");
             }
 
-            Ok(Self(secret_bytes))
+            secret_bytes
         } else {
             log::warn!("Generating new hash secret");
 
@@ -35,8 +43,16 @@ impl HashSecret {
             let mut file = File::create(secret_path).await?;
             file.write_all(&secret_bytes).await?;
 
-            Ok(Self(secret_bytes))
+            secret_bytes
+        };
+
+        let secret_bytes = get_or_insert_secret(shared_state, "hash_secret", secret_bytes).await?;
+
+        if secret_bytes.len() != 64 {
+            anyhow::bail!("Invalid shared hash secret length, expected 64 bytes");
         }
+
+        Ok(Self(secret_bytes))
     }
 
     pub fn hash_string(&self, str: &str, salt: &[u8]) -> [u8; 64] {
@@ -8,14 +8,22 @@ use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
 };
 
+use super::shared_state::{get_or_insert_secret, SharedState};
+
 #[derive(Debug)]
 pub struct JwtSecret(Hmac<Sha256>);
 
 impl JwtSecret {
-    pub async fn load_or_generate_secret() -> Result<Self, anyhow::Error> {
+    /// Loads the secret from the data directory, or generates and saves a new one if it doesn't exist.
+    ///
+    /// If another server instance has already stored its secret in the shared state that secret is used instead,
+    /// so that frontend sessions work on every instance.
+    pub async fn load_or_generate_secret(
+        shared_state: &dyn SharedState,
+    ) -> Result<Self, anyhow::Error> {
         let secret_path = Path::new("data").join("jwt_secret");
 
-        if secret_path.try_exists()? {
+        let secret_bytes = if secret_path.try_exists()? {
             log::info!("Loading jwt secret from file");
 
             let mut secret_bytes = Vec::with_capacity(32);
@@ -28,19 +36,34 @@ impl JwtSecret {
                 anyhow::bail!("Invalid jwt secret length, expected 32 bytes");
             }
 
-            Ok(Self(Hmac::new_from_slice(&secret_bytes)?))
+            secret_bytes
         } else {
             log::warn!("Generating new jwt secret");
 
             let secret_bytes = parcel_common::rand::generate_u8(32);
-            let hmac =
-                Hmac::new_from_slice(&secret_bytes).expect("Key length should always be 32 bytes");
 
             let mut file = File::create(secret_path).await?;
             file.write_all(&secret_bytes).await?;
 
-            Ok(Self(hmac))
+            secret_bytes
+        };
+
+        let secret_bytes = get_or_insert_secret(shared_state, "jwt_secret", secret_bytes).await?;
+
+        if secret_bytes.len() != 32 {
+            anyhow::bail!("Invalid shared jwt secret length, expected 32 bytes");
         }
+
+        Ok(Self(Hmac::new_from_slice(&secret_bytes)?))
+    }
+
+    /// Creates a random secret that's not saved or shared with other server instances.
+    #[cfg(test)]
+    pub fn generate() -> Self {
+        Self(
+            Hmac::new_from_slice(&parcel_common::rand::generate_u8(32))
+                .expect("Hmac should accept keys of any length"),
+        )
     }
 }
 
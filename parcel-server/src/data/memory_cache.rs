@@ -1,4 +1,5 @@
 use std::{
+    borrow::Borrow,
     collections::HashMap,
     ops::{Deref, DerefMut},
     path::Path,
@@ -9,6 +10,8 @@ use bincode::Options;
 use moka::future::{Cache as MokaCache, CacheBuilder};
 use serde::{de::DeserializeOwned, Serialize};
 
+use crate::metrics;
+
 pub struct MemoryCache<K, V>
 where
     K: Eq + PartialEq + std::hash::Hash + Send + Sync + 'static,
@@ -34,6 +37,34 @@ where
                 .name(name),
         )
     }
+
+    /// Returns a clone of the value for the key, recording whether it was a hit in the metrics.
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: std::hash::Hash + Eq + ?Sized,
+    {
+        let value = self.cache.get(key);
+        self.count_lookup(value.is_some());
+
+        value
+    }
+
+    /// Returns true if the cache contains the key, recording whether it was a hit in the metrics.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: std::hash::Hash + Eq + ?Sized,
+    {
+        let contains_key = self.cache.contains_key(key);
+        self.count_lookup(contains_key);
+
+        contains_key
+    }
+
+    fn count_lookup(&self, hit: bool) {
+        metrics::count_cache_lookup(self.cache.name().unwrap_or("unnamed"), hit);
+    }
 }
 
 impl<K, V> Deref for MemoryCache<K, V>
@@ -4,3 +4,4 @@ pub mod jwt_secret;
 pub mod memory_cache;
 pub mod platforms;
 pub mod session_store;
+pub mod shared_state;
@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+/// Authenticates players with a key that is shared with every player, without contacting any external service.
+///
+/// Since there's no platform to verify the identity of the player, the display name is used as the provider id.
+/// This means that anyone who knows the key can log in as any player, so it should only be used on trusted networks such as LAN parties.
+pub struct LocalAuth {
+    key: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyKeyError {
+    InvalidKey,
+    InvalidDisplayName,
+}
+
+impl Display for VerifyKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerifyKeyError::InvalidKey => write!(f, "The key does not match the server's key"),
+            VerifyKeyError::InvalidDisplayName => {
+                write!(f, "The display name must not be empty")
+            }
+        }
+    }
+}
+
+impl LocalAuth {
+    pub fn new(key: String) -> Result<Self, anyhow::Error> {
+        if key.trim().is_empty() {
+            anyhow::bail!("The local auth key must not be empty");
+        }
+
+        Ok(Self { key })
+    }
+
+    /// Verifies the key and returns the provider id of the player.
+    pub fn verify_key(&self, key: &str, display_name: &str) -> Result<String, VerifyKeyError> {
+        if !constant_time_eq(self.key.as_bytes(), key.as_bytes()) {
+            return Err(VerifyKeyError::InvalidKey);
+        }
+
+        let display_name = display_name.trim();
+
+        if display_name.is_empty() {
+            return Err(VerifyKeyError::InvalidDisplayName);
+        }
+
+        Ok(display_name.to_owned())
+    }
+}
+
+/// Compares the two slices without returning early on the first mismatching byte, so that the key can't be guessed by timing the responses.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
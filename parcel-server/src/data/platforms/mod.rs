@@ -1,2 +1,3 @@
 pub mod epic;
+pub mod local;
 pub mod steam;
@@ -5,10 +5,9 @@ use reqwest::{Client, RequestBuilder, StatusCode};
 use serde::Deserialize;
 
 const APP_ID: u32 = 1850570;
-const URL_AUTH_USER_TICKET: &str =
-    "https://api.steampowered.com/ISteamUserAuth/AuthenticateUserTicket/v1/";
-const URL_GET_PLAYER_SUMMARIES: &str =
-    "https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2/";
+const API_URL: &str = "https://api.steampowered.com";
+const PATH_AUTH_USER_TICKET: &str = "/ISteamUserAuth/AuthenticateUserTicket/v1/";
+const PATH_GET_PLAYER_SUMMARIES: &str = "/ISteamUser/GetPlayerSummaries/v2/";
 
 pub type SteamId = u64;
 
@@ -117,6 +116,8 @@ pub struct PlayerSummary {
 #[derive(Debug)]
 pub struct Steam {
     api_key: String,
+    /// The url of the web api without a trailing slash
+    api_url: String,
     web_client: Client,
 }
 
@@ -164,10 +165,20 @@ impl Steam {
         let web_client = Client::builder().user_agent("DS").build()?;
         Ok(Self {
             api_key,
+            api_url: API_URL.into(),
             web_client,
         })
     }
 
+    /// Creates a client that sends its requests to a fake web api.
+    #[cfg(test)]
+    pub fn with_api_url(api_key: String, api_url: String) -> Result<Self, reqwest::Error> {
+        Ok(Self {
+            api_url,
+            ..Self::new(api_key)?
+        })
+    }
+
     /// Verifies the user auth ticket and if successful returns the user steam id and owner id (owner id is different if the game is family shared)
     pub async fn verify_user_auth_ticket<T>(
         &self,
@@ -179,7 +190,7 @@ impl Steam {
         let ticket_str: String = ticket.as_hex_string();
 
         let response = self
-            .create_request(reqwest::Method::GET, URL_AUTH_USER_TICKET)
+            .create_request(reqwest::Method::GET, PATH_AUTH_USER_TICKET)
             .query(&[("appid", APP_ID)])
             .query(&[("ticket", &ticket_str)])
             .send()
@@ -223,7 +234,7 @@ impl Steam {
         &self,
         user_ids: &[&SteamId],
     ) -> Result<HashMap<SteamId, PlayerSummary>, anyhow::Error> {
-        let mut builder = self.create_request(reqwest::Method::GET, URL_GET_PLAYER_SUMMARIES);
+        let mut builder = self.create_request(reqwest::Method::GET, PATH_GET_PLAYER_SUMMARIES);
 
         builder = builder.query(
             &user_ids
@@ -275,9 +286,9 @@ impl Steam {
         }
     }
 
-    fn create_request(&self, method: reqwest::Method, url: &str) -> RequestBuilder {
+    fn create_request(&self, method: reqwest::Method, path: &str) -> RequestBuilder {
         self.web_client
-            .request(method, url)
+            .request(method, format!("{}{path}", self.api_url))
             .query(&[("key", &self.api_key)])
     }
 }
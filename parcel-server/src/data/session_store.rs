@@ -1,150 +1,244 @@
-use std::{
-    fmt::Display,
-    path::{Path, PathBuf},
-    time::Duration,
-};
+use std::{collections::HashMap, path::Path, sync::Arc};
 
 use bincode::Options;
+use chrono::{Duration, NaiveDateTime, Utc};
 use parcel_common::api_types::auth::Provider;
+use serde::Deserialize;
 
-use crate::session::Session;
+use crate::{
+    data::{database::Database, memory_cache::MemoryCache},
+    session::Session,
+};
 
-pub struct SessionStore {
-    sessions: moka::future::Cache<String, Session>,
-    provider_lookup: moka::future::Cache<String, String>,
-    file_path: PathBuf,
-}
+/// Sessions expire when they haven't been used for this long.
+const SESSION_LIFETIME_SECS: i64 = 60 * 60 * 24;
 
-#[derive(Debug, thiserror::Error)]
-pub struct InvalidTokenError;
+/// How long loaded sessions are cached for.
+///
+/// Sessions that are deleted by another server instance sharing the same database can be used for this long on this instance.
+const CACHE_TIME_TO_LIVE_SECS: u64 = 10;
 
-impl Display for InvalidTokenError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "The token must be non zero length")
-    }
+/// Stores game sessions in the database, so that they survive restarts and can be shared between server instances.
+pub struct SessionStore {
+    database: Arc<Database>,
+    /// Recently loaded sessions by token
+    cache: MemoryCache<String, Session>,
 }
 
 impl SessionStore {
-    /// Loads or creates a new session store.
+    pub fn new(database: Arc<Database>) -> Self {
+        Self {
+            database,
+            cache: MemoryCache::with_time_to_live_secs("Sessions", CACHE_TIME_TO_LIVE_SECS),
+        }
+    }
+
+    /// Imports the sessions from the file that sessions were saved to before they were stored in the database, and then deletes the file.
     ///
-    /// If loading fails due to an IO or serialization error a new store is created.
-    pub async fn load_or_create(file_path: &Path) -> Self {
-        // todo: try to load sessions from file
-        let sessions = load_sessions(file_path).await.unwrap_or_default();
-
-        let result = Self {
-            file_path: file_path.to_owned(),
-            sessions: moka::future::CacheBuilder::new(u64::MAX)
-                .time_to_live(Duration::from_secs(60 * 60 * 24))
-                .name("Sessions")
-                .build(),
-            provider_lookup: moka::future::CacheBuilder::new(u64::MAX)
-                .time_to_live(Duration::from_secs(60 * 60 * 24))
-                .name("ProviderLookup")
-                .build(),
+    /// Sessions that belong to accounts that no longer exist are skipped. Returns the number of imported sessions.
+    ///
+    /// If the file can't be read it's renamed to `<file name>.unreadable` so that the server can still start, and so that it's not read again.
+    pub async fn import_legacy_sessions(&self, file_path: &Path) -> Result<usize, anyhow::Error> {
+        if !tokio::fs::try_exists(file_path).await? {
+            return Ok(0);
+        }
+
+        let sessions = match load_legacy_sessions(file_path).await {
+            Ok(sessions) => sessions,
+            Err(err) => {
+                let unreadable_path = file_path.with_extension("unreadable");
+                log::warn!(
+                    "Could not read sessions from {}, moving it to {}: {:#}",
+                    file_path.display(),
+                    unreadable_path.display(),
+                    err
+                );
+                tokio::fs::rename(file_path, &unreadable_path).await?;
+
+                return Ok(0);
+            }
         };
 
-        let mut futures = Vec::new();
+        let mut num_imported = 0;
 
         for session in sessions {
-            futures.push(result.save_session(session));
+            match self.save_session(session).await {
+                Ok(()) => num_imported += 1,
+                Err(err) => log::warn!("Could not import session: {}", err),
+            }
         }
 
-        futures::future::join_all(futures).await;
+        tokio::fs::remove_file(file_path).await?;
 
-        result
+        Ok(num_imported)
     }
 
-    pub async fn save_to_file(&self) -> Result<(), anyhow::Error> {
-        let sessions = self
-            .sessions
-            .iter()
-            .map(|(_, session)| session)
-            .collect::<Vec<_>>();
+    /// Adds or replaces the session.
+    pub async fn save_session(&self, session: Session) -> Result<(), anyhow::Error> {
+        let expires_at = get_expiry_date(&session.last_seen_at);
+        let conn = self.database.connect().await?;
+        conn.game_sessions()
+            .save(&session.to_game_session(expires_at))
+            .await?;
 
-        save_sessions(&self.file_path, &sessions).await?;
+        self.cache.invalidate(&session.token).await;
 
         Ok(())
     }
 
-    pub async fn save_session(&self, session: Session) {
-        self.set_reverse_lookup_token(
-            session.provider,
-            &session.provider_id,
-            session.token.clone(),
-        )
-        .await;
-        self.sessions
-            .insert(self.get_session_key(&session.token), session)
-            .await;
-
-        let _ = self.save_to_file().await;
-    }
+    /// Returns the session with the token if it exists and hasn't expired.
+    pub async fn load_session(&self, token: &str) -> Result<Option<Session>, anyhow::Error> {
+        if let Some(session) = self.cache.get(token) {
+            return Ok(Some(session));
+        }
+
+        let conn = self.database.connect().await?;
+        let session = conn
+            .game_sessions()
+            .get_by_token(token, &Utc::now().naive_utc())
+            .await?
+            .map(Session::from);
+
+        if let Some(session) = &session {
+            self.cache.insert(token.to_owned(), session.clone()).await;
+        }
 
-    pub async fn load_session(&self, token: &str) -> Option<Session> {
-        self.sessions.get(token)
+        Ok(session)
     }
 
-    pub async fn delete_session(&self, token: &str) {
-        let session = self.sessions.remove(&self.get_session_key(token)).await;
+    pub async fn delete_session(&self, token: &str) -> Result<(), anyhow::Error> {
+        let conn = self.database.connect().await?;
+        conn.game_sessions().delete(token).await?;
 
-        if let Some(session) = session {
-            let reverse_lookup_key =
-                self.get_session_reverse_lookup_key(session.provider, &session.provider_id);
+        self.cache.invalidate(token).await;
 
-            self.provider_lookup.remove(&reverse_lookup_key).await;
+        Ok(())
+    }
+
+    /// Deletes every session of the account, logging the player out everywhere.
+    ///
+    /// Returns the number of sessions that were deleted.
+    pub async fn delete_account_sessions(&self, account_id: &str) -> Result<usize, anyhow::Error> {
+        let conn = self.database.connect().await?;
+        let tokens = conn
+            .game_sessions()
+            .delete_by_account(account_id, &Utc::now().naive_utc())
+            .await?;
+
+        for token in &tokens {
+            self.cache.invalidate(token).await;
         }
 
-        let _ = self.save_to_file().await;
+        Ok(tokens.len())
     }
 
-    #[inline]
-    pub fn find_active_session_token(
-        &self,
-        provider: Provider,
-        provider_id: &str,
-    ) -> Option<String> {
-        self.get_reverse_lookup_token(provider, provider_id)
+    /// Deletes all sessions, logging out every player.
+    ///
+    /// Returns the number of sessions that were deleted.
+    pub async fn delete_all_sessions(&self) -> Result<usize, anyhow::Error> {
+        let conn = self.database.connect().await?;
+        let num_deleted = conn
+            .game_sessions()
+            .delete_all(&Utc::now().naive_utc())
+            .await?;
+
+        self.cache.invalidate_all();
+
+        Ok(num_deleted)
     }
 
-    async fn set_reverse_lookup_token(&self, provider: Provider, provider_id: &str, token: String) {
-        let key = self.get_session_reverse_lookup_key(provider, provider_id);
-        self.provider_lookup.insert(key, token).await;
+    /// Returns all active sessions.
+    pub async fn list_sessions(&self) -> Result<Vec<Session>, anyhow::Error> {
+        let conn = self.database.connect().await?;
+
+        Ok(conn
+            .game_sessions()
+            .list(&Utc::now().naive_utc())
+            .await?
+            .into_iter()
+            .map(Session::from)
+            .collect())
     }
 
-    fn get_reverse_lookup_token(&self, provider: Provider, provider_id: &str) -> Option<String> {
-        let key = self.get_session_reverse_lookup_key(provider, provider_id);
-        self.provider_lookup.get(&key)
+    /// Returns the number of active sessions.
+    pub async fn count_sessions(&self) -> Result<u64, anyhow::Error> {
+        let conn = self.database.connect().await?;
+        let count = conn.game_sessions().count(&Utc::now().naive_utc()).await?;
+
+        Ok(count as u64)
     }
 
-    #[inline]
-    fn get_session_key(&self, token: &str) -> String {
-        token.to_owned()
+    /// Updates the last seen date of the session, which also postpones when it expires.
+    pub async fn update_last_seen(
+        &self,
+        token: &str,
+        last_seen_at: NaiveDateTime,
+    ) -> Result<(), anyhow::Error> {
+        let conn = self.database.connect().await?;
+        conn.game_sessions()
+            .update_last_seen(token, &last_seen_at, &get_expiry_date(&last_seen_at))
+            .await?;
+
+        self.cache.invalidate(token).await;
+
+        Ok(())
     }
 
-    #[inline]
-    fn get_session_reverse_lookup_key(&self, provider: Provider, provider_id: &str) -> String {
-        format!("{:?}_{}", provider, provider_id)
+    pub async fn find_active_session_token(
+        &self,
+        provider: Provider,
+        provider_id: &str,
+    ) -> Result<Option<String>, anyhow::Error> {
+        let conn = self.database.connect().await?;
+
+        Ok(conn
+            .game_sessions()
+            .find_token_by_provider_id(provider, provider_id, &Utc::now().naive_utc())
+            .await?)
     }
 }
 
+fn get_expiry_date(last_seen_at: &NaiveDateTime) -> NaiveDateTime {
+    *last_seen_at + Duration::seconds(SESSION_LIFETIME_SECS)
+}
+
 fn get_bincode_options() -> impl bincode::Options {
     bincode::DefaultOptions::new()
         .with_varint_encoding()
         .with_little_endian()
 }
 
-async fn load_sessions(file_path: &Path) -> Result<Vec<Session>, anyhow::Error> {
-    let bincode = get_bincode_options();
-    let bytes = tokio::fs::read(file_path).await?;
-    let sessions = bincode.deserialize(&bytes)?;
-
-    Ok(sessions)
+/// A session as it was saved to the sessions file, before sessions had a login and last seen date.
+#[derive(Deserialize)]
+struct LegacySession {
+    provider: Provider,
+    provider_id: String,
+    account_id: String,
+    token: String,
+    values: HashMap<String, String>,
 }
 
-async fn save_sessions(file_path: &Path, sessions: &[Session]) -> Result<(), anyhow::Error> {
+/// Loads the sessions from the file. The login and last seen dates are set to now, since the file doesn't have them.
+async fn load_legacy_sessions(file_path: &Path) -> Result<Vec<Session>, anyhow::Error> {
     let bincode = get_bincode_options();
-    let bytes = bincode.serialize(sessions)?;
-    tokio::fs::write(file_path, bytes).await?;
-    Ok(())
+    let bytes = tokio::fs::read(file_path).await?;
+    let sessions: Vec<LegacySession> = bincode.deserialize(&bytes)?;
+
+    Ok(sessions
+        .into_iter()
+        .map(|legacy| {
+            let mut session = Session::new(
+                legacy.provider,
+                &legacy.provider_id,
+                &legacy.account_id,
+                legacy.token,
+            );
+
+            for (key, value) in &legacy.values {
+                session.set_raw(key, value);
+            }
+
+            session
+        })
+        .collect())
 }
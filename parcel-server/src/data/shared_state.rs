@@ -0,0 +1,397 @@
+//! State that needs to be the same on every server instance, such as settings, secrets and revoked frontend sessions.
+//!
+//! A single server keeps it in memory. When several server instances use the same database it's stored in the database instead,
+//! and events are sent to the other instances with PostgreSQL's LISTEN/NOTIFY so that they can update their caches.
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::{
+    sync::{broadcast, RwLock},
+    task::JoinHandle,
+};
+use tokio_postgres::{AsyncMessage, NoTls};
+
+use crate::db::models::shared_value::SharedValue;
+
+use super::database::Database;
+
+/// The PostgreSQL channel that events are sent on.
+const NOTIFY_CHANNEL: &str = "parcel_shared_state";
+
+/// How many events can be queued for a subscriber before it starts missing events.
+const EVENT_BUFFER_SIZE: usize = 256;
+
+/// How long to wait before reconnecting after losing the connection that listens for events.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Something that happened on one server instance that the other instances need to know about.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SharedStateEvent {
+    /// The value with the key was changed
+    #[serde(rename_all = "camelCase")]
+    ValueChanged { key: String },
+    /// A frontend session was logged out and the token can't be used anymore
+    #[serde(rename_all = "camelCase")]
+    FrontendSessionRevoked {
+        token: String,
+        expires_at: DateTime<Utc>,
+    },
+    /// The permissions of a frontend account were changed
+    #[serde(rename_all = "camelCase")]
+    FrontendPermissionsChanged { account_id: i64 },
+    /// A frontend login through Steam finished, and the frontend can get the result with the callback token.
+    ///
+    /// The result contains a session token, so it's stored as a shared value instead of being sent to every instance.
+    #[serde(rename_all = "camelCase")]
+    FrontendAuthFinished { callback_token: String },
+}
+
+#[async_trait::async_trait]
+pub trait SharedState: Send + Sync {
+    /// Returns the value with the key, or None if it doesn't exist or has expired.
+    async fn get(&self, key: &str) -> Result<Option<Value>, anyhow::Error>;
+
+    /// Returns every value that hasn't expired with a key that starts with the prefix.
+    async fn list(&self, prefix: &str) -> Result<Vec<(String, Value)>, anyhow::Error>;
+
+    /// Returns the value with the key, or adds the specified value and returns it if the key doesn't exist.
+    ///
+    /// If several instances do this at the same time they all get the value of the instance that added it first.
+    async fn get_or_insert(&self, key: &str, value: Value) -> Result<Value, anyhow::Error>;
+
+    /// Adds or replaces the value with the key. Values without an expiry date are kept forever.
+    ///
+    /// Other instances are not notified, use `publish` for that.
+    async fn set(
+        &self,
+        key: &str,
+        value: Value,
+        expires_at: Option<NaiveDateTime>,
+    ) -> Result<(), anyhow::Error>;
+
+    /// Removes the value with the key and returns it, or returns None if it doesn't exist or has expired.
+    ///
+    /// If several instances do this at the same time only one of them gets the value.
+    async fn take(&self, key: &str) -> Result<Option<Value>, anyhow::Error>;
+
+    /// Sends the event to every server instance, including this one.
+    async fn publish(&self, event: &SharedStateEvent) -> Result<(), anyhow::Error>;
+
+    /// Returns a receiver for the events that are published from now on.
+    fn subscribe(&self) -> broadcast::Receiver<SharedStateEvent>;
+}
+
+/// Keeps the shared state in memory, for when there is only one server instance.
+pub struct LocalSharedState {
+    values: RwLock<HashMap<String, (Value, Option<NaiveDateTime>)>>,
+    events: broadcast::Sender<SharedStateEvent>,
+}
+
+impl Default for LocalSharedState {
+    fn default() -> Self {
+        Self {
+            values: RwLock::new(HashMap::new()),
+            events: broadcast::channel(EVENT_BUFFER_SIZE).0,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SharedState for LocalSharedState {
+    async fn get(&self, key: &str) -> Result<Option<Value>, anyhow::Error> {
+        let now = Utc::now().naive_utc();
+
+        Ok(self
+            .values
+            .read()
+            .await
+            .get(key)
+            .filter(|(_, expires_at)| !is_expired(expires_at, &now))
+            .map(|(value, _)| value.clone()))
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<(String, Value)>, anyhow::Error> {
+        let now = Utc::now().naive_utc();
+
+        Ok(self
+            .values
+            .read()
+            .await
+            .iter()
+            .filter(|(key, (_, expires_at))| {
+                key.starts_with(prefix) && !is_expired(expires_at, &now)
+            })
+            .map(|(key, (value, _))| (key.clone(), value.clone()))
+            .collect())
+    }
+
+    async fn get_or_insert(&self, key: &str, value: Value) -> Result<Value, anyhow::Error> {
+        let now = Utc::now().naive_utc();
+        let mut values = self.values.write().await;
+
+        match values.get(key) {
+            Some((existing, expires_at)) if !is_expired(expires_at, &now) => Ok(existing.clone()),
+            _ => {
+                values.insert(key.to_owned(), (value.clone(), None));
+                Ok(value)
+            }
+        }
+    }
+
+    async fn set(
+        &self,
+        key: &str,
+        value: Value,
+        expires_at: Option<NaiveDateTime>,
+    ) -> Result<(), anyhow::Error> {
+        let now = Utc::now().naive_utc();
+        let mut values = self.values.write().await;
+
+        // Expired values are removed here since there is no background job for them
+        values.retain(|_, (_, expires_at)| !is_expired(expires_at, &now));
+        values.insert(key.to_owned(), (value, expires_at));
+
+        Ok(())
+    }
+
+    async fn take(&self, key: &str) -> Result<Option<Value>, anyhow::Error> {
+        let now = Utc::now().naive_utc();
+
+        Ok(self
+            .values
+            .write()
+            .await
+            .remove(key)
+            .filter(|(_, expires_at)| !is_expired(expires_at, &now))
+            .map(|(value, _)| value))
+    }
+
+    async fn publish(&self, event: &SharedStateEvent) -> Result<(), anyhow::Error> {
+        // Sending only fails if there are no subscribers, which is fine
+        let _ = self.events.send(event.clone());
+
+        Ok(())
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<SharedStateEvent> {
+        self.events.subscribe()
+    }
+}
+
+/// Stores the shared state in the database and sends events to the other server instances that use the same database.
+pub struct DatabaseSharedState {
+    database: Arc<Database>,
+    events: broadcast::Sender<SharedStateEvent>,
+    listener: JoinHandle<()>,
+}
+
+impl DatabaseSharedState {
+    /// Starts listening for events from the other server instances.
+    ///
+    /// Fails if the first connection can't be made. If the connection is lost later on it's reconnected in the background,
+    /// and events that were sent while disconnected are missed.
+    pub async fn start(database: Arc<Database>, database_url: &str) -> Result<Self, anyhow::Error> {
+        let events = broadcast::channel(EVENT_BUFFER_SIZE).0;
+        let connection = connect_listener(database_url).await?;
+        let listener = tokio::spawn(listen(database_url.to_owned(), events.clone(), connection));
+
+        Ok(Self {
+            database,
+            events,
+            listener,
+        })
+    }
+}
+
+impl Drop for DatabaseSharedState {
+    fn drop(&mut self) {
+        self.listener.abort();
+    }
+}
+
+#[async_trait::async_trait]
+impl SharedState for DatabaseSharedState {
+    async fn get(&self, key: &str) -> Result<Option<Value>, anyhow::Error> {
+        let conn = self.database.connect().await?;
+
+        Ok(conn
+            .shared_values()
+            .get(key, &Utc::now().naive_utc())
+            .await?)
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<(String, Value)>, anyhow::Error> {
+        let conn = self.database.connect().await?;
+
+        Ok(conn
+            .shared_values()
+            .list_by_prefix(prefix, &Utc::now().naive_utc())
+            .await?)
+    }
+
+    async fn get_or_insert(&self, key: &str, value: Value) -> Result<Value, anyhow::Error> {
+        let conn = self.database.connect().await?;
+        let shared_values = conn.shared_values();
+
+        shared_values
+            .insert_if_absent(&SharedValue {
+                key: key.to_owned(),
+                value,
+                expires_at: None,
+            })
+            .await?;
+
+        shared_values
+            .get(key, &Utc::now().naive_utc())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Shared value \"{key}\" has expired"))
+    }
+
+    async fn set(
+        &self,
+        key: &str,
+        value: Value,
+        expires_at: Option<NaiveDateTime>,
+    ) -> Result<(), anyhow::Error> {
+        let conn = self.database.connect().await?;
+        conn.shared_values()
+            .save(&SharedValue {
+                key: key.to_owned(),
+                value,
+                expires_at,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    async fn take(&self, key: &str) -> Result<Option<Value>, anyhow::Error> {
+        let conn = self.database.connect().await?;
+
+        Ok(conn
+            .shared_values()
+            .take(key, &Utc::now().naive_utc())
+            .await?)
+    }
+
+    async fn publish(&self, event: &SharedStateEvent) -> Result<(), anyhow::Error> {
+        // The event is received by this instance's listener as well, so it's not sent to the subscribers here
+        let payload = serde_json::to_string(event)?;
+        let conn = self.database.connect().await?;
+        conn.shared_values()
+            .notify(NOTIFY_CHANNEL, &payload)
+            .await?;
+
+        Ok(())
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<SharedStateEvent> {
+        self.events.subscribe()
+    }
+}
+
+/// Returns the value with the key, or stores the specified value if no server instance has stored one yet.
+///
+/// Used for secrets that every instance needs to use the same value for, such as the jwt secret.
+pub async fn get_or_insert_secret(
+    shared_state: &dyn SharedState,
+    key: &str,
+    secret: Vec<u8>,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let value = shared_state
+        .get_or_insert(key, Value::String(hex::encode(secret)))
+        .await?;
+    let hex_secret = value
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Shared secret \"{key}\" is not a string"))?;
+
+    Ok(hex::decode(hex_secret)?)
+}
+
+fn is_expired(expires_at: &Option<NaiveDateTime>, now: &NaiveDateTime) -> bool {
+    expires_at.map_or(false, |expires_at| expires_at <= *now)
+}
+
+/// A connection to the database that is listening for notifications on the events channel.
+struct ListenerConnection {
+    /// The connection is closed when the client is dropped
+    _client: tokio_postgres::Client,
+    messages: futures_util::stream::BoxStream<'static, Result<AsyncMessage, tokio_postgres::Error>>,
+}
+
+async fn connect_listener(database_url: &str) -> Result<ListenerConnection, anyhow::Error> {
+    let (client, mut connection) = tokio_postgres::connect(database_url, NoTls).await?;
+    let mut messages = futures_util::stream::poll_fn(move |cx| connection.poll_message(cx)).boxed();
+
+    // The connection needs to be polled for the LISTEN query to complete
+    {
+        let query = format!("LISTEN {NOTIFY_CHANNEL}");
+        let listen_query = client.batch_execute(&query);
+        tokio::pin!(listen_query);
+
+        loop {
+            tokio::select! {
+                result = &mut listen_query => {
+                    result?;
+                    break;
+                }
+                message = messages.next() => match message {
+                    Some(Ok(_)) => {}
+                    Some(Err(err)) => return Err(err.into()),
+                    None => anyhow::bail!("Connection closed before listening for events"),
+                }
+            }
+        }
+    }
+
+    Ok(ListenerConnection {
+        _client: client,
+        messages,
+    })
+}
+
+/// Forwards events from the database to the subscribers, and reconnects if the connection is lost.
+async fn listen(
+    database_url: String,
+    events: broadcast::Sender<SharedStateEvent>,
+    mut connection: ListenerConnection,
+) {
+    loop {
+        while let Some(message) = connection.messages.next().await {
+            match message {
+                Ok(AsyncMessage::Notification(notification)) => {
+                    match serde_json::from_str(notification.payload()) {
+                        Ok(event) => {
+                            let _ = events.send(event);
+                        }
+                        Err(err) => log::warn!("Received invalid shared state event: {err}"),
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    log::error!("Lost connection while listening for shared state events: {err}");
+                    break;
+                }
+            }
+        }
+
+        connection = loop {
+            tokio::time::sleep(RECONNECT_DELAY).await;
+
+            match connect_listener(&database_url).await {
+                Ok(connection) => {
+                    log::info!("Reconnected to listen for shared state events");
+                    break connection;
+                }
+                Err(err) => {
+                    log::error!("Could not reconnect to listen for shared state events: {err}")
+                }
+            }
+        };
+    }
+}
@@ -0,0 +1,37 @@
+use chrono::NaiveDateTime;
+use diesel::{Insertable, Queryable};
+use parcel_common::api_types::frontend::audit_log::AuditAction;
+use serde::Serialize;
+
+use crate::db::schema::audit_log;
+
+#[derive(Debug, Queryable)]
+pub struct AuditLogEntry {
+    pub id: i64,
+    pub created_at: NaiveDateTime,
+    pub frontend_actor_id: Option<i64>,
+    pub game_actor_id: Option<String>,
+    pub action: AuditAction,
+    pub target_id: Option<String>,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+/// A new audit log entry. The creation date is set when it's recorded.
+#[derive(Debug, Insertable)]
+#[diesel(table_name = audit_log)]
+pub struct NewAuditLogEntry<'a> {
+    pub frontend_actor_id: Option<i64>,
+    pub game_actor_id: Option<&'a str>,
+    pub action: AuditAction,
+    pub target_id: Option<&'a str>,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+/// Converts a value to JSON for the before or after value of an audit log entry.
+///
+/// Returns None if the value can't be represented as JSON, since a missing value is better than not recording the action.
+pub fn to_audit_value<T: Serialize>(value: &T) -> Option<serde_json::Value> {
+    serde_json::to_value(value).ok()
+}
@@ -0,0 +1,38 @@
+use chrono::NaiveDateTime;
+use diesel::{Insertable, Queryable};
+use parcel_common::api_types::auth::Provider;
+
+use crate::db::schema::bans;
+
+#[derive(Debug, Queryable)]
+pub struct Ban {
+    pub id: i64,
+    pub account_id: Option<String>,
+    pub provider: Provider,
+    pub provider_id: String,
+    pub reason: String,
+    pub issued_by: Option<i64>,
+    pub created_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub lifted_at: Option<NaiveDateTime>,
+    pub lifted_by: Option<i64>,
+}
+
+impl Ban {
+    /// Returns true if the ban hasn't been lifted and hasn't expired yet.
+    pub fn is_active(&self, now: &NaiveDateTime) -> bool {
+        self.lifted_at.is_none() && self.expires_at.map_or(true, |expires_at| expires_at > *now)
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = bans)]
+pub struct NewBan<'a> {
+    pub account_id: Option<&'a str>,
+    pub provider: Provider,
+    pub provider_id: &'a str,
+    pub reason: &'a str,
+    pub issued_by: Option<i64>,
+    pub created_at: &'a NaiveDateTime,
+    pub expires_at: Option<&'a NaiveDateTime>,
+}
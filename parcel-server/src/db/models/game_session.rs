@@ -0,0 +1,19 @@
+use chrono::NaiveDateTime;
+use diesel::{AsChangeset, Insertable, Queryable};
+use parcel_common::api_types::auth::Provider;
+
+use crate::db::schema::game_sessions;
+
+#[derive(Debug, Queryable, Insertable, AsChangeset)]
+#[diesel(table_name = game_sessions)]
+pub struct GameSession {
+    pub token: String,
+    pub provider: Provider,
+    pub provider_id: String,
+    pub account_id: String,
+    pub logged_in_at: NaiveDateTime,
+    pub last_seen_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    /// The session's values as a json object of strings
+    pub data: serde_json::Value,
+}
@@ -1,9 +1,14 @@
 pub mod account;
+pub mod audit_log;
+pub mod ban;
 pub mod frontend_account;
+pub mod game_session;
 pub mod highway;
 pub mod like;
 pub mod mission;
 pub mod player_profile;
 pub mod qpid_object;
+pub mod ranking;
 pub mod road;
+pub mod shared_value;
 pub mod wasted_baggage;
@@ -13,12 +13,15 @@ pub mod vehicle_info;
 use std::num::TryFromIntError;
 
 use chrono::NaiveDateTime;
-use diesel::{Insertable, Queryable};
+use diesel::{Insertable, Queryable, QueryableByName};
 use parcel_common::api_types::{self, area::AreaHash, object::ObjectType, TryIntoDsApiType};
 
 use crate::db::schema::qpid_objects;
 
-#[derive(Debug, Queryable)]
+use self::vehicle_info::VehicleInfo;
+
+#[derive(Debug, Queryable, QueryableByName)]
+#[diesel(table_name = qpid_objects)]
 pub struct QpidObject {
     pub id: String,
     pub creator_id: String,
@@ -40,6 +43,30 @@ pub struct QpidObject {
     pub is_deleted: bool,
 }
 
+impl QpidObject {
+    /// Returns the position of the object in world coordinates.
+    ///
+    /// Vehicles can be moved after they're created, in which case their current position is in their vehicle info.
+    pub fn world_position(&self, vehicle_info: Option<&VehicleInfo>) -> (f64, f64, f64) {
+        let (exponent, x, y, z) = match vehicle_info {
+            Some(v) => (
+                v.exponent,
+                v.new_pos_x.unwrap_or(self.pos_x),
+                v.new_pos_y.unwrap_or(self.pos_y),
+                v.new_pos_z.unwrap_or(self.pos_z),
+            ),
+            None => (self.exponent, self.pos_x, self.pos_y, self.pos_z),
+        };
+        let dividend = 10i32.pow(exponent as u32) as f64;
+
+        (
+            x as f64 / dividend,
+            y as f64 / dividend,
+            z as f64 / dividend,
+        )
+    }
+}
+
 #[derive(Debug, Insertable)]
 #[diesel(table_name = qpid_objects)]
 pub struct NewQpidObject<'a> {
@@ -0,0 +1,76 @@
+use chrono::NaiveDateTime;
+use diesel::{Insertable, Queryable};
+use parcel_common::api_types::{self, rank::RankingCategory, IntoDsApiType};
+
+use crate::db::schema::{ranking_records, ranking_schedules};
+
+#[derive(Debug, Queryable)]
+pub struct RankingSchedule {
+    pub id: i32,
+    pub category: RankingCategory,
+    pub start_date: NaiveDateTime,
+    pub end_date: NaiveDateTime,
+    pub closed_at: Option<NaiveDateTime>,
+}
+
+impl IntoDsApiType for RankingSchedule {
+    type ApiType = api_types::requests::get_ranking_schedules::RankingSchedule;
+
+    fn into_ds_api_type(self) -> Self::ApiType {
+        Self::ApiType {
+            basement_index: self.id,
+            start_date: self.start_date.timestamp_millis(),
+            end_date: self.end_date.timestamp_millis(),
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = ranking_schedules)]
+pub struct NewRankingSchedule<'a> {
+    pub category: RankingCategory,
+    pub start_date: &'a NaiveDateTime,
+    pub end_date: &'a NaiveDateTime,
+}
+
+#[derive(Debug, Queryable)]
+pub struct RankingRecord {
+    pub id: i64,
+    pub schedule_id: i32,
+    pub account_id: String,
+    pub score: i64,
+    pub try_count: i32,
+    pub clear_count: i32,
+    pub rank: Option<i32>,
+    pub reward_tier: Option<i16>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl RankingRecord {
+    /// Converts the record into the game's api type. The category can't be read from the record itself so it has to be passed in from the schedule.
+    pub fn into_ds_api_record(self, category: RankingCategory) -> api_types::rank::RankingRecord {
+        api_types::rank::RankingRecord {
+            category: category as i32,
+            clear_count: self.clear_count as u32,
+            try_count: self.try_count as u32,
+            difficulty: 0,
+            detail_rank: self.reward_tier.map(|tier| tier as i32).unwrap_or(-1),
+            flags: 0,
+            mission_id: 0,
+            rank: self.rank.unwrap_or(0),
+            score: self.score.clamp(0, i32::MAX as i64) as i32,
+            season_id: self.schedule_id,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = ranking_records)]
+pub struct NewRankingRecord<'a> {
+    pub schedule_id: i32,
+    pub account_id: &'a str,
+    pub score: i64,
+    pub try_count: i32,
+    pub clear_count: i32,
+    pub updated_at: &'a NaiveDateTime,
+}
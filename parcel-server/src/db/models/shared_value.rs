@@ -0,0 +1,13 @@
+use chrono::NaiveDateTime;
+use diesel::{AsChangeset, Insertable, Queryable};
+
+use crate::db::schema::shared_values;
+
+#[derive(Debug, Queryable, Insertable, AsChangeset)]
+#[diesel(table_name = shared_values, treat_none_as_null = true)]
+pub struct SharedValue {
+    pub key: String,
+    pub value: serde_json::Value,
+    /// Expired values are ignored and deleted by a background job. Values without an expiry date are kept forever.
+    pub expires_at: Option<NaiveDateTime>,
+}
@@ -28,6 +28,34 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    audit_log (id) {
+        id -> Int8,
+        created_at -> Timestamp,
+        frontend_actor_id -> Nullable<Int8>,
+        game_actor_id -> Nullable<Varchar>,
+        action -> Int4,
+        target_id -> Nullable<Varchar>,
+        before -> Nullable<Jsonb>,
+        after -> Nullable<Jsonb>,
+    }
+}
+
+diesel::table! {
+    bans (id) {
+        id -> Int8,
+        account_id -> Nullable<Varchar>,
+        provider -> Int4,
+        provider_id -> Varchar,
+        reason -> Varchar,
+        issued_by -> Nullable<Int8>,
+        created_at -> Timestamp,
+        expires_at -> Nullable<Timestamp>,
+        lifted_at -> Nullable<Timestamp>,
+        lifted_by -> Nullable<Int8>,
+    }
+}
+
 diesel::table! {
     devoted_highway_resources (id) {
         id -> Int8,
@@ -77,6 +105,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    game_sessions (token) {
+        token -> Varchar,
+        provider -> Int4,
+        provider_id -> Varchar,
+        account_id -> Varchar,
+        logged_in_at -> Timestamp,
+        last_seen_at -> Timestamp,
+        expires_at -> Timestamp,
+        data -> Jsonb,
+    }
+}
+
 diesel::table! {
     likes (id) {
         id -> Int8,
@@ -386,6 +427,30 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    ranking_records (id) {
+        id -> Int8,
+        schedule_id -> Int4,
+        account_id -> Varchar,
+        score -> Int8,
+        try_count -> Int4,
+        clear_count -> Int4,
+        rank -> Nullable<Int4>,
+        reward_tier -> Nullable<Int2>,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    ranking_schedules (id) {
+        id -> Int4,
+        category -> Int4,
+        start_date -> Timestamp,
+        end_date -> Timestamp,
+        closed_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     road_data (road_id) {
         road_id -> Varchar,
@@ -417,6 +482,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    shared_values (key) {
+        key -> Varchar,
+        value -> Jsonb,
+        expires_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     total_highway_likes (account_id) {
         account_id -> Varchar,
@@ -447,11 +520,15 @@ diesel::table! {
     }
 }
 
+diesel::joinable!(audit_log -> accounts (game_actor_id));
+diesel::joinable!(audit_log -> frontend_accounts (frontend_actor_id));
+diesel::joinable!(bans -> accounts (account_id));
 diesel::joinable!(devoted_highway_resources -> accounts (account_id));
 diesel::joinable!(frontend_account_credentials -> frontend_accounts (account_id));
 diesel::joinable!(frontend_account_provider_connections -> frontend_accounts (account_id));
 diesel::joinable!(frontend_account_sessions -> frontend_accounts (account_id));
 diesel::joinable!(frontend_accounts -> accounts (game_account_id));
+diesel::joinable!(game_sessions -> accounts (account_id));
 diesel::joinable!(mission_baggage_ammo_infos -> mission_baggages (baggage_id));
 diesel::joinable!(mission_baggages -> missions (mission_id));
 diesel::joinable!(mission_catapult_shell_infos -> missions (mission_id));
@@ -479,6 +556,8 @@ diesel::joinable!(qpid_object_stone_infos -> qpid_objects (object_id));
 diesel::joinable!(qpid_object_tags -> qpid_objects (object_id));
 diesel::joinable!(qpid_object_vehicle_infos -> qpid_objects (object_id));
 diesel::joinable!(qpid_objects -> accounts (creator_id));
+diesel::joinable!(ranking_records -> accounts (account_id));
+diesel::joinable!(ranking_records -> ranking_schedules (schedule_id));
 diesel::joinable!(road_data -> roads (road_id));
 diesel::joinable!(road_via_qpids -> roads (road_id));
 diesel::joinable!(roads -> accounts (creator_id));
@@ -489,11 +568,14 @@ diesel::allow_tables_to_appear_in_same_query!(
     account_histories,
     account_strand_contracts,
     accounts,
+    audit_log,
+    bans,
     devoted_highway_resources,
     frontend_account_credentials,
     frontend_account_provider_connections,
     frontend_account_sessions,
     frontend_accounts,
+    game_sessions,
     likes,
     mission_baggage_ammo_infos,
     mission_baggages,
@@ -518,9 +600,12 @@ diesel::allow_tables_to_appear_in_same_query!(
     qpid_object_tags,
     qpid_object_vehicle_infos,
     qpid_objects,
+    ranking_records,
+    ranking_schedules,
     road_data,
     road_via_qpids,
     roads,
+    shared_values,
     total_highway_likes,
     total_highway_resources,
     wasted_baggages,
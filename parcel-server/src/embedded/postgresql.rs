@@ -11,6 +11,7 @@ lazy_static::lazy_static! {
 }
 
 const DB_NAME: &str = "parcels";
+const INSTALLATION_DIR: &str = "data/postgresql/bin";
 
 /// Sets up local postgresql server (if necessary) and returns the database connection string.
 pub async fn setup_postgresql(args: &Options) -> Result<String, anyhow::Error> {
@@ -25,25 +26,12 @@ pub async fn setup_postgresql(args: &Options) -> Result<String, anyhow::Error> {
                 }
                 None => {
                     let settings = Settings {
-                        version: VersionReq::parse("=16.3.0").unwrap(),
-                        installation_dir: PathBuf::from("data/postgresql/bin"),
-                        temporary: false,
-                        data_dir: PathBuf::from("data/postgresql/data"),
                         password: "ds_1234".into(),
                         password_file: PathBuf::from("data/postgresql/pw"),
-                        timeout: None,
-                        ..Default::default()
+                        ..create_settings(PathBuf::from("data/postgresql/data"), false)
                     };
-                    let mut pg_embed = PostgreSQL::new(settings);
-                    pg_embed.setup().await?;
-                    pg_embed.start().await?;
+                    let (pg_embed, url) = start_postgresql(settings).await?;
 
-                    log::debug!("Creating database if it doesn't exist...");
-                    if !pg_embed.database_exists(DB_NAME).await? {
-                        pg_embed.create_database(DB_NAME).await?;
-                    }
-
-                    let url = pg_embed.settings().url(DB_NAME);
                     static_pg_embed.replace(pg_embed);
                     Ok(url)
                 }
@@ -52,6 +40,51 @@ pub async fn setup_postgresql(args: &Options) -> Result<String, anyhow::Error> {
     }
 }
 
+/// Sets up and starts a postgresql server with its data stored in the specified directory.
+///
+/// The server is stopped and its data directory is deleted when the returned server is dropped.
+#[cfg(test)]
+pub async fn setup_temporary_postgresql(
+    data_dir: &std::path::Path,
+) -> Result<(PostgreSQL, String), anyhow::Error> {
+    static INSTALLED: tokio::sync::OnceCell<()> = tokio::sync::OnceCell::const_new();
+
+    let settings = Settings {
+        password_file: data_dir.join("pw"),
+        ..create_settings(data_dir.join("data"), true)
+    };
+    let mut pg_embed = PostgreSQL::new(settings);
+    let mut is_setup = false;
+
+    // Tests run in parallel and share the installation directory, so the first test installs the binaries while the others wait.
+    // After that, setting up only initializes the data directory of each test.
+    {
+        let pg_embed = &mut pg_embed;
+        let is_setup = &mut is_setup;
+
+        INSTALLED
+            .get_or_try_init(|| async move {
+                pg_embed
+                    .setup()
+                    .await
+                    .context("Could not setup PostgreSQL server")?;
+                *is_setup = true;
+
+                Ok::<_, anyhow::Error>(())
+            })
+            .await?;
+    }
+
+    if !is_setup {
+        pg_embed
+            .setup()
+            .await
+            .context("Could not setup PostgreSQL server")?;
+    }
+
+    start_and_create_database(pg_embed).await
+}
+
 pub async fn stop_postgresql() -> Result<(), anyhow::Error> {
     let server = &mut *PG_EMBED.lock().await;
 
@@ -64,3 +97,45 @@ pub async fn stop_postgresql() -> Result<(), anyhow::Error> {
         None => Ok(()),
     }
 }
+
+fn create_settings(data_dir: PathBuf, temporary: bool) -> Settings {
+    Settings {
+        version: VersionReq::parse("=16.3.0").unwrap(),
+        installation_dir: PathBuf::from(INSTALLATION_DIR),
+        temporary,
+        data_dir,
+        timeout: None,
+        ..Default::default()
+    }
+}
+
+/// Installs (if necessary) and starts the server, then creates the database if it doesn't exist.
+///
+/// Returns the server along with the connection string to the database.
+async fn start_postgresql(settings: Settings) -> Result<(PostgreSQL, String), anyhow::Error> {
+    let mut pg_embed = PostgreSQL::new(settings);
+    pg_embed
+        .setup()
+        .await
+        .context("Could not setup PostgreSQL server")?;
+
+    start_and_create_database(pg_embed).await
+}
+
+/// Starts the server that has been set up, then creates the database if it doesn't exist.
+async fn start_and_create_database(
+    mut pg_embed: PostgreSQL,
+) -> Result<(PostgreSQL, String), anyhow::Error> {
+    pg_embed
+        .start()
+        .await
+        .context("Could not start PostgreSQL server")?;
+
+    log::debug!("Creating database if it doesn't exist...");
+    if !pg_embed.database_exists(DB_NAME).await? {
+        pg_embed.create_database(DB_NAME).await?;
+    }
+
+    let url = pg_embed.settings().url(DB_NAME);
+    Ok((pg_embed, url))
+}
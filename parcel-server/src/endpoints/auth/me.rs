@@ -15,7 +15,7 @@ use crate::{
 /// This route doesn't exist on the "real" ds server, it's only used for debugging.
 ///
 /// Leaving it in for production is safe since the same info is sent to the client on auth.
-#[get("auth/me")]
+#[get("me")]
 pub async fn me(
     session: Session,
     database: Data<Database>,
@@ -5,11 +5,11 @@ use std::fmt::Display;
 use actix_http::StatusCode;
 use actix_web::{
     get,
-    web::{Data, Json, Query},
+    web::{Data, Json, Path, Query},
     HttpRequest,
 };
 
-use chrono::Utc;
+use chrono::{NaiveDateTime, Utc};
 use parcel_common::{
     api_types::auth::{AuthResponse, Provider, SessionInfo, SessionProperties, UserInfo},
     rand,
@@ -21,6 +21,7 @@ use crate::{
         database::Database,
         platforms::{
             epic::{self, Epic},
+            local::LocalAuth,
             steam::{self, Steam},
         },
         session_store::SessionStore,
@@ -35,8 +36,7 @@ use super::InternalError;
 #[derive(Debug, Deserialize)]
 pub struct AuthQuery {
     provider: Provider,
-    #[serde(rename = "display_name")]
-    _display_name: String,
+    display_name: String,
     code: String,
 }
 
@@ -44,9 +44,11 @@ pub struct AuthQuery {
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     ApiResponseError(anyhow::Error),
+    Banned { expires_at: Option<NaiveDateTime> },
     InvalidCode,
     InternalError(InternalError),
     NotWhitelisted,
+    ProviderDisabled(Provider),
 }
 
 impl From<crate::db::QueryError> for Error {
@@ -65,6 +67,10 @@ impl Display for Error {
                     err
                 )
             }
+            Error::Banned { expires_at } => match expires_at {
+                Some(expires_at) => write!(f, "Account is banned until {}", expires_at),
+                None => write!(f, "Account is permanently banned"),
+            },
             Error::InvalidCode => {
                 write!(f, "Could not authenticate user from code")
             }
@@ -74,6 +80,9 @@ impl Display for Error {
             Error::NotWhitelisted => {
                 write!(f, "Account is not whitelisted")
             }
+            Error::ProviderDisabled(provider) => {
+                write!(f, "{} authentication is disabled on this server", provider)
+            }
         }
     }
 }
@@ -83,36 +92,43 @@ impl CommonResponseError for Error {
     fn get_status_code(&self) -> String {
         match self {
             Error::ApiResponseError(_) => "AU-AE".into(),
+            Error::Banned { .. } => "AU-BN".into(),
             Error::InvalidCode => "AU-IC".into(),
             Error::InternalError(err) => err.get_status_code(),
             Error::NotWhitelisted => "AU-NW".into(),
+            Error::ProviderDisabled(_) => "AU-PD".into(),
         }
     }
 
     fn get_http_status_code(&self) -> StatusCode {
         match self {
             Error::ApiResponseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Error::Banned { .. } => StatusCode::FORBIDDEN,
             Error::InternalError(err) => err.get_http_status_code(),
             Error::InvalidCode => StatusCode::FORBIDDEN,
             Error::NotWhitelisted => StatusCode::FORBIDDEN,
+            Error::ProviderDisabled(_) => StatusCode::FORBIDDEN,
         }
     }
 
     fn get_message(&self) -> String {
         match self {
             Error::ApiResponseError(_) => "provider error".into(),
+            Error::Banned { .. } => "banned".into(),
             Error::InvalidCode => "invalid provider code".into(),
             Error::InternalError(err) => err.get_message(),
             Error::NotWhitelisted => "not whitelisted".into(),
+            Error::ProviderDisabled(_) => "provider disabled".into(),
         }
     }
 }
 
-#[get("auth/ds")]
+#[get("ds")]
 pub async fn auth(
     request: Query<AuthQuery>,
-    steam: Data<Steam>,
+    steam: Data<Option<Steam>>,
     epic: Data<Epic>,
+    local_auth: Data<Option<LocalAuth>>,
     session_store: Data<SessionStore>,
     db: Data<Database>,
     gateway_url: Data<Option<GatewayUrl>>,
@@ -126,6 +142,10 @@ pub async fn auth(
 
     match &request.provider {
         Provider::Steam => {
+            let steam = steam
+                .get_ref()
+                .as_ref()
+                .ok_or(Error::ProviderDisabled(Provider::Steam))?;
             let user_id = steam
                 .verify_user_auth_ticket(&request.code)
                 .await
@@ -196,12 +216,123 @@ pub async fn auth(
             provider_id = account_id.account_id;
             display_name = account_info.display_name;
         }
+        Provider::Local => {
+            provider = Provider::Local;
+            provider_id = verify_local_key(
+                &local_auth,
+                &request.code,
+                &request.display_name,
+                &server_settings,
+                &whitelist,
+            )
+            .await?;
+            display_name = request.display_name.trim().to_owned();
+        }
+    }
+
+    start_session(
+        provider,
+        provider,
+        &provider_id,
+        &display_name,
+        &session_store,
+        &db,
+        &gateway_url,
+        &http_request,
+    )
+    .await
+}
+
+/// Authenticates a game client with the local auth key in the url instead of through the platform the game is running on.
+///
+/// This lets unmodified game clients use local auth by setting the server url to `http(s)://<host>/auth/local/<key>`.
+/// The provider and code sent by the game are ignored since they can't be verified without contacting Steam or Epic.
+#[get("local/{key}")]
+pub async fn auth_local(
+    key: Path<String>,
+    request: Query<AuthQuery>,
+    local_auth: Data<Option<LocalAuth>>,
+    session_store: Data<SessionStore>,
+    db: Data<Database>,
+    gateway_url: Data<Option<GatewayUrl>>,
+    http_request: HttpRequest,
+    server_settings: Data<ServerSettings>,
+    whitelist: Data<WhitelistSettings>,
+) -> Result<Json<AuthResponse>, Error> {
+    let provider_id = verify_local_key(
+        &local_auth,
+        &key,
+        &request.display_name,
+        &server_settings,
+        &whitelist,
+    )
+    .await?;
+
+    // Respond with the provider the game is running on since it's the only one it knows about
+    start_session(
+        Provider::Local,
+        request.provider,
+        &provider_id,
+        request.display_name.trim(),
+        &session_store,
+        &db,
+        &gateway_url,
+        &http_request,
+    )
+    .await
+}
+
+/// Verifies the key and returns the provider id of the player if they're allowed to log in.
+async fn verify_local_key(
+    local_auth: &Option<LocalAuth>,
+    key: &str,
+    display_name: &str,
+    server_settings: &ServerSettings,
+    whitelist: &WhitelistSettings,
+) -> Result<String, Error> {
+    let local_auth = local_auth
+        .as_ref()
+        .ok_or(Error::ProviderDisabled(Provider::Local))?;
+    let provider_id = local_auth.verify_key(key, display_name).map_err(|err| {
+        log::debug!("Local auth failed for {:?}: {}", display_name, err);
+        Error::InvalidCode
+    })?;
+
+    if !server_settings.read().await.public_server
+        && !whitelist.read().await.is_whitelisted(&provider_id)
+    {
+        log::info!("Blocked non whitelisted provider id: {}", provider_id);
+        return Err(Error::NotWhitelisted);
     }
 
+    Ok(provider_id)
+}
+
+/// Creates or updates the account for the authenticated player and starts a new session, replacing any previous session.
+///
+/// `response_provider` is the provider that is sent back to the game, which can differ from the account's provider when using local auth.
+#[allow(clippy::too_many_arguments)]
+async fn start_session(
+    provider: Provider,
+    response_provider: Provider,
+    provider_id: &str,
+    display_name: &str,
+    session_store: &SessionStore,
+    db: &Database,
+    gateway_url: &Option<GatewayUrl>,
+    http_request: &HttpRequest,
+) -> Result<Json<AuthResponse>, Error> {
     let login_date = Utc::now().naive_utc();
 
-    if let Some(token) = session_store.find_active_session_token(provider, &provider_id) {
-        session_store.delete_session(&token).await;
+    if let Some(token) = session_store
+        .find_active_session_token(provider, provider_id)
+        .await
+        .map_err(|err| Error::InternalError(err.into()))?
+    {
+        session_store
+            .delete_session(&token)
+            .await
+            .map_err(|err| Error::InternalError(err.into()))?;
     }
 
     // create account if one doesn't exist
@@ -210,13 +341,34 @@ pub async fn auth(
         .await
         .map_err(|err| Error::InternalError(err.into()))?;
     let accounts = db.accounts();
+    let existing_account = accounts.get_by_provider_id(provider, provider_id).await?;
+
+    if let Some(ban) = db
+        .bans()
+        .find_active_ban(
+            existing_account.as_ref().map(|account| account.id.as_str()),
+            provider,
+            provider_id,
+        )
+        .await?
+    {
+        log::info!(
+            "Blocked banned provider id: {} (ban {}, reason: {:?})",
+            provider_id,
+            ban.id,
+            ban.reason
+        );
+        return Err(Error::Banned {
+            expires_at: ban.expires_at,
+        });
+    }
 
     // find account for provider id, or create it if it doesn't exist yet, and also update display name
-    let account = match accounts.get_by_provider_id(provider, &provider_id).await? {
+    let account = match existing_account {
         Some(account) => {
             // update display name
             accounts
-                .update_display_name_and_last_login(&account.id, &display_name, &login_date)
+                .update_display_name_and_last_login(&account.id, display_name, &login_date)
                 .await?;
 
             account
@@ -231,25 +383,23 @@ pub async fn auth(
             );
 
             accounts
-                .create(provider, &provider_id, &display_name, &login_date)
+                .create(provider, provider_id, display_name, &login_date)
                 .await?
         }
     };
 
     // create session
-    let session = Session::new(
-        provider,
-        &provider_id,
-        &account.id,
-        generate_session_token(),
-    );
+    let session = Session::new(provider, provider_id, &account.id, generate_session_token());
     let token = session.get_token().to_owned();
-    session_store.save_session(session).await;
+    session_store
+        .save_session(session)
+        .await
+        .map_err(|err| Error::InternalError(err.into()))?;
 
-    let gateway_url = match gateway_url.as_ref() {
+    let gateway_url = match gateway_url {
         Some(gateway_url) => gateway_url.0.clone(),
         None => {
-            let url = infer_gateway_url(&http_request);
+            let url = infer_gateway_url(http_request);
 
             log::debug!("Inferred gateway url: {}", url);
 
@@ -261,7 +411,7 @@ pub async fn auth(
         user: UserInfo {
             id: account.id.clone(),
             display_name: account.display_name.clone(),
-            provider,
+            provider: response_provider,
         },
         session: SessionInfo {
             token,
@@ -5,11 +5,15 @@ use actix_web::{
     web::{Data, Json},
 };
 use diesel::ConnectionError;
-use parcel_common::api_types::requests::delete_object::DeleteObjectRequest;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use parcel_common::api_types::{
+    frontend::audit_log::AuditAction, requests::delete_object::DeleteObjectRequest,
+};
+use serde_json::json;
 
 use crate::{
     data::database::Database,
-    db::QueryError,
+    db::{models::audit_log::NewAuditLogEntry, QueryError},
     endpoints::{EmptyResponse, InternalError},
     response_error::{impl_response_error, CommonResponseError},
     session::Session,
@@ -90,17 +94,44 @@ pub async fn delete_object(
     }
 
     let conn = database.connect().await?;
-    let objects = conn.qpid_objects();
-
-    let object = objects.get_by_id(&request.object_id).await?;
+    let object = conn.qpid_objects().get_by_id(&request.object_id).await?;
 
     // Note that it's important we don't remove the object from the database.
     // This is because some objects can hold items (like postboxes) that might
     // hold items that other players have donated, or otherwise may rely on.
     if let Some(object) = object {
-        objects
-            .mark_deleted_for_account(&object.id, &session.account_id)
-            .await?;
+        let account_id = session.account_id.as_str();
+        let object = &object;
+
+        // The audit entry is recorded in the same transaction so that it always matches what was deleted
+        conn.transaction(|conn| {
+            async move {
+                conn.qpid_objects()
+                    .mark_deleted_for_account(&object.id, account_id)
+                    .await?;
+
+                conn.audit_log()
+                    .record(&NewAuditLogEntry {
+                        frontend_actor_id: None,
+                        game_actor_id: Some(account_id),
+                        action: AuditAction::DeleteObject,
+                        target_id: Some(&object.id),
+                        before: Some(json!({
+                            "creatorId": object.creator_id,
+                            "objectType": object.object_type,
+                            "isDeleted": object.is_deleted,
+                        })),
+                        // Only the creator's deletion hides the object from other players
+                        after: Some(json!({
+                            "isDeleted": object.is_deleted || object.creator_id == account_id,
+                        })),
+                    })
+                    .await
+            }
+            .scope_boxed()
+        })
+        .await?;
+
         Ok(EmptyResponse)
     } else {
         Err(Error::ObjectNotFound(request.object_id.clone()))
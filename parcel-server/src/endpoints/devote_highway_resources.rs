@@ -1,5 +1,7 @@
 use actix_web::{post, web::Data};
-use parcel_common::api_types::requests::devote_highway_resources::DevoteHighwayResourcesRequest;
+use parcel_common::api_types::{
+    rank::RankingCategory, requests::devote_highway_resources::DevoteHighwayResourcesRequest,
+};
 
 use crate::{
     data::database::Database,
@@ -20,5 +22,23 @@ async fn devote_highway_resources(
         .devote_resources(&session.account_id, &request.put_histories)
         .await?;
 
+    let total_put_num = request
+        .put_histories
+        .iter()
+        .map(|history| history.put_num.max(0) as i64)
+        .sum::<i64>();
+
+    if total_put_num > 0 {
+        conn.rankings()
+            .add_progress(
+                &session.account_id,
+                RankingCategory::HighwayResources,
+                total_put_num,
+                0,
+                0,
+            )
+            .await?;
+    }
+
     Ok(EmptyResponse)
 }
@@ -1,16 +1,24 @@
-use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
 
 use actix_web::{
     post,
     web::{Data, Json},
 };
+use chrono::Utc;
 use parcel_common::api_types::{
     mission::{MissionType, OnlineMissionType, ProgressState},
     requests::find_missions::{FindMissionsRequest, FindMissionsResponse},
     IntoDsApiType,
 };
 
-use crate::{data::database::Database, endpoints::InternalError, session::Session};
+use crate::{
+    data::database::{missions::MissionCandidate, Database},
+    endpoints::InternalError,
+    session::Session,
+};
+
+/// How often the order of creators without priority changes.
+const ROTATION_INTERVAL_SECS: i64 = 60 * 60;
 
 #[post("findMissions")]
 pub async fn find_missions(
@@ -38,42 +46,41 @@ pub async fn find_missions(
         OnlineMissionType::SharedLastStranding,
     ];
     const PROGRESS_STATES: &[ProgressState] = &[ProgressState::Available, ProgressState::Ready];
-    let missions = db_missions
-        .find_missions(
+
+    let limits = PotLimits::from_request(&request);
+    let candidates = db_missions
+        .find_mission_candidates(
             ONLINE_MISSION_TYPES,
             MISSION_TYPES,
             &[&session.account_id],
             PROGRESS_STATES,
-            Some(&request.qpid_ids),
+            &request.qpid_ids,
+            limits.missions.unwrap_or(request.limit as usize) as i64,
         )
         .await?;
 
+    let priority_ids = request.target_ids.as_deref().unwrap_or_default();
+    let rotation = (Utc::now().timestamp() / ROTATION_INTERVAL_SECS) as usize;
+    let selected_ids = select_missions(
+        candidates,
+        &request.qpid_ids,
+        priority_ids,
+        &limits,
+        request.limit as usize,
+        rotation,
+    );
+
     let mut missions = db_missions
-        .query_mission_data(missions)
+        .query_mission_data(db_missions.get_by_ids(&selected_ids).await?)
         .await?
         .into_iter()
-        .collect::<Vec<_>>();
-
-    if let Some(target_ids) = &request.target_ids {
-        missions.sort_unstable_by(|a, b| {
-            let contains_a = target_ids.contains(&a.mission.creator_id);
-            let contains_b = target_ids.contains(&b.mission.creator_id);
-
-            if contains_a && !contains_b {
-                Ordering::Less
-            } else if !contains_a && contains_b {
-                Ordering::Greater
-            } else {
-                Ordering::Equal
-            }
-        });
-    }
+        .map(|mission| (mission.mission.id.clone(), mission))
+        .collect::<HashMap<_, _>>();
 
-    // todo: limit number of missions based on request parameters: limit_pot_baggages, mission_limit_per_pot, private_limit_per_pot
-
-    let res_missions = missions
-        .into_iter()
-        .take(request.limit as usize)
+    // Return the missions in the order they were selected in
+    let res_missions = selected_ids
+        .iter()
+        .filter_map(|id| missions.remove(id))
         .map(|m| m.into_ds_api_type())
         .collect();
 
@@ -81,3 +88,156 @@ pub async fn find_missions(
         missions: res_missions,
     }))
 }
+
+/// Limits for each pot (qpid) from the request. None means no limit.
+struct PotLimits {
+    missions: Option<usize>,
+    private_missions: Option<usize>,
+    baggages: Option<usize>,
+}
+
+impl PotLimits {
+    fn from_request(request: &FindMissionsRequest) -> Self {
+        // The game sends 0 when there's no limit
+        let limit = |value: u32| (value > 0).then_some(value as usize);
+
+        Self {
+            missions: limit(request.mission_limit_per_pot),
+            private_missions: limit(request.private_limit_per_pot),
+            baggages: limit(request.limit_pot_baggages),
+        }
+    }
+}
+
+/// Picks which missions to return and returns their ids.
+///
+/// In every pot the missions from `priority_ids` are picked first, then the remaining creators take turns so that a
+/// single player can't fill a pot with their own missions. The order of the remaining creators changes with `rotation`.
+/// The pots also take turns until `limit` missions have been picked.
+fn select_missions(
+    candidates: Vec<MissionCandidate>,
+    qpid_ids: &[i32],
+    priority_ids: &[String],
+    limits: &PotLimits,
+    limit: usize,
+    rotation: usize,
+) -> Vec<String> {
+    let mut pots = HashMap::<i32, Vec<(String, VecDeque<MissionCandidate>)>>::new();
+
+    // Candidates are sorted by qpid and creator, so every creator's missions are next to each other
+    for candidate in candidates {
+        let creators = pots.entry(candidate.qpid_id).or_default();
+
+        match creators.last_mut() {
+            Some((creator_id, missions)) if *creator_id == candidate.creator_id => {
+                missions.push_back(candidate)
+            }
+            _ => creators.push((candidate.creator_id.clone(), VecDeque::from([candidate]))),
+        }
+    }
+
+    let mut selected_pots = Vec::with_capacity(qpid_ids.len());
+
+    for qpid_id in qpid_ids {
+        let Some(creators) = pots.remove(qpid_id) else {
+            continue;
+        };
+
+        let (mut prioritized, mut others): (Vec<_>, Vec<_>) = creators
+            .into_iter()
+            .partition(|(creator_id, _)| priority_ids.contains(creator_id));
+
+        prioritized
+            .sort_by_key(|(creator_id, _)| priority_ids.iter().position(|id| id == creator_id));
+
+        if !others.is_empty() {
+            let offset = rotation % others.len();
+            others.rotate_left(offset);
+        }
+
+        let mut pot = PotSelection::default();
+        pot.take_turns(prioritized, limits);
+        pot.take_turns(others, limits);
+        selected_pots.push(VecDeque::from(pot.missions));
+    }
+
+    let mut result = Vec::new();
+
+    while result.len() < limit && selected_pots.iter().any(|pot| !pot.is_empty()) {
+        for pot in &mut selected_pots {
+            if result.len() >= limit {
+                break;
+            }
+
+            if let Some(id) = pot.pop_front() {
+                result.push(id);
+            }
+        }
+    }
+
+    result
+}
+
+#[derive(Default)]
+struct PotSelection {
+    missions: Vec<String>,
+    num_private: usize,
+    num_baggages: usize,
+}
+
+impl PotSelection {
+    /// Lets the creators pick one mission each at a time until they run out of missions or the pot is full.
+    fn take_turns(
+        &mut self,
+        mut creators: Vec<(String, VecDeque<MissionCandidate>)>,
+        limits: &PotLimits,
+    ) {
+        while !creators.is_empty() {
+            creators.retain_mut(|(_, missions)| {
+                if limits
+                    .missions
+                    .is_some_and(|max| self.missions.len() >= max)
+                {
+                    return false;
+                }
+
+                while let Some(mission) = missions.pop_front() {
+                    if self.try_add(mission, limits) {
+                        break;
+                    }
+                }
+
+                !missions.is_empty()
+            });
+        }
+    }
+
+    fn try_add(&mut self, mission: MissionCandidate, limits: &PotLimits) -> bool {
+        let is_private = mission.online_mission_type == OnlineMissionType::Private;
+        let num_baggages = mission.num_baggages as usize;
+
+        if is_private
+            && limits
+                .private_missions
+                .is_some_and(|max| self.num_private >= max)
+        {
+            return false;
+        }
+
+        if limits
+            .baggages
+            .is_some_and(|max| self.num_baggages + num_baggages > max)
+        {
+            return false;
+        }
+
+        if is_private {
+            self.num_private += 1;
+        }
+
+        self.num_baggages += num_baggages;
+        self.missions.push(mission.id);
+
+        true
+    }
+}
@@ -8,7 +8,11 @@ use parcel_common::api_types::{
     IntoDsApiType, TryIntoDsApiType,
 };
 
-use crate::{data::database::Database, endpoints::InternalError, session::Session};
+use crate::{
+    data::database::{accounts::PrioritizedCreators, qpid_objects::ObjectSelection, Database},
+    endpoints::InternalError,
+    session::Session,
+};
 
 #[post("findQpidObjects")]
 pub async fn find_qpid_objects(
@@ -54,13 +58,29 @@ pub async fn find_qpid_objects(
         priority_ids = Some(ids);
     }
 
-    if let Some(_object) = request.object {
+    let contracts = conn
+        .accounts()
+        .get_strand_contracts(&session.account_id)
+        .await?;
+    let contract_ids = contracts
+        .iter()
+        .map(|contract| contract.contract_account_id.as_str())
+        .collect::<Vec<_>>();
+    let creators = PrioritizedCreators {
+        priority_ids: priority_ids.as_deref().unwrap_or_default(),
+        contract_ids: &contract_ids,
+    };
+
+    if let Some(object) = request.object {
         let found_objects = objects
             .find_objects(
                 &[area_hash],
                 &[qpid_id],
-                priority_ids.as_deref(),
-                10000,
+                &ObjectSelection {
+                    creators,
+                    type_limits: &object.counts,
+                    limit: 10000,
+                },
                 &[&session.account_id],
             )
             .await?;
@@ -85,11 +105,7 @@ pub async fn find_qpid_objects(
         let roads = conn.roads();
 
         let found_roads = roads
-            .find_roads(
-                &road_request,
-                &[&session.account_id],
-                priority_ids.as_deref(),
-            )
+            .find_roads(&road_request, &[&session.account_id], &creators)
             .await?
             .into_iter()
             .map(|r| r.into_ds_api_type())
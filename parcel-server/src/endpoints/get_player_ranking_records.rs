@@ -1,15 +1,39 @@
-use actix_web::{post, web::Json, Responder};
+use actix_web::{
+    post,
+    web::{Data, Json},
+};
 use parcel_common::api_types::requests::get_player_ranking_records::{
     GetPlayerRankingRecordsRequest, GetPlayerRankingRecordsResponse,
 };
 
+use crate::{data::database::Database, endpoints::InternalError, session::Session};
+
 #[post("getPlayerRankingRecords")]
 pub async fn get_player_ranking_records(
     request: Json<GetPlayerRankingRecordsRequest>,
-) -> impl Responder {
-    Json(GetPlayerRankingRecordsResponse {
-        update_time: 0,
-        rewards: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-        records: Vec::new(),
-    })
+    session: Session,
+    database: Data<Database>,
+) -> Result<Json<GetPlayerRankingRecordsResponse>, InternalError> {
+    let conn = database.connect().await?;
+    let rankings = conn.rankings();
+
+    // The season id is the basement index that was sent to the client in getRankingSchedules, which is the schedule id
+    let schedule_id = request.season_id as i32;
+    let records = match rankings.get_schedule(schedule_id).await? {
+        Some(schedule) => rankings
+            .get_account_records(&session.account_id, &[schedule_id])
+            .await?
+            .into_iter()
+            .map(|record| record.into_ds_api_record(schedule.category))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let rewards = rankings.get_reward_counts(&session.account_id).await?;
+
+    Ok(Json(GetPlayerRankingRecordsResponse {
+        update_time: chrono::Utc::now().timestamp_millis(),
+        rewards,
+        records,
+    }))
 }
@@ -1,12 +1,30 @@
-use actix_web::{post, web::Json, Responder};
-use parcel_common::api_types::requests::get_ranking_schedules::{
-    GetRankingSchedulesRequest, GetRankingSchedulesResponse,
+use actix_web::{
+    post,
+    web::{Data, Json},
 };
+use parcel_common::api_types::{
+    requests::get_ranking_schedules::{GetRankingSchedulesRequest, GetRankingSchedulesResponse},
+    IntoDsApiType,
+};
+
+use crate::{data::database::Database, endpoints::InternalError};
 
 #[post("getRankingSchedules")]
-pub async fn get_ranking_schedules(request: Json<GetRankingSchedulesRequest>) -> impl Responder {
-    Json(GetRankingSchedulesResponse {
-        updated_time: 0,
-        schedules: Vec::new(),
-    })
+pub async fn get_ranking_schedules(
+    request: Json<GetRankingSchedulesRequest>,
+    database: Data<Database>,
+) -> Result<Json<GetRankingSchedulesResponse>, InternalError> {
+    let conn = database.connect().await?;
+    let schedules = conn
+        .rankings()
+        .get_started_schedules(request.request_num.max(0) as i64)
+        .await?
+        .into_iter()
+        .map(|schedule| schedule.into_ds_api_type())
+        .collect();
+
+    Ok(Json(GetRankingSchedulesResponse {
+        updated_time: chrono::Utc::now().timestamp_millis(),
+        schedules,
+    }))
 }
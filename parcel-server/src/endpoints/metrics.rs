@@ -0,0 +1,35 @@
+use actix_http::header;
+use actix_web::{get, web::Data, HttpRequest, HttpResponse};
+
+use crate::{data::session_store::SessionStore, endpoints::InternalError};
+
+/// The token that scrapers need to send as a bearer token to read the metrics.
+#[derive(Debug, Clone)]
+pub struct MetricsToken(pub String);
+
+/// Exports the server metrics in the Prometheus text format.
+///
+/// If a metrics token is configured the request needs to be authorized with it, otherwise anyone can read the metrics.
+#[get("metrics")]
+pub async fn metrics(
+    request: HttpRequest,
+    metrics_token: Data<Option<MetricsToken>>,
+    session_store: Data<SessionStore>,
+) -> Result<HttpResponse, InternalError> {
+    if let Some(MetricsToken(token)) = metrics_token.as_ref() {
+        let authorization = request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok());
+
+        if authorization != Some(&format!("Bearer {token}")) {
+            return Ok(HttpResponse::Unauthorized().finish());
+        }
+    }
+
+    let active_sessions = session_store.count_sessions().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(crate::metrics::render(active_sessions)))
+}
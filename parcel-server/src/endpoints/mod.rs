@@ -20,6 +20,7 @@ mod get_road_data;
 mod get_version;
 mod get_wasted_baggages;
 mod lookup;
+pub mod metrics;
 mod put_wasted_baggages;
 mod reverse_lookup;
 mod send_like;
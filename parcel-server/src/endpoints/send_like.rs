@@ -6,7 +6,7 @@ use actix_web::{
     web::{Data, Json},
 };
 use diesel::ConnectionError;
-use parcel_common::api_types::requests::send_like::SendLikeRequest;
+use parcel_common::api_types::{rank::RankingCategory, requests::send_like::SendLikeRequest};
 
 use crate::{
     data::database::{likes::LikeTarget, Database},
@@ -141,6 +141,16 @@ pub async fn send_like(
         )
         .await?;
 
+    conn.rankings()
+        .add_progress(
+            &request.account_id,
+            RankingCategory::Likes,
+            (request.likes_auto + request.likes_manual) as i64,
+            0,
+            0,
+        )
+        .await?;
+
     accounts
         .add_relationship_history(
             &session.account_id,
@@ -3,6 +3,8 @@ use actix_web::{
     web::{Data, Json},
 };
 use parcel_common::api_types::{
+    mission::ProgressState,
+    rank::RankingCategory,
     requests::set_mission_progress::{SetMissionProgressRequest, SetMissionProgressResponse},
     IntoDsApiType,
 };
@@ -45,6 +47,33 @@ pub async fn set_mission_progress(
             )
             .await?;
 
+        // Only count the transition into a finished state so that repeated progress updates aren't counted twice
+        if mission.progress_state != request.progress_state {
+            let rankings = conn.rankings();
+
+            match request.progress_state {
+                ProgressState::Success => {
+                    let num_baggages = request.baggages.as_ref().map(|b| b.len()).unwrap_or(0);
+
+                    rankings
+                        .add_progress(
+                            &session.account_id,
+                            RankingCategory::Deliveries,
+                            num_baggages.max(1) as i64,
+                            1,
+                            1,
+                        )
+                        .await?;
+                }
+                ProgressState::Failed => {
+                    rankings
+                        .add_progress(&session.account_id, RankingCategory::Deliveries, 0, 1, 0)
+                        .await?;
+                }
+                _ => {}
+            }
+        }
+
         if session.account_id != mission.creator_id {
             accounts
                 .add_relationship_history(
@@ -12,20 +12,30 @@ use parcel_common::api_types::frontend::{
         ListAccountsResponse, ListAccountsType, LocalAccount, ProviderConnection,
         ResetPasswordRequest, SetAccountPermissionsRequest,
     },
+    audit_log::AuditAction,
     auth::FrontendPermissions,
 };
 use serde::Deserialize;
+use serde_json::json;
 
 use crate::{
-    data::{database::Database, hash_secret::HashSecret},
-    db::models::frontend_account::{
-        ChangeFrontendAccount, NewAccountProviderConnection, NewFrontendAccount,
+    data::{
+        database::Database,
+        hash_secret::HashSecret,
+        shared_state::{SharedState, SharedStateEvent},
+    },
+    db::models::{
+        audit_log::{to_audit_value, NewAuditLogEntry},
+        frontend_account::{
+            ChangeFrontendAccount, NewAccountProviderConnection, NewFrontendAccount,
+        },
     },
     endpoints::{EmptyResponse, ValidatedJson},
     frontend::{
         error::ApiError,
         jwt_session::{JwtSession, SessionBlacklistCache, SessionPermissionsCache},
         result::{ApiResponse, ApiResult},
+        shared_caches,
     },
 };
 
@@ -170,6 +180,7 @@ pub async fn set_account_permissions(
     request: Json<SetAccountPermissionsRequest>,
     database: Data<Database>,
     session_permissions_cache: Data<SessionPermissionsCache>,
+    shared_state: Data<dyn SharedState>,
 ) -> ApiResult<Vec<FrontendPermissions>> {
     // Check that we have permission
     if !session.has_permissions(FrontendPermissions::ManageAccounts) {
@@ -191,6 +202,11 @@ pub async fn set_account_permissions(
 
     let conn = database.connect().await?;
     let accounts = conn.frontend_accounts();
+    let old_permissions = accounts
+        .get_by_id(account_id)
+        .await?
+        .map(|account| FlagSet::<FrontendPermissions>::new_truncated(account.permissions))
+        .ok_or(ApiError::NotFound)?;
 
     let mut new_permissions = FlagSet::default();
 
@@ -205,6 +221,20 @@ pub async fn set_account_permissions(
     session_permissions_cache
         .insert(account_id, new_permissions)
         .await;
+    shared_state
+        .publish(&SharedStateEvent::FrontendPermissionsChanged { account_id })
+        .await?;
+
+    conn.audit_log()
+        .record(&NewAuditLogEntry {
+            frontend_actor_id: Some(session.account_id),
+            game_actor_id: None,
+            action: AuditAction::SetAccountPermissions,
+            target_id: Some(&account_id.to_string()),
+            before: to_audit_value(&old_permissions.into_iter().collect::<Vec<_>>()),
+            after: to_audit_value(&new_permissions.into_iter().collect::<Vec<_>>()),
+        })
+        .await?;
 
     ApiResponse::ok(new_permissions.into_iter().collect())
 }
@@ -251,6 +281,17 @@ pub async fn create_credentials(
                 )
                 .await?;
 
+            conn.audit_log()
+                .record(&NewAuditLogEntry {
+                    frontend_actor_id: Some(session.account_id),
+                    game_actor_id: None,
+                    action: AuditAction::CreateCredentials,
+                    target_id: Some(&account_id.to_string()),
+                    before: None,
+                    after: Some(json!({ "username": credentials.username })),
+                })
+                .await?;
+
             ApiResponse::ok(LocalAccount {
                 username: credentials.username,
             })
@@ -266,6 +307,7 @@ pub async fn reset_password(
     database: Data<Database>,
     hash_secret: Data<HashSecret>,
     session_blacklist: Data<SessionBlacklistCache>,
+    shared_state: Data<dyn SharedState>,
 ) -> ApiResult<EmptyResponse> {
     let account_id = params.into_inner();
 
@@ -326,12 +368,26 @@ pub async fn reset_password(
 
             if let Some(sessions) = sessions {
                 for session in sessions {
+                    let expires_at = session.expires_at.and_utc();
                     session_blacklist
-                        .insert(session.token, session.expires_at.and_utc())
+                        .insert(session.token.clone(), expires_at)
                         .await;
+                    shared_caches::revoke_session(&**shared_state, &session.token, expires_at)
+                        .await?;
                 }
             }
 
+            conn.audit_log()
+                .record(&NewAuditLogEntry {
+                    frontend_actor_id: Some(session.account_id),
+                    game_actor_id: None,
+                    action: AuditAction::ResetPassword,
+                    target_id: Some(&account_id.to_string()),
+                    before: None,
+                    after: Some(json!({ "logoutSessions": request.logout_sessions })),
+                })
+                .await?;
+
             ApiResponse::ok(EmptyResponse)
         }
         None => Err(ApiError::NotFound),
@@ -351,6 +407,16 @@ pub async fn create_frontend_account(
     }
 
     let conn = database.connect().await?;
+    let request = request.into_inner();
+    let audit_details = match &request {
+        CreateFrontendAccountRequest::WithCredentials(credentials) => {
+            json!({ "username": credentials.username })
+        }
+        CreateFrontendAccountRequest::WithProvider {
+            provider,
+            provider_id,
+        } => json!({ "provider": provider, "providerId": provider_id }),
+    };
 
     let account = conn
         .transaction(|conn| {
@@ -364,7 +430,7 @@ pub async fn create_frontend_account(
                     })
                     .await?;
 
-                match request.into_inner() {
+                match request {
                     CreateFrontendAccountRequest::WithCredentials(CreateCredentialsRequest {
                         username,
                         password,
@@ -415,5 +481,16 @@ pub async fn create_frontend_account(
         })
         .await?;
 
+    conn.audit_log()
+        .record(&NewAuditLogEntry {
+            frontend_actor_id: Some(session.account_id),
+            game_actor_id: None,
+            action: AuditAction::CreateFrontendAccount,
+            target_id: Some(&account.id.to_string()),
+            before: None,
+            after: Some(audit_details),
+        })
+        .await?;
+
     ApiResponse::ok(account.id)
 }
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use actix_web::{
+    get,
+    web::{Data, Query},
+};
+use parcel_common::api_types::frontend::{
+    accounts::GameAccountSummary,
+    audit_log::{AuditAction, AuditLogEntry, ListAuditLogResponse},
+    auth::FrontendPermissions,
+};
+use serde::Deserialize;
+
+use super::parse_optional_date;
+use crate::{
+    data::database::{audit_log::AuditLogFilter, Database},
+    frontend::{
+        error::ApiError,
+        jwt_session::JwtSession,
+        result::{ApiResponse, ApiResult},
+    },
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 500;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAuditLogQuery {
+    action: Option<AuditAction>,
+    frontend_actor_id: Option<i64>,
+    game_actor_id: Option<String>,
+    target_id: Option<String>,
+    /// Only include entries created at or after this date, in RFC 3339 format
+    from: Option<String>,
+    /// Only include entries created before this date, in RFC 3339 format
+    to: Option<String>,
+    #[serde(default)]
+    page: i64,
+    page_size: Option<i64>,
+}
+
+#[get("auditLog")]
+pub async fn list_audit_log(
+    session: JwtSession,
+    database: Data<Database>,
+    query: Query<ListAuditLogQuery>,
+) -> ApiResult<ListAuditLogResponse> {
+    if !session.has_permissions(FrontendPermissions::ManageServerSettings) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let page_size = query
+        .page_size
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let page = query.page.max(0);
+
+    let filter = AuditLogFilter {
+        action: query.action,
+        frontend_actor_id: query.frontend_actor_id,
+        game_actor_id: query.game_actor_id.as_deref(),
+        target_id: query.target_id.as_deref(),
+        from: parse_optional_date(query.from.as_deref(), "Invalid from date")?,
+        to: parse_optional_date(query.to.as_deref(), "Invalid to date")?,
+    };
+
+    let conn = database.connect().await?;
+    let (entries, total) = conn
+        .audit_log()
+        .list(&filter, page * page_size, page_size)
+        .await?;
+    let account_names = conn
+        .accounts()
+        .get_by_ids(
+            &entries
+                .iter()
+                .filter_map(|entry| entry.game_actor_id.as_ref())
+                .collect::<Vec<_>>(),
+        )
+        .await?
+        .into_iter()
+        .map(|account| (account.id, account.display_name))
+        .collect::<HashMap<_, _>>();
+
+    ApiResponse::ok(ListAuditLogResponse {
+        entries: entries
+            .into_iter()
+            .map(|entry| AuditLogEntry {
+                id: entry.id,
+                created_at: entry.created_at.and_utc().to_rfc3339(),
+                frontend_actor_id: entry.frontend_actor_id,
+                game_actor: entry.game_actor_id.map(|id| GameAccountSummary {
+                    name: account_names.get(&id).cloned().unwrap_or_default(),
+                    id,
+                }),
+                action: entry.action,
+                target_id: entry.target_id,
+                before: entry.before.map(|value| value.to_string()),
+                after: entry.after.map(|value| value.to_string()),
+            })
+            .collect(),
+        total,
+    })
+}
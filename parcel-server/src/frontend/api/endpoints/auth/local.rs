@@ -24,7 +24,7 @@ pub async fn auth_local(
     database: Data<Database>,
     jwt_secret: Data<JwtSecret>,
     hash_secret: Data<HashSecret>,
-    steam: Data<Steam>,
+    steam: Data<Option<Steam>>,
 ) -> ApiResult<AuthAccountInfo> {
     let conn = database.connect().await?;
     let accounts = conn.frontend_accounts();
@@ -60,23 +60,26 @@ pub async fn auth_local(
 
                 match provider_connection {
                     Some(provider_connection) => match provider_connection.provider {
-                        Provider::Steam => {
-                            let steam_id = provider_connection
-                                .provider_id
-                                .parse::<u64>()
-                                .map_err(anyhow::Error::msg)?;
-                            let user_summary = steam
-                                .get_player_summaries(&[&steam_id])
-                                .await?
-                                .into_iter()
-                                .next();
+                        Provider::Steam => match steam.get_ref() {
+                            Some(steam) => {
+                                let steam_id = provider_connection
+                                    .provider_id
+                                    .parse::<u64>()
+                                    .map_err(anyhow::Error::msg)?;
+                                let user_summary = steam
+                                    .get_player_summaries(&[&steam_id])
+                                    .await?
+                                    .into_iter()
+                                    .next();
 
-                            match user_summary {
-                                Some((_, summary)) => Some(summary.avatar_full),
-                                None => None,
+                                match user_summary {
+                                    Some((_, summary)) => Some(summary.avatar_full),
+                                    None => None,
+                                }
                             }
-                        }
-                        Provider::Epic => None,
+                            None => None,
+                        },
+                        Provider::Epic | Provider::Local => None,
                     },
                     None => None,
                 }
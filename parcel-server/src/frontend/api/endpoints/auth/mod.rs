@@ -23,6 +23,8 @@ use crate::{
         database::Database,
         jwt_secret::JwtSecret,
         memory_cache::{MemoryCache, PersistentCache},
+        platforms::steam::Steam,
+        shared_state::SharedState,
     },
     db::models::frontend_account::FrontendAccount,
     endpoints::EmptyResponse,
@@ -30,6 +32,7 @@ use crate::{
         error::ApiError,
         jwt_session::{JwtSession, SessionBlacklistCache, BLACKLIST_CACHE_PATH},
         result::{ApiResponse, ApiResult},
+        shared_caches,
     },
 };
 
@@ -39,9 +42,16 @@ pub type FrontendAuthCache = MemoryCache<String, CheckAuthResponse>;
 pub async fn auth(
     request: Json<AuthRequest>,
     http_request: HttpRequest,
+    steam: Data<Option<Steam>>,
 ) -> ApiResult<InitAuthResponse> {
     match request.provider {
         Provider::Steam => {
+            if steam.is_none() {
+                return Err(ApiError::Unprocessable(anyhow::anyhow!(
+                    "Steam login is disabled on this server"
+                )));
+            }
+
             let redirector = Redirector::new(
                 &get_site_url(&http_request),
                 "/frontend/api/auth/callback/steam",
@@ -57,6 +67,9 @@ pub async fn auth(
         Provider::Epic => {
             Err(anyhow::anyhow!("Epic auth is not implemented, use a local account").into())
         }
+        Provider::Local => Err(ApiError::Unprocessable(anyhow::anyhow!(
+            "Local auth is only used by the game, use a local account"
+        ))),
     }
 }
 
@@ -64,7 +77,18 @@ pub async fn auth(
 pub async fn check_auth(
     request: Json<CheckAuthRequest>,
     auth_cache: Data<FrontendAuthCache>,
+    shared_state: Data<dyn SharedState>,
 ) -> ApiResult<CheckAuthResponse> {
+    // The response is removed from the shared state so that the session token in it isn't stored any longer than needed,
+    // and it's cached on this instance in case the frontend checks it again
+    if let Some(auth_response) =
+        shared_caches::take_auth_response(&**shared_state, &request.callback_token).await?
+    {
+        auth_cache
+            .insert(request.callback_token.clone(), auth_response)
+            .await;
+    }
+
     let auth_response = auth_cache.get(&request.callback_token);
 
     if let Some(auth_response) = auth_response {
@@ -81,6 +105,7 @@ pub async fn logout(
     session: JwtSession,
     session_blacklist_cache: Data<SessionBlacklistCache>,
     database: Data<Database>,
+    shared_state: Data<dyn SharedState>,
 ) -> ApiResult<EmptyResponse> {
     database
         .connect()
@@ -93,12 +118,13 @@ pub async fn logout(
         .ok_or_else(|| anyhow::anyhow!("Invalid expiry time"))?
         .and_utc();
     session_blacklist_cache
-        .insert(session.token, expiry_time)
+        .insert(session.token.clone(), expiry_time)
         .await;
     session_blacklist_cache
         .save_to_file(Path::new(BLACKLIST_CACHE_PATH))
         .await
         .map_err(|err| ApiError::Internal(anyhow::anyhow!(err)))?;
+    shared_caches::revoke_session(&**shared_state, &session.token, expiry_time).await?;
 
     ApiResponse::ok(EmptyResponse)
 }
@@ -13,9 +13,12 @@ use parcel_common::api_types::{
 use steam_auth::Verifier;
 
 use crate::{
-    data::{database::Database, jwt_secret::JwtSecret, platforms::steam::Steam},
+    data::{
+        database::Database, jwt_secret::JwtSecret, platforms::steam::Steam,
+        shared_state::SharedState,
+    },
     db::models::frontend_account::{NewAccountProviderConnection, NewFrontendAccount},
-    frontend::{api::endpoints::auth::FrontendAuthCache, error::ApiError},
+    frontend::{api::endpoints::auth::FrontendAuthCache, error::ApiError, shared_caches},
     ServerSettings,
 };
 
@@ -25,11 +28,16 @@ use super::generate_response_token;
 pub async fn steam_callback(
     request: HttpRequest,
     database: Data<Database>,
-    steam: Data<Steam>,
+    steam: Data<Option<Steam>>,
     auth_cache: Data<FrontendAuthCache>,
     jwt_secret: Data<JwtSecret>,
     server_settings: Data<ServerSettings>,
+    shared_state: Data<dyn SharedState>,
 ) -> Result<Redirect, ApiError> {
+    let steam = steam.get_ref().as_ref().ok_or_else(|| {
+        ApiError::Unprocessable(anyhow::anyhow!("Steam login is disabled on this server"))
+    })?;
+
     let (request, verifier) =
         Verifier::from_querystring(&request.query_string()).map_err(anyhow::Error::msg)?;
 
@@ -61,7 +69,7 @@ pub async fn steam_callback(
 
             match account {
                 Some(account) => {
-                    create_auth_response(&jwt_secret, &steam, &accounts, account, steam_id).await?
+                    create_auth_response(&jwt_secret, steam, &accounts, account, steam_id).await?
                 }
                 None => {
                     if !server_settings.read().await.allow_frontend_login {
@@ -117,7 +125,7 @@ pub async fn steam_callback(
                                 error: "Game account not found, log in to the game server and try again".into(),
                             },
                             Some(account) => {
-                                create_auth_response(&jwt_secret, &steam, &accounts, account, steam_id).await?
+                                create_auth_response(&jwt_secret, steam, &accounts, account, steam_id).await?
                             }
                         }
                     }
@@ -133,7 +141,12 @@ pub async fn steam_callback(
         }
     };
 
-    auth_cache.insert(response_token.clone(), response).await;
+    auth_cache
+        .insert(response_token.clone(), response.clone())
+        .await;
+
+    // The frontend could check the result on another server instance
+    shared_caches::store_auth_response(&**shared_state, &response_token, &response).await?;
 
     Ok(Redirect::to(format!(
         "/frontend/login?callback_token={}",
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use actix_web::{
     get,
@@ -11,7 +11,7 @@ use parcel_common::api_types::{
         accounts::GameAccountSummary,
         baggages::{
             Baggage, ListLostCargoResponse, ListSharedCargoResponse, ListWastedCargoResponse,
-            LostCargoListItem, SharedCargoListItem, WastedCargoListItem,
+            LostCargoListItem, MissingLostCargoListItem, SharedCargoListItem, WastedCargoListItem,
         },
     },
     mission::{MissionType, OnlineMissionType, ProgressState},
@@ -150,6 +150,8 @@ pub async fn list_lost_cargo(
 
     let data_missions = missions.query_mission_data(data_missions).await?;
     let mut baggages = Vec::new();
+    // The destination and name hash of every lost cargo in the world
+    let mut found_baggages = HashSet::new();
 
     for mission in data_missions {
         let creator = accounts
@@ -158,10 +160,11 @@ pub async fn list_lost_cargo(
             .unwrap_or_else(|| "Deleted account".into());
 
         for baggage in mission.baggages {
-            let baggage_data = game_data.baggages.get(&(baggage.name_hash as u32));
+            let name_hash = baggage.name_hash as u32;
+            let baggage_data = game_data.baggages.get(&name_hash);
 
             let mut item_name = game_data
-                .baggage_name(baggage.name_hash as u32, Language::English)
+                .baggage_name(name_hash, Language::English)
                 .map(|n| n.to_owned())
                 .unwrap_or_else(|| baggage.name_hash.to_string());
 
@@ -183,18 +186,68 @@ pub async fn list_lost_cargo(
                 .map(|n| n.to_owned())
                 .unwrap_or_else(|| mission.mission.qpid_end_location.to_string());
 
+            let expected_destination_names = game_data
+                .lost_baggage_destinations(name_hash)
+                .into_iter()
+                .map(|qpid_id| {
+                    game_data
+                        .qpid_area_name(qpid_id, Language::English)
+                        .map(|n| n.to_owned())
+                        .unwrap_or_else(|| qpid_id.to_string())
+                })
+                .collect::<Vec<_>>();
+            let expected_destination = (!expected_destination_names.is_empty())
+                .then(|| expected_destination_names.join(", "));
+
+            found_baggages.insert((mission.mission.qpid_end_location, name_hash));
+
             baggages.push(LostCargoListItem {
                 name: item_name,
                 category,
                 amount: baggage.amount,
                 location: location_name,
                 end_location: target_location_name,
+                expected_destination,
                 creator: creator.clone(),
             });
         }
     }
 
-    ApiResponse::ok(ListLostCargoResponse { baggages })
+    let mut destinations = game_data.lost_baggages.iter().collect::<Vec<_>>();
+    destinations.sort_unstable_by_key(|(qpid_id, _)| **qpid_id);
+    let mut missing = Vec::new();
+
+    for (qpid_id, name_hashes) in destinations {
+        let destination_name = game_data
+            .qpid_area_name(*qpid_id, Language::English)
+            .map(|n| n.to_owned())
+            .unwrap_or_else(|| qpid_id.to_string());
+
+        for name_hash in name_hashes {
+            if found_baggages.contains(&(*qpid_id, *name_hash)) {
+                continue;
+            }
+
+            let item_name = game_data
+                .baggage_name(*name_hash, Language::English)
+                .map(|n| n.replace("{0}", "1"))
+                .unwrap_or_else(|| name_hash.to_string());
+
+            let category = game_data
+                .baggages
+                .get(name_hash)
+                .map(|b| format!("{:?}", b.baggage_metadata.type_contents))
+                .unwrap_or_else(|| "Unknown".into());
+
+            missing.push(MissingLostCargoListItem {
+                name: item_name,
+                category,
+                destination: destination_name.clone(),
+            });
+        }
+    }
+
+    ApiResponse::ok(ListLostCargoResponse { baggages, missing })
 }
 
 #[get("baggages/list/wastedCargo")]
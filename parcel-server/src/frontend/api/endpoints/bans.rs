@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use actix_web::{
+    delete, get, post,
+    web::{Data, Json, Path, Query},
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use parcel_common::api_types::frontend::{
+    accounts::GameAccountSummary,
+    audit_log::AuditAction,
+    auth::FrontendPermissions,
+    bans::{BanListItem, BanTarget, CreateBanRequest},
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    data::{database::Database, session_store::SessionStore},
+    db::models::{
+        account::Account,
+        audit_log::NewAuditLogEntry,
+        ban::{Ban, NewBan},
+    },
+    endpoints::EmptyResponse,
+    frontend::{
+        error::ApiError,
+        jwt_session::JwtSession,
+        result::{ApiResponse, ApiResult},
+    },
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListBansQuery {
+    #[serde(default)]
+    include_inactive: bool,
+}
+
+#[get("bans")]
+pub async fn list_bans(
+    session: JwtSession,
+    database: Data<Database>,
+    query: Query<ListBansQuery>,
+) -> ApiResult<Vec<BanListItem>> {
+    if !session.has_permissions(FrontendPermissions::ManageAccounts) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let conn = database.connect().await?;
+    let bans = conn.bans().list(query.include_inactive).await?;
+    let accounts = conn
+        .accounts()
+        .get_by_ids(
+            &bans
+                .iter()
+                .filter_map(|ban| ban.account_id.as_ref())
+                .collect::<Vec<_>>(),
+        )
+        .await?
+        .into_iter()
+        .map(|account| (account.id.clone(), account))
+        .collect::<HashMap<_, _>>();
+
+    let now = Utc::now().naive_utc();
+
+    ApiResponse::ok(
+        bans.into_iter()
+            .map(|ban| {
+                let account = ban.account_id.as_ref().and_then(|id| accounts.get(id));
+                into_list_item(ban, account, &now)
+            })
+            .collect(),
+    )
+}
+
+/// Bans an account or provider id and ends the player's current game session, if they have one.
+#[post("bans")]
+pub async fn create_ban(
+    session: JwtSession,
+    database: Data<Database>,
+    session_store: Data<SessionStore>,
+    request: Json<CreateBanRequest>,
+) -> ApiResult<BanListItem> {
+    if !session.has_permissions(FrontendPermissions::ManageAccounts) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let reason = request.reason.trim();
+
+    if reason.is_empty() {
+        return Err(ApiError::Unprocessable(anyhow::anyhow!(
+            "A reason is required"
+        )));
+    }
+
+    let now = Utc::now().naive_utc();
+    let expires_at = match &request.expires_at {
+        Some(expires_at) => {
+            let expires_at = DateTime::parse_from_rfc3339(expires_at)
+                .map_err(|_| ApiError::Unprocessable(anyhow::anyhow!("Invalid expiry date")))?
+                .naive_utc();
+
+            if expires_at <= now {
+                return Err(ApiError::Unprocessable(anyhow::anyhow!(
+                    "The expiry date must be in the future"
+                )));
+            }
+
+            Some(expires_at)
+        }
+        None => None,
+    };
+
+    let conn = database.connect().await?;
+    let accounts = conn.accounts();
+
+    let (account, provider, provider_id) = match &request.target {
+        BanTarget::Account { account_id } => {
+            let account = accounts
+                .get_by_ids(&[account_id])
+                .await?
+                .into_iter()
+                .next()
+                .ok_or(ApiError::NotFound)?;
+            let provider = account.provider;
+            let provider_id = account.provider_id.clone();
+
+            (Some(account), provider, provider_id)
+        }
+        BanTarget::Provider {
+            provider,
+            provider_id,
+        } => {
+            let provider_id = provider_id.trim().to_owned();
+
+            if provider_id.is_empty() {
+                return Err(ApiError::Unprocessable(anyhow::anyhow!(
+                    "The provider id is required"
+                )));
+            }
+
+            let account = accounts.get_by_provider_id(*provider, &provider_id).await?;
+
+            (account, *provider, provider_id)
+        }
+    };
+
+    let frontend_account_id = session.account_id;
+    let account_id = account.as_ref().map(|account| account.id.as_str());
+    let provider_id = provider_id.as_str();
+    let expires_at = expires_at.as_ref();
+    let now = &now;
+    let ban = conn
+        .transaction(|conn| {
+            async move {
+                let ban = conn
+                    .bans()
+                    .create(&NewBan {
+                        account_id,
+                        provider,
+                        provider_id,
+                        reason,
+                        issued_by: Some(frontend_account_id),
+                        created_at: now,
+                        expires_at,
+                    })
+                    .await?;
+
+                conn.audit_log()
+                    .record(&NewAuditLogEntry {
+                        frontend_actor_id: Some(frontend_account_id),
+                        game_actor_id: None,
+                        action: AuditAction::CreateBan,
+                        target_id: Some(&ban.id.to_string()),
+                        before: None,
+                        after: Some(json!({
+                            "accountId": account_id,
+                            "provider": provider,
+                            "providerId": provider_id,
+                            "reason": reason,
+                            "expiresAt": expires_at.map(|date| date.and_utc().to_rfc3339()),
+                        })),
+                    })
+                    .await?;
+
+                Ok(ban)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    // Sessions always belong to an account, so there's nothing to end if the provider id has never logged in
+    if let Some(account_id) = account_id {
+        session_store.delete_account_sessions(account_id).await?;
+    }
+
+    log::info!(
+        "Frontend account {} banned {:?} provider id {} until {:?}: {}",
+        session.account_id,
+        provider,
+        provider_id,
+        expires_at,
+        reason
+    );
+
+    ApiResponse::ok(into_list_item(ban, account.as_ref(), now))
+}
+
+#[delete("bans/{id}")]
+pub async fn lift_ban(
+    session: JwtSession,
+    database: Data<Database>,
+    params: Path<i64>,
+) -> ApiResult<EmptyResponse> {
+    if !session.has_permissions(FrontendPermissions::ManageAccounts) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let id = params.into_inner();
+    let frontend_account_id = session.account_id;
+    let conn = database.connect().await?;
+    let lifted = conn
+        .transaction(|conn| {
+            async move {
+                let Some(ban) = conn
+                    .bans()
+                    .lift(id, Some(frontend_account_id), &Utc::now().naive_utc())
+                    .await?
+                else {
+                    return Ok(false);
+                };
+
+                conn.audit_log()
+                    .record(&NewAuditLogEntry {
+                        frontend_actor_id: Some(frontend_account_id),
+                        game_actor_id: None,
+                        action: AuditAction::LiftBan,
+                        target_id: Some(&ban.id.to_string()),
+                        before: None,
+                        after: Some(json!({
+                            "accountId": ban.account_id,
+                            "provider": ban.provider,
+                            "providerId": ban.provider_id,
+                        })),
+                    })
+                    .await?;
+
+                Ok(true)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    if !lifted {
+        return match conn.bans().get_by_id(id).await? {
+            Some(_) => Err(ApiError::Unprocessable(anyhow::anyhow!(
+                "The ban has already been lifted"
+            ))),
+            None => Err(ApiError::NotFound),
+        };
+    }
+
+    log::info!("Frontend account {} lifted ban {}", session.account_id, id);
+
+    ApiResponse::ok(EmptyResponse)
+}
+
+fn into_list_item(ban: Ban, account: Option<&Account>, now: &NaiveDateTime) -> BanListItem {
+    BanListItem {
+        id: ban.id,
+        is_active: ban.is_active(now),
+        account: account.map(|account| GameAccountSummary {
+            id: account.id.clone(),
+            name: account.display_name.clone(),
+        }),
+        provider: ban.provider,
+        provider_id: ban.provider_id,
+        reason: ban.reason,
+        issued_by: ban.issued_by,
+        created_at: ban.created_at.and_utc().to_rfc3339(),
+        expires_at: ban.expires_at.map(|date| date.and_utc().to_rfc3339()),
+        lifted_at: ban.lifted_at.map(|date| date.and_utc().to_rfc3339()),
+        lifted_by: ban.lifted_by,
+    }
+}
@@ -0,0 +1,54 @@
+use actix_web::{
+    get,
+    web::{Data, Query},
+    HttpResponse,
+};
+use parcel_common::api_types::area::AreaHash;
+use parcel_game_data::{Area, GameData};
+use serde::Deserialize;
+
+use crate::{
+    data::database::Database,
+    frontend::{error::ApiError, jwt_session::JwtSession},
+    geojson::{self, Layers},
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeoJsonQuery {
+    /// Only include features in this area. Defaults to every area
+    area: Option<Area>,
+    /// Every layer is included by default
+    objects: Option<bool>,
+    delivery_points: Option<bool>,
+    roads: Option<bool>,
+}
+
+/// Returns the objects, delivery points and roads as a GeoJSON feature collection.
+///
+/// Unlike the other endpoints the response isn't wrapped in an api response, so that map tools can use it directly.
+#[get("geojson")]
+pub async fn get_geojson(
+    _session: JwtSession,
+    database: Data<Database>,
+    game_data: Data<GameData>,
+    query: Query<GeoJsonQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let area = query
+        .area
+        .map(AreaHash::try_from)
+        .transpose()
+        .map_err(|_| ApiError::Unprocessable(anyhow::anyhow!("Invalid area")))?;
+    let layers = Layers {
+        objects: query.objects.unwrap_or(true),
+        delivery_points: query.delivery_points.unwrap_or(true),
+        roads: query.roads.unwrap_or(true),
+    };
+
+    let conn = database.connect().await?;
+    let collection = geojson::build_feature_collection(&conn, &game_data, area, &layers).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/geo+json")
+        .json(collection))
+}
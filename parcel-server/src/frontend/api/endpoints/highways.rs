@@ -0,0 +1,163 @@
+use std::collections::{BTreeMap, HashMap};
+
+use actix_web::{get, web::Data};
+use parcel_common::api_types::frontend::{
+    accounts::GameAccountSummary,
+    highways::{HighwayConstruction, HighwayContributor, HighwayResourceProgress},
+};
+use parcel_game_data::{GameData, HighwaySegment, Language};
+
+use crate::{
+    data::database::Database,
+    frontend::{
+        jwt_session::JwtSession,
+        result::{ApiResponse, ApiResult},
+    },
+};
+
+/// The number of contributors that are returned for each construction.
+const NUM_TOP_CONTRIBUTORS: i64 = 5;
+
+/// Lists the construction progress of every highway segment in the game data,
+/// and every other construction that has resources devoted to it.
+#[get("highways")]
+pub async fn list_highway_constructions(
+    _session: JwtSession,
+    database: Data<Database>,
+    game_data: Data<GameData>,
+) -> ApiResult<Vec<HighwayConstruction>> {
+    let conn = database.connect().await?;
+    let highway_resources = conn.highway_resources();
+
+    // Start with every segment so that segments without devoted resources are included
+    let mut devoted_resources = game_data
+        .highway_segments
+        .keys()
+        .map(|construction_id| (*construction_id, BTreeMap::<i16, i64>::new()))
+        .collect::<BTreeMap<_, _>>();
+
+    for resources in highway_resources.list_total_resources().await? {
+        devoted_resources
+            .entry(resources.construction_id)
+            .or_default()
+            .insert(resources.resource_id, resources.num_resources);
+    }
+
+    let contributors = highway_resources
+        .get_top_contributors(NUM_TOP_CONTRIBUTORS)
+        .await?;
+    let account_names = conn
+        .accounts()
+        .get_by_ids(
+            &contributors
+                .iter()
+                .map(|contributor| &contributor.account_id)
+                .collect::<Vec<_>>(),
+        )
+        .await?
+        .into_iter()
+        .map(|account| (account.id, account.display_name))
+        .collect::<HashMap<_, _>>();
+
+    let mut top_contributors = HashMap::<i32, Vec<HighwayContributor>>::new();
+
+    for contributor in contributors {
+        top_contributors
+            .entry(contributor.construction_id)
+            .or_default()
+            .push(HighwayContributor {
+                account: GameAccountSummary {
+                    name: account_names
+                        .get(&contributor.account_id)
+                        .cloned()
+                        .unwrap_or_default(),
+                    id: contributor.account_id,
+                },
+                num_resources: contributor.num_resources,
+            });
+    }
+
+    let mut constructions = devoted_resources
+        .into_iter()
+        .map(|(construction_id, devoted)| {
+            let segment = game_data.highway_segments.get(&construction_id);
+
+            HighwayConstruction {
+                construction_id,
+                name: game_data
+                    .highway_segment_name(construction_id, Language::English)
+                    .cloned(),
+                area: segment.map(|segment| segment.metadata.area),
+                resources: resource_progress(&game_data, segment, &devoted),
+                top_contributors: top_contributors
+                    .remove(&construction_id)
+                    .unwrap_or_default(),
+                completion_percentage: segment
+                    .and_then(|segment| completion_percentage(segment, &devoted)),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Sort by the order the segments are listed in game, unknown constructions last
+    constructions.sort_by_key(|construction| {
+        game_data
+            .highway_segments
+            .get(&construction.construction_id)
+            .map(|segment| segment.metadata.order_in_list)
+            .unwrap_or(u32::MAX)
+    });
+
+    ApiResponse::ok(constructions)
+}
+
+/// Returns the devoted and required amount of every resource that has been devoted to the segment
+/// or is required to finish it.
+fn resource_progress(
+    game_data: &GameData,
+    segment: Option<&HighwaySegment>,
+    devoted: &BTreeMap<i16, i64>,
+) -> Vec<HighwayResourceProgress> {
+    let mut resource_ids = devoted.keys().copied().collect::<Vec<_>>();
+
+    if let Some(segment) = segment {
+        resource_ids.extend(segment.required_resources.keys());
+        resource_ids.sort_unstable();
+        resource_ids.dedup();
+    }
+
+    resource_ids
+        .into_iter()
+        .map(|resource_id| HighwayResourceProgress {
+            resource_id,
+            name: game_data
+                .highway_resource_name(resource_id, Language::English)
+                .cloned(),
+            num_resources: devoted.get(&resource_id).copied().unwrap_or(0),
+            required_resources: segment
+                .and_then(|segment| segment.required_resources.get(&resource_id))
+                .copied(),
+        })
+        .collect()
+}
+
+/// Returns how much of the required resources have been devoted, from 0 to 100.
+///
+/// Resources devoted beyond the required amount don't count towards other resources.
+fn completion_percentage(segment: &HighwaySegment, devoted: &BTreeMap<i16, i64>) -> Option<f64> {
+    let total_required = segment.required_resources.values().sum::<i64>();
+
+    if total_required <= 0 {
+        return None;
+    }
+
+    let total_devoted = segment
+        .required_resources
+        .iter()
+        .map(|(resource_id, required)| {
+            let devoted = devoted.get(resource_id).copied().unwrap_or(0);
+            devoted.min(*required).max(0)
+        })
+        .sum::<i64>();
+
+    Some(total_devoted as f64 / total_required as f64 * 100.0)
+}
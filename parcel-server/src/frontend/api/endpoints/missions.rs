@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+
+use actix_web::{
+    delete, get, post, put,
+    web::{self, Data, Json},
+};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use parcel_common::api_types::frontend::{
+    accounts::GameAccountSummary,
+    audit_log::AuditAction,
+    auth::FrontendPermissions,
+    missions::{
+        MissionBaggage, MissionBaggageAmmoInfo, MissionCatapultShellInfo, MissionDetails,
+        MissionDynamicLocationInfo, MissionDynamicMissionInfo, MissionSupplyInfo,
+        SetMissionProgressStateRequest,
+    },
+};
+use parcel_game_data::{GameData, Language};
+use serde_json::json;
+
+use crate::{
+    data::database::{missions::DbMission, Database, DatabaseConnection},
+    db::{
+        models::{
+            audit_log::NewAuditLogEntry,
+            mission::{dynamic_location_info::DynamicLocationInfo, Mission},
+        },
+        QueryError,
+    },
+    endpoints::EmptyResponse,
+    frontend::{
+        error::ApiError,
+        jwt_session::JwtSession,
+        result::{ApiResponse, ApiResult},
+    },
+};
+
+#[get("missions/{id}")]
+pub async fn get_mission(
+    session: JwtSession,
+    database: Data<Database>,
+    game_data: Data<GameData>,
+    id: web::Path<String>,
+) -> ApiResult<MissionDetails> {
+    if !session.has_permissions(FrontendPermissions::ManageMissions) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let conn = database.connect().await?;
+    let mission = conn
+        .missions()
+        .get_by_id(&id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    ApiResponse::ok(query_mission_details(&conn, &game_data, mission).await?)
+}
+
+#[put("missions/{id}/progressState")]
+pub async fn set_mission_progress_state(
+    session: JwtSession,
+    database: Data<Database>,
+    game_data: Data<GameData>,
+    id: web::Path<String>,
+    request: Json<SetMissionProgressStateRequest>,
+) -> ApiResult<MissionDetails> {
+    if !session.has_permissions(FrontendPermissions::ManageMissions) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let frontend_account_id = session.account_id;
+    let id = id.as_str();
+    let progress_state = request.progress_state;
+    let clear_worker = request.clear_worker;
+    let conn = database.connect().await?;
+    let mission = conn
+        .transaction(|conn| {
+            async move {
+                let missions = conn.missions();
+                let Some(before) = missions.get_by_id(id).await? else {
+                    return Ok(None);
+                };
+                let mission = missions
+                    .set_progress_state(id, progress_state, clear_worker)
+                    .await?;
+
+                if let Some(mission) = &mission {
+                    record_mission_change(
+                        conn,
+                        frontend_account_id,
+                        AuditAction::SetMissionProgressState,
+                        &before,
+                        Some(mission),
+                    )
+                    .await?;
+                }
+
+                Ok(mission)
+            }
+            .scope_boxed()
+        })
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    log::info!(
+        "Frontend account {} set progress state of mission {} to {:?} (clear worker: {})",
+        session.account_id,
+        mission.id,
+        progress_state,
+        clear_worker
+    );
+
+    ApiResponse::ok(query_mission_details(&conn, &game_data, mission).await?)
+}
+
+/// Returns a mission that's stuck in progress (for example if the worker stopped playing) back to available.
+#[post("missions/{id}/reset")]
+pub async fn reset_mission(
+    session: JwtSession,
+    database: Data<Database>,
+    game_data: Data<GameData>,
+    id: web::Path<String>,
+) -> ApiResult<MissionDetails> {
+    if !session.has_permissions(FrontendPermissions::ManageMissions) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let frontend_account_id = session.account_id;
+    let id = id.as_str();
+    let conn = database.connect().await?;
+    let (exists, mission) = conn
+        .transaction(|conn| {
+            async move {
+                let missions = conn.missions();
+                let Some(before) = missions.get_by_id(id).await? else {
+                    return Ok((false, None));
+                };
+                let mission = missions.reset_progress(id).await?;
+
+                if let Some(mission) = &mission {
+                    record_mission_change(
+                        conn,
+                        frontend_account_id,
+                        AuditAction::ResetMission,
+                        &before,
+                        Some(mission),
+                    )
+                    .await?;
+                }
+
+                Ok((true, mission))
+            }
+            .scope_boxed()
+        })
+        .await?;
+    let mission = match (exists, mission) {
+        (_, Some(mission)) => mission,
+        (true, None) => {
+            return Err(ApiError::Unprocessable(anyhow::anyhow!(
+                "The mission is not in progress"
+            )))
+        }
+        (false, None) => return Err(ApiError::NotFound),
+    };
+
+    log::info!(
+        "Frontend account {} reset mission {} to available",
+        session.account_id,
+        mission.id
+    );
+
+    ApiResponse::ok(query_mission_details(&conn, &game_data, mission).await?)
+}
+
+#[delete("missions/{id}")]
+pub async fn delete_mission(
+    session: JwtSession,
+    database: Data<Database>,
+    id: web::Path<String>,
+) -> ApiResult<EmptyResponse> {
+    if !session.has_permissions(FrontendPermissions::ManageMissions) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let frontend_account_id = session.account_id;
+    let mission_id = id.as_str();
+    let conn = database.connect().await?;
+    let deleted = conn
+        .transaction(|conn| {
+            async move {
+                let missions = conn.missions();
+                let Some(before) = missions.get_by_id(mission_id).await? else {
+                    return Ok(false);
+                };
+
+                missions.delete_mission_by_id(mission_id).await?;
+                record_mission_change(
+                    conn,
+                    frontend_account_id,
+                    AuditAction::DeleteMission,
+                    &before,
+                    None,
+                )
+                .await?;
+
+                Ok(true)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    if !deleted {
+        return Err(ApiError::NotFound);
+    }
+
+    log::info!(
+        "Frontend account {} deleted mission {}",
+        session.account_id,
+        id
+    );
+
+    ApiResponse::ok(EmptyResponse)
+}
+
+/// Records a change that was made to a mission through the frontend in the audit log.
+///
+/// `after` is None if the mission was deleted.
+async fn record_mission_change(
+    conn: &DatabaseConnection<'_>,
+    frontend_account_id: i64,
+    action: AuditAction,
+    before: &Mission,
+    after: Option<&Mission>,
+) -> Result<(), QueryError> {
+    let to_audit_value = |mission: &Mission| {
+        json!({
+            "creatorId": mission.creator_id,
+            "workerId": mission.worker_id,
+            "progressState": mission.progress_state,
+        })
+    };
+
+    conn.audit_log()
+        .record(&NewAuditLogEntry {
+            frontend_actor_id: Some(frontend_account_id),
+            game_actor_id: None,
+            action,
+            target_id: Some(&before.id),
+            before: Some(to_audit_value(before)),
+            after: after.map(to_audit_value),
+        })
+        .await
+}
+
+async fn query_mission_details(
+    conn: &DatabaseConnection<'_>,
+    game_data: &GameData,
+    mission: Mission,
+) -> Result<MissionDetails, ApiError> {
+    let DbMission {
+        mission,
+        supply_info,
+        dynamic_start_info,
+        dynamic_end_info,
+        dynamic_delivered_info,
+        dynamic_mission_info,
+        catapult_shell_info,
+        baggages,
+        mut baggage_ammo_infos,
+        relations,
+    } = conn
+        .missions()
+        .query_mission_data([mission])
+        .await?
+        .pop()
+        .expect("There should be exactly one item in the vec");
+
+    let mut account_ids = vec![&mission.creator_id];
+    account_ids.extend(mission.worker_id.as_ref());
+    account_ids.extend(relations.iter());
+
+    let account_names = conn
+        .accounts()
+        .get_by_ids(&account_ids)
+        .await?
+        .into_iter()
+        .map(|account| (account.id, account.display_name))
+        .collect::<HashMap<_, _>>();
+    let get_account = |account_id: String| GameAccountSummary {
+        name: account_names.get(&account_id).cloned().unwrap_or_default(),
+        id: account_id,
+    };
+
+    let baggages = baggages
+        .into_iter()
+        .map(|baggage| MissionBaggage {
+            name: game_data
+                .baggage_name(baggage.name_hash as u32, Language::English)
+                .map(|name| name.replace("{0}", &baggage.amount.to_string())),
+            ammo_info: baggage_ammo_infos
+                .remove(&baggage.id)
+                .map(|info| MissionBaggageAmmoInfo {
+                    ammo_id: info.ammo_id,
+                    clip_count: info.clip_count,
+                    count: info.count,
+                }),
+            id: baggage.id,
+            name_hash: baggage.name_hash,
+            amount: baggage.amount,
+            user_index: baggage.user_index,
+            x: baggage.x,
+            y: baggage.y,
+            z: baggage.z,
+            is_returned: baggage.is_returned,
+        })
+        .collect();
+
+    Ok(MissionDetails {
+        creator: get_account(mission.creator_id),
+        worker: mission.worker_id.map(get_account),
+        relations: relations.into_iter().map(get_account).collect(),
+        id: mission.id,
+        qpid_id: mission.qpid_id,
+        qpid_start_location: mission.qpid_start_location,
+        qpid_end_location: mission.qpid_end_location,
+        qpid_delivered_location: mission.qpid_delivered_location,
+        mission_static_id: mission.mission_static_id,
+        mission_name: game_data
+            .mission_name(mission.mission_static_id, Language::English)
+            .cloned(),
+        mission_type: mission.mission_type,
+        online_mission_type: mission.online_mission_type,
+        progress_state: mission.progress_state,
+        registered_time: mission.registered_time.and_utc().to_rfc3339(),
+        expiration_time: mission.expiration_time.and_utc().to_rfc3339(),
+        supply_info: supply_info.map(|info| MissionSupplyInfo {
+            item_name: u32::try_from(info.item_hash)
+                .ok()
+                .and_then(|hash| game_data.baggage_name(hash, Language::English))
+                .cloned(),
+            item_hash: info.item_hash,
+            amount: info.amount,
+        }),
+        dynamic_start_info: dynamic_start_info.map(into_location_info),
+        dynamic_end_info: dynamic_end_info.map(into_location_info),
+        dynamic_delivered_info: dynamic_delivered_info.map(into_location_info),
+        dynamic_mission_info: dynamic_mission_info.map(|info| MissionDynamicMissionInfo {
+            client_name_hash: info.client_name_hash,
+            reward_name_hash: info.reward_name_hash,
+        }),
+        catapult_shell_info: catapult_shell_info.map(|info| MissionCatapultShellInfo {
+            local_id: info.local_id,
+            x: info.x,
+            y: info.y,
+            z: info.z,
+        }),
+        baggages,
+    })
+}
+
+fn into_location_info(info: DynamicLocationInfo) -> MissionDynamicLocationInfo {
+    MissionDynamicLocationInfo {
+        location_id: info.location_id,
+        x: info.x,
+        y: info.y,
+        z: info.z,
+    }
+}
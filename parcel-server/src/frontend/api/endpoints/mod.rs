@@ -1,6 +1,33 @@
 pub mod accounts;
+pub mod audit_log;
 pub mod auth;
 pub mod baggages;
+pub mod bans;
 pub mod game_data;
+pub mod geojson;
+pub mod highways;
+pub mod missions;
 pub mod qpid_objects;
+pub mod rankings;
+pub mod sessions;
 pub mod settings;
+pub mod statistics;
+
+use chrono::{DateTime, NaiveDateTime};
+
+use crate::frontend::error::ApiError;
+
+/// Parses an optional date in RFC 3339 format from a request.
+///
+/// The error message is returned to the client if the date is invalid.
+fn parse_optional_date(
+    date: Option<&str>,
+    error: &'static str,
+) -> Result<Option<NaiveDateTime>, ApiError> {
+    date.map(|date| {
+        DateTime::parse_from_rfc3339(date)
+            .map(|date| date.naive_utc())
+            .map_err(|_| ApiError::Unprocessable(anyhow::anyhow!(error)))
+    })
+    .transpose()
+}
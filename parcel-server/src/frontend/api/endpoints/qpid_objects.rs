@@ -1,20 +1,35 @@
 use std::collections::HashMap;
 
 use actix_web::{
-    get,
-    web::{self, Data},
+    get, put,
+    web::{self, Data, Json, Query},
 };
+use diesel_async::scoped_futures::ScopedFutureExt;
 use parcel_common::api_types::{
     area::AreaHash,
     frontend::{
         accounts::GameAccountSummary,
-        qpid_objects::{QpidObject, QpidObjectType},
+        audit_log::AuditAction,
+        auth::FrontendPermissions,
+        qpid_objects::{
+            QpidObject, QpidObjectBridgeInfo, QpidObjectComment, QpidObjectCustomizeInfo,
+            QpidObjectDetails, QpidObjectExtraInfo, QpidObjectMaterialsContribution,
+            QpidObjectParkingInfo, QpidObjectRopeInfo, QpidObjectStoneInfo, QpidObjectType,
+            QpidObjectVehicleInfo, SetObjectCreatorRequest, SetObjectDeletedRequest,
+            SetObjectsDeletedResponse,
+        },
     },
 };
 use parcel_game_data::Area;
+use serde::Deserialize;
+use serde_json::json;
 
 use crate::{
-    data::database::Database,
+    data::database::{qpid_objects::DbQpidObject, Database, DatabaseConnection},
+    db::models::{
+        audit_log::NewAuditLogEntry,
+        qpid_object::{self, vehicle_info::VehicleInfo},
+    },
     frontend::{
         error::ApiError,
         jwt_session::JwtSession,
@@ -22,33 +37,41 @@ use crate::{
     },
 };
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListQpidObjectsQuery {
+    /// Requires the ManageObjects permission
+    #[serde(default)]
+    include_deleted: bool,
+}
+
 #[get("qpidObjects/{area}")]
 pub async fn list_qpid_objects(
-    _session: JwtSession,
+    session: JwtSession,
     database: Data<Database>,
     area: web::Path<Area>,
+    query: Query<ListQpidObjectsQuery>,
 ) -> ApiResult<Vec<QpidObject>> {
-    let area = match area.into_inner() {
-        Area::Area01 => Ok(AreaHash::EasternRegion),
-        Area::Area02 => Ok(AreaHash::CentralRegion),
-        Area::Area04 => Ok(AreaHash::WesternRegion),
-        _ => Err(ApiError::Unprocessable(anyhow::anyhow!("Invalid area"))),
-    }?;
+    if query.include_deleted && !session.has_permissions(FrontendPermissions::ManageObjects) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let area = AreaHash::try_from(area.into_inner())
+        .map_err(|_| ApiError::Unprocessable(anyhow::anyhow!("Invalid area")))?;
 
     let conn = database.connect().await?;
-    let qpid_objects = conn.qpid_objects().find_objects_by_area(area).await?;
-    let creator_names = conn
-        .accounts()
-        .get_by_ids(
-            &qpid_objects
-                .iter()
-                .map(|q| &q.creator_id)
-                .collect::<Vec<_>>(),
-        )
-        .await?
-        .into_iter()
-        .map(|account| (account.id, account.display_name))
-        .collect::<HashMap<_, _>>();
+    let qpid_objects = conn
+        .qpid_objects()
+        .find_objects_by_area(area, query.include_deleted)
+        .await?;
+    let creator_names = get_account_names(
+        &conn,
+        &qpid_objects
+            .iter()
+            .map(|q| &q.creator_id)
+            .collect::<Vec<_>>(),
+    )
+    .await?;
     let vehicle_infos = conn
         .qpid_objects()
         .query_vehicle_infos(
@@ -63,52 +86,371 @@ pub async fn list_qpid_objects(
         qpid_objects
             .into_iter()
             .map(|q| {
-                let object_type = (q.object_type.clone(), q.sub_type.as_ref()).into();
-                let unknown_type = match &object_type {
-                    QpidObjectType::Unknown => {
-                        Some((q.object_type.to_string(), q.sub_type.to_string()))
-                    }
-                    _ => None,
-                };
-
                 let vehicle_info = vehicle_infos.get(&q.id);
-                let dividend = match vehicle_info {
-                    Some(v) => 10i32.pow(v.exponent as u32),
-                    None => 10i32.pow(q.exponent as u32),
-                } as f64;
-                let location = match vehicle_info {
-                    Some(v) => (
-                        (v.new_pos_x.unwrap_or(q.pos_x) as f64 / dividend) as f32,
-                        (v.new_pos_y.unwrap_or(q.pos_y) as f64 / dividend) as f32,
-                        (v.new_pos_z.unwrap_or(q.pos_z) as f64 / dividend) as f32,
-                    ),
-                    None => (
-                        (q.pos_x as f64 / dividend) as f32,
-                        (q.pos_y as f64 / dividend) as f32,
-                        (q.pos_z as f64 / dividend) as f32,
-                    ),
+                into_api_object(q, vehicle_info, &creator_names)
+            })
+            .collect(),
+    )
+}
+
+#[get("qpidObjects/object/{id}")]
+pub async fn get_qpid_object(
+    session: JwtSession,
+    database: Data<Database>,
+    id: web::Path<String>,
+) -> ApiResult<QpidObjectDetails> {
+    if !session.has_permissions(FrontendPermissions::ManageObjects) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let conn = database.connect().await?;
+    let object = conn
+        .qpid_objects()
+        .get_by_id(&id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    ApiResponse::ok(query_object_details(&conn, object).await?)
+}
+
+#[put("qpidObjects/object/{id}/deleted")]
+pub async fn set_qpid_object_deleted(
+    session: JwtSession,
+    database: Data<Database>,
+    id: web::Path<String>,
+    request: Json<SetObjectDeletedRequest>,
+) -> ApiResult<QpidObjectDetails> {
+    if !session.has_permissions(FrontendPermissions::ManageObjects) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let frontend_account_id = session.account_id;
+    let id = id.as_str();
+    let is_deleted = request.is_deleted;
+    let conn = database.connect().await?;
+    let object = conn
+        .transaction(|conn| {
+            async move {
+                let objects = conn.qpid_objects();
+                let Some(before) = objects.get_by_id(id).await? else {
+                    return Ok(None);
                 };
-                let is_lost = match vehicle_info {
-                    Some(v) => v.is_lost,
-                    None => true,
+                let object = objects.set_deleted(id, is_deleted).await?;
+
+                conn.audit_log()
+                    .record(&NewAuditLogEntry {
+                        frontend_actor_id: Some(frontend_account_id),
+                        game_actor_id: None,
+                        action: AuditAction::SetObjectDeleted,
+                        target_id: Some(id),
+                        before: Some(json!({ "isDeleted": before.is_deleted })),
+                        after: Some(json!({ "isDeleted": is_deleted })),
+                    })
+                    .await?;
+
+                Ok(object)
+            }
+            .scope_boxed()
+        })
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    ApiResponse::ok(query_object_details(&conn, object).await?)
+}
+
+#[put("qpidObjects/object/{id}/creator")]
+pub async fn set_qpid_object_creator(
+    session: JwtSession,
+    database: Data<Database>,
+    id: web::Path<String>,
+    request: Json<SetObjectCreatorRequest>,
+) -> ApiResult<QpidObjectDetails> {
+    if !session.has_permissions(FrontendPermissions::ManageObjects) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let conn = database.connect().await?;
+
+    if conn
+        .accounts()
+        .get_by_ids(&[&request.account_id])
+        .await?
+        .is_empty()
+    {
+        return Err(ApiError::Unprocessable(anyhow::anyhow!(
+            "The new creator's account does not exist"
+        )));
+    }
+
+    let frontend_account_id = session.account_id;
+    let id = id.as_str();
+    let creator_id = request.account_id.as_str();
+    let object = conn
+        .transaction(|conn| {
+            async move {
+                let objects = conn.qpid_objects();
+                let Some(before) = objects.get_by_id(id).await? else {
+                    return Ok(None);
                 };
+                let object = objects.set_creator(id, creator_id).await?;
 
-                QpidObject {
-                    id: q.id,
-                    location,
-                    location_id: q.qpid_id,
-                    object_type,
-                    unknown_type,
-                    is_lost,
-                    creator: GameAccountSummary {
-                        name: creator_names
-                            .get(&q.creator_id)
-                            .cloned()
-                            .unwrap_or_default(),
-                        id: q.creator_id,
-                    },
-                }
-            })
+                conn.audit_log()
+                    .record(&NewAuditLogEntry {
+                        frontend_actor_id: Some(frontend_account_id),
+                        game_actor_id: None,
+                        action: AuditAction::SetObjectCreator,
+                        target_id: Some(id),
+                        before: Some(json!({ "creatorId": before.creator_id })),
+                        after: Some(json!({ "creatorId": creator_id })),
+                    })
+                    .await?;
+
+                Ok(object)
+            }
+            .scope_boxed()
+        })
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    ApiResponse::ok(query_object_details(&conn, object).await?)
+}
+
+/// Deletes or restores every object created by the account, for example to clean up after a griefer.
+#[put("qpidObjects/creator/{accountId}/deleted")]
+pub async fn set_qpid_objects_deleted_by_creator(
+    session: JwtSession,
+    database: Data<Database>,
+    account_id: web::Path<String>,
+    request: Json<SetObjectDeletedRequest>,
+) -> ApiResult<SetObjectsDeletedResponse> {
+    if !session.has_permissions(FrontendPermissions::ManageObjects) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let conn = database.connect().await?;
+
+    if conn
+        .accounts()
+        .get_by_ids(&[&*account_id])
+        .await?
+        .is_empty()
+    {
+        return Err(ApiError::NotFound);
+    }
+
+    let frontend_account_id = session.account_id;
+    let account_id = account_id.as_str();
+    let is_deleted = request.is_deleted;
+    let num_affected_objects = conn
+        .transaction(|conn| {
+            async move {
+                let num_affected_objects = conn
+                    .qpid_objects()
+                    .set_deleted_by_creator(account_id, is_deleted)
+                    .await?;
+
+                conn.audit_log()
+                    .record(&NewAuditLogEntry {
+                        frontend_actor_id: Some(frontend_account_id),
+                        game_actor_id: None,
+                        action: AuditAction::SetObjectsDeletedByCreator,
+                        target_id: Some(account_id),
+                        before: None,
+                        after: Some(json!({
+                            "isDeleted": is_deleted,
+                            "numAffectedObjects": num_affected_objects,
+                        })),
+                    })
+                    .await?;
+
+                Ok(num_affected_objects)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    ApiResponse::ok(SetObjectsDeletedResponse {
+        num_affected_objects,
+    })
+}
+
+async fn get_account_names(
+    conn: &DatabaseConnection<'_>,
+    account_ids: &[impl AsRef<str>],
+) -> Result<HashMap<String, String>, ApiError> {
+    Ok(conn
+        .accounts()
+        .get_by_ids(account_ids)
+        .await?
+        .into_iter()
+        .map(|account| (account.id, account.display_name))
+        .collect())
+}
+
+fn into_api_object(
+    q: qpid_object::QpidObject,
+    vehicle_info: Option<&VehicleInfo>,
+    account_names: &HashMap<String, String>,
+) -> QpidObject {
+    let object_type = (q.object_type.clone(), q.sub_type.as_ref()).into();
+    let unknown_type = match &object_type {
+        QpidObjectType::Unknown => Some((q.object_type.to_string(), q.sub_type.to_string())),
+        _ => None,
+    };
+
+    let (x, y, z) = q.world_position(vehicle_info);
+    let location = (x as f32, y as f32, z as f32);
+    let is_lost = match vehicle_info {
+        Some(v) => v.is_lost,
+        None => true,
+    };
+
+    QpidObject {
+        id: q.id,
+        location,
+        location_id: q.qpid_id,
+        object_type,
+        unknown_type,
+        is_lost,
+        is_deleted: q.is_deleted,
+        creator: GameAccountSummary {
+            name: account_names
+                .get(&q.creator_id)
+                .cloned()
+                .unwrap_or_default(),
+            id: q.creator_id,
+        },
+    }
+}
+
+async fn query_object_details(
+    conn: &DatabaseConnection<'_>,
+    object: qpid_object::QpidObject,
+) -> Result<QpidObjectDetails, ApiError> {
+    let DbQpidObject {
+        object,
+        rope_info,
+        stone_info,
+        bridge_info,
+        parking_info,
+        vehicle_info,
+        extra_info,
+        customize_info,
+        comment,
+        comment_phrases,
+        construction_materials,
+        recycle_materials,
+        tags,
+    } = conn
+        .qpid_objects()
+        .query_object_data([object])
+        .await?
+        .pop()
+        .expect("There should be exactly one item in the vec");
+
+    let construction_materials = construction_materials.unwrap_or_default();
+    let recycle_materials = recycle_materials.unwrap_or_default();
+
+    let mut account_ids = vec![&object.creator_id];
+    account_ids.extend(
+        construction_materials
+            .iter()
+            .filter_map(|m| m.contributor_id.as_ref()),
+    );
+    account_ids.extend(
+        recycle_materials
+            .iter()
+            .filter_map(|m| m.contributor_id.as_ref()),
+    );
+    let account_names = get_account_names(conn, &account_ids).await?;
+    let get_account = |account_id: String| GameAccountSummary {
+        name: account_names.get(&account_id).cloned().unwrap_or_default(),
+        id: account_id,
+    };
+
+    let sub_type = object.sub_type.clone();
+    let likes = object.likes;
+    let updated_time = object.updated_time.and_utc().to_rfc3339();
+
+    let comment = comment.map(|comment| {
+        let mut phrases = comment_phrases.unwrap_or_default();
+        phrases.sort_by_key(|phrase| phrase.sort_order);
+
+        QpidObjectComment {
+            writer: comment.writer,
+            likes: comment.likes,
+            parent_index: comment.parent_index,
+            is_deleted: comment.is_deleted,
+            reference_object: comment.reference_object,
+            phrases: phrases.into_iter().map(|phrase| phrase.phrase).collect(),
+        }
+    });
+
+    let construction_materials = construction_materials
+        .into_iter()
+        .map(|m| QpidObjectMaterialsContribution {
+            contributor: m.contributor_id.map(get_account),
+            materials: vec![m.mats_0, m.mats_1, m.mats_2, m.mats_3, m.mats_4, m.mats_5],
+            repair_materials: Some(vec![
+                m.repair_0, m.repair_1, m.repair_2, m.repair_3, m.repair_4, m.repair_5,
+            ]),
+            time: m.contribute_time.and_utc().to_rfc3339(),
+        })
+        .collect();
+    let recycle_materials = recycle_materials
+        .into_iter()
+        .map(|m| QpidObjectMaterialsContribution {
+            contributor: m.contributor_id.map(get_account),
+            materials: vec![m.mats_0, m.mats_1, m.mats_2, m.mats_3, m.mats_4, m.mats_5],
+            repair_materials: None,
+            time: m.recycle_time.and_utc().to_rfc3339(),
+        })
+        .collect();
+
+    Ok(QpidObjectDetails {
+        object: into_api_object(object, vehicle_info.as_ref(), &account_names),
+        sub_type,
+        likes,
+        updated_time,
+        tags: tags
+            .unwrap_or_default()
+            .into_iter()
+            .map(|tag| tag.tag)
             .collect(),
-    )
+        comment,
+        construction_materials,
+        recycle_materials,
+        rope_info: rope_info.map(|info| QpidObjectRopeInfo {
+            pitch: info.pitch,
+            heading: info.heading,
+            length: info.len,
+        }),
+        stone_info: stone_info.map(|info| QpidObjectStoneInfo {
+            resting_count: info.resting_count,
+        }),
+        bridge_info: bridge_info.map(|info| QpidObjectBridgeInfo { angle: info.angle }),
+        parking_info: parking_info.map(|info| QpidObjectParkingInfo {
+            location_id: info.location_id,
+            dynamic_location_id: info.dynamic_location_id,
+            current_qpid_id: info.current_qpid_id,
+            is_parking: info.is_parking,
+        }),
+        vehicle_info: vehicle_info.map(|info| QpidObjectVehicleInfo {
+            location_id: info.location_id,
+            dynamic_location_id: info.dynamic_location_id,
+            current_qpid_id: info.current_qpid_id,
+            is_parking: info.is_parking,
+            is_lost: info.is_lost,
+            is_race: info.is_race,
+            customize_type: info.customize_type,
+            customize_color: info.customize_color,
+        }),
+        extra_info: extra_info.map(|info| QpidObjectExtraInfo {
+            alternative_qpid_id: info.alternative_qpid_id,
+        }),
+        customize_info: customize_info.map(|info| QpidObjectCustomizeInfo {
+            customize_param: info.customize_param,
+            customize_color: info.customize_color,
+        }),
+    })
 }
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use actix_web::{
+    delete, get, post,
+    web::{Data, Json, Path},
+};
+use chrono::DateTime;
+use parcel_common::api_types::frontend::{
+    accounts::GameAccountSummary,
+    auth::FrontendPermissions,
+    rankings::{CreateRankingScheduleRequest, RankingRecordListItem, RankingScheduleListItem},
+};
+
+use crate::{
+    data::database::Database,
+    db::models::ranking::RankingSchedule,
+    endpoints::EmptyResponse,
+    frontend::{
+        error::ApiError,
+        jwt_session::JwtSession,
+        result::{ApiResponse, ApiResult},
+    },
+};
+
+#[get("rankings/schedules")]
+pub async fn list_ranking_schedules(
+    session: JwtSession,
+    database: Data<Database>,
+) -> ApiResult<Vec<RankingScheduleListItem>> {
+    if !session.has_permissions(FrontendPermissions::ManageServerSettings) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let conn = database.connect().await?;
+    let rankings = conn.rankings();
+    let schedules = rankings.get_all_schedules().await?;
+    let num_participants = rankings
+        .count_participants(&schedules.iter().map(|s| s.id).collect::<Vec<_>>())
+        .await?;
+
+    ApiResponse::ok(
+        schedules
+            .into_iter()
+            .map(|schedule| {
+                let num_participants = num_participants.get(&schedule.id).copied().unwrap_or(0);
+                into_list_item(schedule, num_participants)
+            })
+            .collect(),
+    )
+}
+
+#[post("rankings/schedules")]
+pub async fn create_ranking_schedule(
+    session: JwtSession,
+    database: Data<Database>,
+    request: Json<CreateRankingScheduleRequest>,
+) -> ApiResult<RankingScheduleListItem> {
+    if !session.has_permissions(FrontendPermissions::ManageServerSettings) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let start_date = DateTime::parse_from_rfc3339(&request.start_date)
+        .map_err(|_| ApiError::Unprocessable(anyhow::anyhow!("Invalid start date")))?
+        .naive_utc();
+    let end_date = DateTime::parse_from_rfc3339(&request.end_date)
+        .map_err(|_| ApiError::Unprocessable(anyhow::anyhow!("Invalid end date")))?
+        .naive_utc();
+
+    if end_date <= start_date {
+        return Err(ApiError::Unprocessable(anyhow::anyhow!(
+            "The end date must be after the start date"
+        )));
+    }
+
+    let conn = database.connect().await?;
+    let schedule = conn
+        .rankings()
+        .create_schedule(request.category, &start_date, &end_date)
+        .await?;
+
+    ApiResponse::ok(into_list_item(schedule, 0))
+}
+
+#[delete("rankings/schedules/{id}")]
+pub async fn delete_ranking_schedule(
+    session: JwtSession,
+    database: Data<Database>,
+    params: Path<i32>,
+) -> ApiResult<EmptyResponse> {
+    if !session.has_permissions(FrontendPermissions::ManageServerSettings) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let conn = database.connect().await?;
+
+    if !conn.rankings().delete_schedule(params.into_inner()).await? {
+        return Err(ApiError::NotFound);
+    }
+
+    ApiResponse::ok(EmptyResponse)
+}
+
+#[get("rankings/schedules/{id}/records")]
+pub async fn list_ranking_records(
+    session: JwtSession,
+    database: Data<Database>,
+    params: Path<i32>,
+) -> ApiResult<Vec<RankingRecordListItem>> {
+    if !session.has_permissions(FrontendPermissions::ManageServerSettings) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let schedule_id = params.into_inner();
+    let conn = database.connect().await?;
+    let rankings = conn.rankings();
+
+    if rankings.get_schedule(schedule_id).await?.is_none() {
+        return Err(ApiError::NotFound);
+    }
+
+    let records = rankings.get_records(schedule_id).await?;
+    let account_names = conn
+        .accounts()
+        .get_by_ids(&records.iter().map(|r| &r.account_id).collect::<Vec<_>>())
+        .await?
+        .into_iter()
+        .map(|account| (account.id, account.display_name))
+        .collect::<HashMap<_, _>>();
+
+    ApiResponse::ok(
+        records
+            .into_iter()
+            .map(|record| RankingRecordListItem {
+                account: GameAccountSummary {
+                    name: account_names
+                        .get(&record.account_id)
+                        .cloned()
+                        .unwrap_or_default(),
+                    id: record.account_id,
+                },
+                score: record.score,
+                try_count: record.try_count,
+                clear_count: record.clear_count,
+                rank: record.rank,
+                reward_tier: record.reward_tier,
+            })
+            .collect(),
+    )
+}
+
+fn into_list_item(schedule: RankingSchedule, num_participants: i64) -> RankingScheduleListItem {
+    RankingScheduleListItem {
+        id: schedule.id,
+        category: schedule.category,
+        start_date: schedule.start_date.and_utc().to_rfc3339(),
+        end_date: schedule.end_date.and_utc().to_rfc3339(),
+        closed_at: schedule.closed_at.map(|date| date.and_utc().to_rfc3339()),
+        num_participants,
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use actix_web::{
+    delete, get,
+    web::{Data, Path},
+};
+use parcel_common::api_types::frontend::{
+    accounts::GameAccountSummary,
+    audit_log::AuditAction,
+    auth::FrontendPermissions,
+    sessions::{GameSessionListItem, KickSessionsResponse},
+};
+use serde_json::json;
+
+use crate::{
+    data::{database::Database, session_store::SessionStore},
+    db::models::audit_log::NewAuditLogEntry,
+    frontend::{
+        error::ApiError,
+        jwt_session::JwtSession,
+        result::{ApiResponse, ApiResult},
+    },
+};
+
+/// Lists the players that currently have an active game session, most recently seen first.
+#[get("sessions")]
+pub async fn list_game_sessions(
+    session: JwtSession,
+    database: Data<Database>,
+    session_store: Data<SessionStore>,
+) -> ApiResult<Vec<GameSessionListItem>> {
+    if !session.has_permissions(FrontendPermissions::ManageAccounts) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let mut sessions = session_store.list_sessions().await?;
+    sessions.sort_unstable_by(|a, b| b.last_seen_at.cmp(&a.last_seen_at));
+
+    let conn = database.connect().await?;
+    let account_names = conn
+        .accounts()
+        .get_by_ids(&sessions.iter().map(|s| &s.account_id).collect::<Vec<_>>())
+        .await?
+        .into_iter()
+        .map(|account| (account.id, account.display_name))
+        .collect::<HashMap<_, _>>();
+
+    ApiResponse::ok(
+        sessions
+            .into_iter()
+            .map(|game_session| GameSessionListItem {
+                account: GameAccountSummary {
+                    name: account_names
+                        .get(&game_session.account_id)
+                        .cloned()
+                        .unwrap_or_default(),
+                    id: game_session.account_id,
+                },
+                provider: game_session.provider,
+                provider_id: game_session.provider_id,
+                logged_in_at: game_session.logged_in_at.and_utc().to_rfc3339(),
+                last_seen_at: game_session.last_seen_at.and_utc().to_rfc3339(),
+            })
+            .collect(),
+    )
+}
+
+/// Ends the game session of every player.
+#[delete("sessions")]
+pub async fn kick_all_game_sessions(
+    session: JwtSession,
+    database: Data<Database>,
+    session_store: Data<SessionStore>,
+) -> ApiResult<KickSessionsResponse> {
+    if !session.has_permissions(FrontendPermissions::ManageAccounts) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let num_kicked = session_store.delete_all_sessions().await?;
+
+    log::info!(
+        "Frontend account {} kicked all {} game sessions",
+        session.account_id,
+        num_kicked
+    );
+
+    database
+        .connect()
+        .await?
+        .audit_log()
+        .record(&NewAuditLogEntry {
+            frontend_actor_id: Some(session.account_id),
+            game_actor_id: None,
+            action: AuditAction::KickGameSessions,
+            target_id: None,
+            before: None,
+            after: Some(json!({ "numKicked": num_kicked })),
+        })
+        .await?;
+
+    ApiResponse::ok(KickSessionsResponse { num_kicked })
+}
+
+/// Ends the game session of a player, which forces the game to log in again.
+#[delete("sessions/{account_id}")]
+pub async fn kick_game_session(
+    session: JwtSession,
+    database: Data<Database>,
+    session_store: Data<SessionStore>,
+    account_id: Path<String>,
+) -> ApiResult<KickSessionsResponse> {
+    if !session.has_permissions(FrontendPermissions::ManageAccounts) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let tokens = session_store
+        .list_sessions()
+        .await?
+        .into_iter()
+        .filter(|game_session| game_session.account_id == *account_id)
+        .map(|game_session| game_session.token)
+        .collect::<Vec<_>>();
+
+    if tokens.is_empty() {
+        return Err(ApiError::NotFound);
+    }
+
+    for token in &tokens {
+        session_store.delete_session(token).await?;
+    }
+
+    log::info!(
+        "Frontend account {} kicked game account {}",
+        session.account_id,
+        account_id
+    );
+
+    database
+        .connect()
+        .await?
+        .audit_log()
+        .record(&NewAuditLogEntry {
+            frontend_actor_id: Some(session.account_id),
+            game_actor_id: None,
+            action: AuditAction::KickGameSessions,
+            target_id: Some(&account_id),
+            before: None,
+            after: Some(json!({ "numKicked": tokens.len() })),
+        })
+        .await?;
+
+    ApiResponse::ok(KickSessionsResponse {
+        num_kicked: tokens.len(),
+    })
+}
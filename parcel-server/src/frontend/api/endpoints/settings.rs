@@ -1,13 +1,17 @@
 use actix_web::{
-    get, put,
+    get, post, put,
     web::{Data, Json},
 };
 use parcel_common::api_types::frontend::{
+    audit_log::AuditAction,
     auth::FrontendPermissions,
-    settings::{SettingsValues, WhitelistEntry},
+    settings::{RetentionReport, RetentionSettings, SettingsValues, WhitelistEntry},
 };
 
 use crate::{
+    background_jobs::apply_retention_rules::{log_report, RetentionCandidates},
+    data::database::Database,
+    db::models::audit_log::{to_audit_value, NewAuditLogEntry},
     frontend::{
         error::ApiError,
         jwt_session::JwtSession,
@@ -34,21 +38,80 @@ pub async fn set_server_settings(
     session: JwtSession,
     request_settings: Json<SettingsValues>,
     settings: Data<ServerSettings>,
+    database: Data<Database>,
 ) -> ApiResult<SettingsValues> {
     // check that the session has access
     if !session.has_permissions(FrontendPermissions::ManageServerSettings) {
         return Err(ApiError::Forbidden);
     }
 
+    let old_settings = (*settings.read().await).clone();
+
     settings
         .write(|settings| {
             **settings = request_settings.clone();
         })
         .await?;
 
+    let conn = database.connect().await?;
+    conn.audit_log()
+        .record(&NewAuditLogEntry {
+            frontend_actor_id: Some(session.account_id),
+            game_actor_id: None,
+            action: AuditAction::SetServerSettings,
+            target_id: None,
+            before: to_audit_value(&old_settings),
+            after: to_audit_value(&*request_settings),
+        })
+        .await?;
+
     ApiResponse::ok(request_settings.into_inner())
 }
 
+/// Returns what the specified retention rules would remove if they were applied now, without removing anything.
+#[post("settings/retention/preview")]
+pub async fn preview_retention_rules(
+    session: JwtSession,
+    rules: Json<RetentionSettings>,
+    database: Data<Database>,
+) -> ApiResult<RetentionReport> {
+    if !session.has_permissions(FrontendPermissions::ManageServerSettings) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let conn = database.connect().await?;
+    let candidates = RetentionCandidates::find(&conn, &rules).await?;
+
+    ApiResponse::ok(candidates.report())
+}
+
+/// Applies the saved retention rules now, even if they're not enabled.
+#[post("settings/retention/apply")]
+pub async fn apply_retention_rules(
+    session: JwtSession,
+    database: Data<Database>,
+    settings: Data<ServerSettings>,
+) -> ApiResult<RetentionReport> {
+    if !session.has_permissions(FrontendPermissions::ManageServerSettings) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let rules = settings.read().await.retention.clone();
+    let conn = database.connect().await?;
+    let report = RetentionCandidates::find(&conn, &rules)
+        .await?
+        .remove(&conn, Some(session.account_id))
+        .await?;
+
+    log::info!(
+        "Frontend account {} applied the retention rules",
+        session.account_id
+    );
+    log_report(&report);
+
+    ApiResponse::ok(report)
+}
+
 #[get("settings/whitelist")]
 pub async fn get_whitelist(
     session: JwtSession,
@@ -67,6 +130,7 @@ pub async fn set_whitelist(
     session: JwtSession,
     mut request_whitelist: Json<Vec<WhitelistEntry>>,
     whitelist: Data<WhitelistSettings>,
+    database: Data<Database>,
 ) -> ApiResult<Vec<WhitelistEntry>> {
     // check that the session has access
     if !session.has_permissions(FrontendPermissions::ManageServerSettings) {
@@ -91,6 +155,8 @@ pub async fn set_whitelist(
         }
     }
 
+    let old_whitelist = whitelist.read().await.clone().into_inner();
+
     whitelist
         .write(|whitelist| {
             whitelist.clear();
@@ -98,5 +164,17 @@ pub async fn set_whitelist(
         })
         .await?;
 
+    let conn = database.connect().await?;
+    conn.audit_log()
+        .record(&NewAuditLogEntry {
+            frontend_actor_id: Some(session.account_id),
+            game_actor_id: None,
+            action: AuditAction::SetWhitelist,
+            target_id: None,
+            before: to_audit_value(&old_whitelist),
+            after: to_audit_value(&*request_whitelist),
+        })
+        .await?;
+
     ApiResponse::ok(request_whitelist.into_inner())
 }
@@ -0,0 +1,164 @@
+use actix_web::{
+    get,
+    web::{Data, Path, Query},
+};
+use parcel_common::api_types::frontend::{
+    auth::FrontendPermissions,
+    qpid_objects::QpidObjectType,
+    statistics::{
+        DevotedResourcesStatistic, LikesPerDayStatistic, MissionStatistics, ObjectCountStatistic,
+        ProfileStatistics, Statistics,
+    },
+};
+use serde::Deserialize;
+
+use super::parse_optional_date;
+use crate::{
+    data::database::{statistics::StatisticsFilter, Database, DatabaseConnection},
+    frontend::{
+        error::ApiError,
+        jwt_session::JwtSession,
+        result::{ApiResponse, ApiResult},
+    },
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatisticsQuery {
+    /// Only include data from this date onwards, in RFC 3339 format
+    from: Option<String>,
+    /// Only include data from before this date, in RFC 3339 format
+    to: Option<String>,
+}
+
+/// Returns statistics for the whole server.
+///
+/// Requires the ManageAccounts permission, the same as viewing the statistics of another player.
+#[get("statistics")]
+pub async fn get_server_statistics(
+    session: JwtSession,
+    database: Data<Database>,
+    query: Query<StatisticsQuery>,
+) -> ApiResult<Statistics> {
+    if !session.has_permissions(FrontendPermissions::ManageAccounts) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let conn = database.connect().await?;
+    let filter = StatisticsFilter {
+        account_id: None,
+        from: parse_optional_date(query.from.as_deref(), "Invalid from date")?,
+        to: parse_optional_date(query.to.as_deref(), "Invalid to date")?,
+    };
+
+    ApiResponse::ok(query_statistics(&conn, &filter).await?)
+}
+
+/// Returns statistics for a game account.
+///
+/// Players can see their own statistics, otherwise the ManageAccounts permission is required.
+#[get("statistics/accounts/{id}")]
+pub async fn get_account_statistics(
+    session: JwtSession,
+    database: Data<Database>,
+    account_id: Path<String>,
+    query: Query<StatisticsQuery>,
+) -> ApiResult<Statistics> {
+    let conn = database.connect().await?;
+
+    if !session.has_permissions(FrontendPermissions::ManageAccounts) {
+        let own_account_id = conn
+            .frontend_accounts()
+            .get_by_id(session.account_id)
+            .await?
+            .and_then(|account| account.game_account_id);
+
+        if own_account_id.as_deref() != Some(account_id.as_str()) {
+            return Err(ApiError::Forbidden);
+        }
+    }
+
+    if conn
+        .accounts()
+        .get_by_ids(&[account_id.as_str()])
+        .await?
+        .is_empty()
+    {
+        return Err(ApiError::NotFound);
+    }
+
+    let filter = StatisticsFilter {
+        account_id: Some(account_id.as_str()),
+        from: parse_optional_date(query.from.as_deref(), "Invalid from date")?,
+        to: parse_optional_date(query.to.as_deref(), "Invalid to date")?,
+    };
+
+    ApiResponse::ok(query_statistics(&conn, &filter).await?)
+}
+
+async fn query_statistics(
+    conn: &DatabaseConnection<'_>,
+    filter: &StatisticsFilter<'_>,
+) -> Result<Statistics, ApiError> {
+    let statistics = conn.statistics();
+
+    // Several object types can map to the same frontend type, so the counts are merged
+    let mut objects = Vec::<ObjectCountStatistic>::new();
+
+    for count in statistics.count_objects(filter).await? {
+        let object_type = QpidObjectType::from((count.object_type, count.sub_type.as_str()));
+
+        match objects
+            .iter_mut()
+            .find(|o| o.object_type == object_type && o.area_id == count.area_id)
+        {
+            Some(existing) => existing.count += count.count,
+            None => objects.push(ObjectCountStatistic {
+                object_type,
+                area_id: count.area_id,
+                count: count.count,
+            }),
+        }
+    }
+
+    let missions = statistics.count_missions(filter).await?;
+    let profile = statistics.profile_totals(filter.account_id).await?;
+
+    Ok(Statistics {
+        objects,
+        likes_per_day: statistics
+            .likes_per_day(filter)
+            .await?
+            .into_iter()
+            .map(|likes| LikesPerDayStatistic {
+                date: likes.day.format("%Y-%m-%d").to_string(),
+                likes_given: likes.likes_given,
+                likes_received: likes.likes_received,
+            })
+            .collect(),
+        devoted_resources: statistics
+            .devoted_resources(filter)
+            .await?
+            .into_iter()
+            .map(|resources| DevotedResourcesStatistic {
+                construction_id: resources.construction_id,
+                resource_id: resources.resource_id,
+                num_resources: resources.num_resources,
+            })
+            .collect(),
+        missions: MissionStatistics {
+            created: missions.created,
+            completed: missions.completed,
+        },
+        profile: (profile.num_profiles > 0).then_some(ProfileStatistics {
+            delivered_baggage: profile.delivered_baggage,
+            delivered_weight: profile.delivered_weight,
+            distance_traveled: profile.distance_traveled,
+            num_likes_received_npc: profile.num_likes_received_npc,
+            num_likes_received_online: profile.num_likes_received_online,
+            legend_count: profile.legend_count,
+            super_legend_count: profile.super_legend_count,
+            ss_legend_count: profile.ss_legend_count,
+        }),
+    })
+}
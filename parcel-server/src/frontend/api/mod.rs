@@ -20,10 +20,35 @@ pub fn configure_endpoints(cfg: &mut ServiceConfig) {
         .service(accounts::create_credentials)
         .service(accounts::reset_password)
         .service(accounts::create_frontend_account)
+        .service(audit_log::list_audit_log)
+        .service(bans::list_bans)
+        .service(bans::create_ban)
+        .service(bans::lift_ban)
+        .service(sessions::list_game_sessions)
+        .service(sessions::kick_all_game_sessions)
+        .service(sessions::kick_game_session)
         .service(settings::get_server_settings)
         .service(settings::set_server_settings)
+        .service(settings::preview_retention_rules)
+        .service(settings::apply_retention_rules)
         .service(settings::get_whitelist)
         .service(settings::set_whitelist)
+        .service(statistics::get_server_statistics)
+        .service(statistics::get_account_statistics)
         .service(game_data::list_qpid_areas)
-        .service(qpid_objects::list_qpid_objects);
+        .service(highways::list_highway_constructions)
+        .service(geojson::get_geojson)
+        .service(qpid_objects::list_qpid_objects)
+        .service(qpid_objects::get_qpid_object)
+        .service(qpid_objects::set_qpid_object_deleted)
+        .service(qpid_objects::set_qpid_object_creator)
+        .service(qpid_objects::set_qpid_objects_deleted_by_creator)
+        .service(missions::get_mission)
+        .service(missions::set_mission_progress_state)
+        .service(missions::reset_mission)
+        .service(missions::delete_mission)
+        .service(rankings::list_ranking_schedules)
+        .service(rankings::create_ranking_schedule)
+        .service(rankings::delete_ranking_schedule)
+        .service(rankings::list_ranking_records);
 }
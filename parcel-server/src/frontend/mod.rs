@@ -2,6 +2,7 @@ pub mod api;
 mod error;
 pub mod jwt_session;
 mod result;
+pub mod shared_caches;
 
 use actix_web::{
     get,
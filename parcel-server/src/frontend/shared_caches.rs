@@ -0,0 +1,132 @@
+//! Keeps the frontend caches of this server instance up to date with changes that are made on other instances.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use parcel_common::api_types::frontend::auth::CheckAuthResponse;
+use tokio::{sync::broadcast::error::RecvError, task::JoinHandle};
+
+use crate::data::shared_state::{SharedState, SharedStateEvent};
+
+use super::jwt_session::{SessionBlacklistCache, SessionPermissionsCache};
+
+/// Revoked sessions are stored in the shared state with this prefix followed by the token.
+const REVOKED_SESSION_KEY_PREFIX: &str = "revoked_frontend_session/";
+
+/// Finished frontend logins are stored in the shared state with this prefix followed by the callback token.
+const AUTH_RESPONSE_KEY_PREFIX: &str = "frontend_auth_response/";
+
+/// How long the frontend has to check the result of a login, the same as the lifetime of the frontend auth cache.
+const AUTH_RESPONSE_LIFETIME_SECS: i64 = 60 * 2;
+
+/// Stops the session from being used on every server instance.
+///
+/// The caller is expected to have added the session to this instance's blacklist already.
+pub async fn revoke_session(
+    shared_state: &dyn SharedState,
+    token: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<(), anyhow::Error> {
+    shared_state
+        .set(
+            &format!("{REVOKED_SESSION_KEY_PREFIX}{token}"),
+            serde_json::to_value(expires_at)?,
+            Some(expires_at.naive_utc()),
+        )
+        .await?;
+    shared_state
+        .publish(&SharedStateEvent::FrontendSessionRevoked {
+            token: token.to_owned(),
+            expires_at,
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Stores the result of a frontend login until the frontend checks it, which could be on another server instance.
+pub async fn store_auth_response(
+    shared_state: &dyn SharedState,
+    callback_token: &str,
+    response: &CheckAuthResponse,
+) -> Result<(), anyhow::Error> {
+    let expires_at = Utc::now() + Duration::seconds(AUTH_RESPONSE_LIFETIME_SECS);
+
+    shared_state
+        .set(
+            &format!("{AUTH_RESPONSE_KEY_PREFIX}{callback_token}"),
+            serde_json::to_value(response)?,
+            Some(expires_at.naive_utc()),
+        )
+        .await?;
+    shared_state
+        .publish(&SharedStateEvent::FrontendAuthFinished {
+            callback_token: callback_token.to_owned(),
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Removes the result of a frontend login from the shared state and returns it, or None if it was already taken or has expired.
+pub async fn take_auth_response(
+    shared_state: &dyn SharedState,
+    callback_token: &str,
+) -> Result<Option<CheckAuthResponse>, anyhow::Error> {
+    shared_state
+        .take(&format!("{AUTH_RESPONSE_KEY_PREFIX}{callback_token}"))
+        .await?
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(Into::into)
+}
+
+/// Adds the sessions that were revoked on any server instance and haven't expired yet to the blacklist.
+///
+/// Returns the number of sessions that were added.
+pub async fn load_revoked_sessions(
+    shared_state: &dyn SharedState,
+    blacklist: &SessionBlacklistCache,
+) -> Result<usize, anyhow::Error> {
+    let revoked_sessions = shared_state.list(REVOKED_SESSION_KEY_PREFIX).await?;
+    let num_revoked_sessions = revoked_sessions.len();
+
+    for (key, expires_at) in revoked_sessions {
+        let token = key[REVOKED_SESSION_KEY_PREFIX.len()..].to_owned();
+        blacklist
+            .insert(token, serde_json::from_value(expires_at)?)
+            .await;
+    }
+
+    Ok(num_revoked_sessions)
+}
+
+/// Applies the events from every server instance to this instance's caches, until the shared state is dropped.
+pub fn spawn_cache_updater(
+    shared_state: &dyn SharedState,
+    blacklist: Arc<SessionBlacklistCache>,
+    permissions: Arc<SessionPermissionsCache>,
+) -> JoinHandle<()> {
+    let mut events = shared_state.subscribe();
+
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(SharedStateEvent::FrontendSessionRevoked { token, expires_at }) => {
+                    blacklist.insert(token, expires_at).await;
+                }
+                Ok(SharedStateEvent::FrontendPermissionsChanged { account_id }) => {
+                    permissions.invalidate(&account_id).await;
+                }
+                // The login result is taken from the shared state by the instance that the frontend checks it on
+                Ok(SharedStateEvent::FrontendAuthFinished { .. }) => {}
+                Ok(SharedStateEvent::ValueChanged { .. }) => {}
+                Err(RecvError::Lagged(num_missed)) => {
+                    log::warn!("Missed {num_missed} shared state events, clearing cached frontend permissions");
+                    permissions.invalidate_all();
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    })
+}
@@ -0,0 +1,270 @@
+//! Exporting the shared world as a GeoJSON feature collection, so that it can be overlaid on community made maps.
+//!
+//! The coordinates are positions in the game world (x, y, z) and not longitude and latitude,
+//! so map tools need to use a flat coordinate system that matches their map image.
+
+use std::{collections::HashMap, fs::File, path::Path};
+
+use anyhow::Context;
+use parcel_common::api_types::{area::AreaHash, frontend::qpid_objects::QpidObjectType};
+use parcel_game_data::{Area, ConstructionPointType, GameData, Language};
+use serde::Serialize;
+
+use crate::{
+    data::database::{roads::DbRoad, Database, DatabaseConnection},
+    db::QueryError,
+};
+
+const AREAS: [AreaHash; 3] = [
+    AreaHash::EasternRegion,
+    AreaHash::CentralRegion,
+    AreaHash::WesternRegion,
+];
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+pub struct FeatureCollection {
+    pub features: Vec<Feature>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+pub struct Feature {
+    pub id: String,
+    pub geometry: Geometry,
+    pub properties: FeatureProperties,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+pub enum Geometry {
+    Point { coordinates: [f64; 3] },
+    LineString { coordinates: Vec<[f64; 3]> },
+}
+
+/// The `kind` property tells which layer the feature belongs to.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum FeatureProperties {
+    #[serde(rename_all = "camelCase")]
+    Object {
+        object_type: QpidObjectType,
+        creator_id: String,
+        creator_name: String,
+        likes: i64,
+        qpid_id: i32,
+    },
+    #[serde(rename_all = "camelCase")]
+    DeliveryPoint {
+        qpid_id: i32,
+        name: Option<String>,
+        construction_type: ConstructionPointType,
+    },
+    #[serde(rename_all = "camelCase")]
+    Road {
+        creator_id: String,
+        creator_name: String,
+        start_qpid_id: i32,
+        end_qpid_id: i32,
+        via_qpid_ids: Vec<i32>,
+        path_length: i32,
+        /// In RFC 3339 format
+        created_at: String,
+    },
+}
+
+/// Which kinds of features to include.
+#[derive(Debug, Clone, Copy)]
+pub struct Layers {
+    pub objects: bool,
+    pub delivery_points: bool,
+    pub roads: bool,
+}
+
+impl Default for Layers {
+    fn default() -> Self {
+        Self {
+            objects: true,
+            delivery_points: true,
+            roads: true,
+        }
+    }
+}
+
+/// Builds a feature collection of the objects, delivery points and roads in every area, or only in the specified area.
+///
+/// Deleted objects are not included. Roads are drawn as straight lines between the delivery points they pass through,
+/// since the actual path is only known by the game. Roads that pass through delivery points that aren't in the game data are left out.
+pub async fn build_feature_collection(
+    conn: &DatabaseConnection<'_>,
+    game_data: &GameData,
+    area: Option<AreaHash>,
+    layers: &Layers,
+) -> Result<FeatureCollection, QueryError> {
+    let mut features = Vec::new();
+
+    if layers.delivery_points {
+        let mut qpid_areas = game_data
+            .qpid_areas
+            .values()
+            .filter(|qpid_area| {
+                area.map_or(true, |area| qpid_area.metadata.area == Area::from(area))
+            })
+            .collect::<Vec<_>>();
+        qpid_areas.sort_by_key(|qpid_area| qpid_area.qpid_id);
+
+        for qpid_area in qpid_areas {
+            features.push(Feature {
+                id: qpid_area.qpid_id.to_string(),
+                geometry: Geometry::Point {
+                    coordinates: qpid_area.metadata.location.into(),
+                },
+                properties: FeatureProperties::DeliveryPoint {
+                    qpid_id: qpid_area.qpid_id,
+                    name: qpid_area.names.get(&Language::English).cloned(),
+                    construction_type: qpid_area.metadata.construction_type,
+                },
+            });
+        }
+    }
+
+    let areas = match area {
+        Some(area) => vec![area],
+        None => AREAS.to_vec(),
+    };
+
+    if layers.objects {
+        let qpid_objects = conn.qpid_objects();
+
+        for area in &areas {
+            let objects = qpid_objects.find_objects_by_area(*area, false).await?;
+            let object_ids = objects.iter().map(|o| o.id.as_ref()).collect::<Vec<_>>();
+            let vehicle_infos = qpid_objects.query_vehicle_infos(&object_ids).await?;
+            let creator_names = get_account_names(
+                conn,
+                &objects.iter().map(|o| &o.creator_id).collect::<Vec<_>>(),
+            )
+            .await?;
+
+            for object in objects {
+                let (x, y, z) = object.world_position(vehicle_infos.get(&object.id));
+                let object_type =
+                    QpidObjectType::from((object.object_type.clone(), object.sub_type.as_str()));
+
+                features.push(Feature {
+                    geometry: Geometry::Point {
+                        coordinates: [x, y, z],
+                    },
+                    properties: FeatureProperties::Object {
+                        object_type,
+                        creator_name: creator_names
+                            .get(&object.creator_id)
+                            .cloned()
+                            .unwrap_or_default(),
+                        creator_id: object.creator_id,
+                        likes: object.likes,
+                        qpid_id: object.qpid_id,
+                    },
+                    id: object.id,
+                });
+            }
+        }
+    }
+
+    if layers.roads {
+        let roads = conn.roads();
+
+        for area in &areas {
+            let area_roads = roads.list_roads(Some(*area)).await?;
+            let creator_names = get_account_names(
+                conn,
+                &area_roads
+                    .iter()
+                    .map(|r| &r.road.creator_id)
+                    .collect::<Vec<_>>(),
+            )
+            .await?;
+
+            for road in area_roads {
+                let Some(coordinates) = road_coordinates(game_data, &road) else {
+                    continue;
+                };
+
+                features.push(Feature {
+                    geometry: Geometry::LineString { coordinates },
+                    properties: FeatureProperties::Road {
+                        creator_name: creator_names
+                            .get(&road.road.creator_id)
+                            .cloned()
+                            .unwrap_or_default(),
+                        creator_id: road.road.creator_id,
+                        start_qpid_id: road.road.qpid_start_id,
+                        end_qpid_id: road.road.qpid_end_id,
+                        via_qpid_ids: road.via_qpids.iter().map(|v| v.qpid_id).collect(),
+                        path_length: road.road.length,
+                        created_at: road.road.created_at.and_utc().to_rfc3339(),
+                    },
+                    id: road.road.id,
+                });
+            }
+        }
+    }
+
+    Ok(FeatureCollection { features })
+}
+
+/// Exports every layer of the whole world to a GeoJSON file at the specified path, overwriting it if it already exists.
+pub async fn export_geojson(
+    database: &Database,
+    game_data: &GameData,
+    path: &Path,
+) -> Result<(), anyhow::Error> {
+    let conn = database.connect().await?;
+    let collection = build_feature_collection(&conn, game_data, None, &Layers::default()).await?;
+
+    let file = File::create(path)
+        .with_context(|| format!("Could not create GeoJSON file at {}", path.display()))?;
+    serde_json::to_writer(file, &collection)?;
+
+    log::info!(
+        "Exported {} features to {}",
+        collection.features.len(),
+        path.display()
+    );
+
+    Ok(())
+}
+
+/// Returns the locations of the delivery points the road passes through, in order.
+///
+/// Returns None if any of the delivery points aren't in the game data.
+fn road_coordinates(game_data: &GameData, road: &DbRoad) -> Option<Vec<[f64; 3]>> {
+    let qpid_ids = std::iter::once(road.road.qpid_start_id)
+        .chain(road.via_qpids.iter().map(|v| v.qpid_id))
+        .chain(std::iter::once(road.road.qpid_end_id));
+    let mut coordinates = Vec::<[f64; 3]>::new();
+
+    for qpid_id in qpid_ids {
+        let location = game_data.qpid_areas.get(&qpid_id)?.metadata.location.into();
+
+        if coordinates.last() != Some(&location) {
+            coordinates.push(location);
+        }
+    }
+
+    // A line string needs at least two positions
+    (coordinates.len() >= 2).then_some(coordinates)
+}
+
+async fn get_account_names(
+    conn: &DatabaseConnection<'_>,
+    account_ids: &[impl AsRef<str>],
+) -> Result<HashMap<String, String>, QueryError> {
+    Ok(conn
+        .accounts()
+        .get_by_ids(account_ids)
+        .await?
+        .into_iter()
+        .map(|account| (account.id, account.display_name))
+        .collect())
+}
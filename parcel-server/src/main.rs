@@ -4,17 +4,23 @@ mod db;
 mod embedded;
 mod endpoints;
 mod frontend;
+mod geojson;
+mod metrics;
 mod middleware;
 mod response_error;
 mod session;
 mod settings;
+#[cfg(test)]
+mod tests;
 mod whitelist;
+mod world_snapshot;
 
 use std::{
     fs::File,
     io::BufReader,
     net::IpAddr,
     path::{Path, PathBuf},
+    sync::Arc,
     time::Duration,
 };
 
@@ -24,16 +30,18 @@ use actix_web::{
     App, HttpServer,
 };
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use data::{
     database::Database,
     hash_secret::HashSecret,
     jwt_secret::JwtSecret,
     memory_cache::PersistentCache,
-    platforms::{epic::Epic, steam::Steam},
+    platforms::{epic::Epic, local::LocalAuth, steam::Steam},
+    shared_state::{DatabaseSharedState, LocalSharedState, SharedState},
 };
 use diesel::{pg::Pg, Connection, PgConnection};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use endpoints::metrics::MetricsToken;
 use fern::{
     colors::{Color, ColoredLevelConfig},
     DateBased,
@@ -44,20 +52,32 @@ use frontend::{
         SessionBlacklistCache, SessionBlacklistCacheExpiry, SessionPermissionsCache,
         BLACKLIST_CACHE_PATH,
     },
+    shared_caches,
 };
 use moka::future::CacheBuilder;
 use parcel_common::api_types::frontend::settings::SettingsValues;
 use parcel_game_data::GameData;
 use rustls::{Certificate, PrivateKey};
 use rustls_pemfile::{certs, pkcs8_private_keys};
-use settings::Settings;
-
-use crate::{data::session_store::SessionStore, middleware::wrap_errors};
+use serde::{de::DeserializeOwned, Serialize};
+use settings::{FileFormat, FilePersist, JsonFormat, Settings, SharedStatePersist};
+use whitelist::WhitelistFormat;
+
+use crate::{
+    data::session_store::SessionStore,
+    middleware::{
+        request_logger::{access_logger, RequestLogConfig, RequestLogger, REQUEST_LOG_TARGET},
+        wrap_errors,
+    },
+};
 
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
 
-pub type ServerSettings = Settings<SettingsValues, settings::JsonPersist>;
-pub type WhitelistSettings = Settings<whitelist::Whitelist, whitelist::WhitelistPersist>;
+/// Where sessions were saved before they were stored in the database.
+const LEGACY_SESSIONS_PATH: &str = "data/sessions";
+
+pub type ServerSettings = Settings<SettingsValues>;
+pub type WhitelistSettings = Settings<whitelist::Whitelist>;
 
 /// A custom server implementation for Death Stranding Directory's Cut.
 ///
@@ -98,8 +118,18 @@ pub struct Options {
     optional_encryption: bool,
 
     /// The Steam web api key used for authenticating and getting user info for Steam players. The key can be found here: https://steamcommunity.com/dev/apikey
+    ///
+    /// If unspecified then Steam players can only log in with local auth, and logging in to the frontend with Steam is disabled.
     #[arg(long = "steam-api-key", env = "STEAM_API_KEY")]
-    steam_api_key: String,
+    steam_api_key: Option<String>,
+
+    /// An optional key that players can use to log in without the server contacting Steam or Epic, for example on a LAN without internet access.
+    ///
+    /// To use it, players set their server url to `http(s)://<host>/auth/local/<key>`.
+    /// Since the identity of the player can't be verified, the display name of the player is used to identify their account.
+    /// This means that anyone who knows the key can log in as anyone else, so only share it with people you trust.
+    #[arg(long = "local-auth-key", env = "LOCAL_AUTH_KEY")]
+    local_auth_key: Option<String>,
 
     /// The optional connection string to a postgresql database. This is where all data will be stored
     ///
@@ -118,13 +148,83 @@ pub struct Options {
     #[arg(long = "gateway-url", env = "GATEWAY_URL")]
     gateway_url: Option<String>,
 
-    /// If enabled request logs will also include decrypted request body and response.
-    /// This is a lot slower than normal logging so don't use this in production
-    #[arg(long, default_value_t = false, env = "DEEP_LOGGING")]
-    deep_logging: bool,
+    /// If enabled every game api and auth request is logged as a json line to `logs/requests`, with session tokens and provider codes redacted
+    #[arg(long = "request-log", default_value_t = false, env = "REQUEST_LOG")]
+    request_log: bool,
+
+    /// The chance that the decrypted request and response bodies are included in the request log, from 0 to 1
+    #[arg(
+        long = "request-log-sample-rate",
+        default_value_t = 0.0,
+        env = "REQUEST_LOG_SAMPLE_RATE"
+    )]
+    request_log_sample_rate: f64,
+
+    /// Overrides the request log sample rate for an endpoint, for example `get_like_history=1`
+    ///
+    /// Endpoints are identified by the name of the endpoint function, the same as in the metrics. Can be specified multiple times.
+    #[arg(
+        long = "request-log-endpoint-sample-rate",
+        env = "REQUEST_LOG_ENDPOINT_SAMPLE_RATES",
+        value_delimiter = ',',
+        value_parser = parse_endpoint_sample_rate
+    )]
+    request_log_endpoint_sample_rates: Vec<(String, f64)>,
+
+    /// An optional token that is required to read the Prometheus metrics at `/metrics`.
+    ///
+    /// Scrapers send it as a bearer token in the Authorization header. If unspecified then anyone can read the metrics.
+    #[arg(long = "metrics-token", env = "METRICS_TOKEN")]
+    metrics_token: Option<String>,
+
+    /// If enabled the settings, whitelist, secrets and logged out frontend sessions are stored in the database and shared with the other server instances that use it
+    ///
+    /// Use this to run several server instances for one community. Every instance needs to be started with this and the same `--database-url`.
+    /// The settings and whitelist files in the data directory are only used to fill the database the first time.
+    #[arg(
+        long = "multi-instance",
+        default_value_t = false,
+        env = "MULTI_INSTANCE"
+    )]
+    multi_instance: bool,
 
     #[arg(long, default_value = "data/game_data.json", env = "GAME_DATA_PATH")]
     game_data_path: PathBuf,
+
+    /// Run a command against the database instead of starting the server
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Export the shared world (accounts, objects, missions, roads, likes, etc) to an archive that can be imported on another server
+    ///
+    /// Frontend accounts, rankings, settings and the whitelist are not exported.
+    ExportWorld {
+        /// Where to save the archive
+        path: PathBuf,
+    },
+    /// Import a world archive created with `export-world`
+    ///
+    /// Accounts that already exist on this server are merged with the imported accounts.
+    /// The archive must have been exported from a server with the same version as this one.
+    ImportWorld {
+        /// The archive to import
+        path: PathBuf,
+
+        /// What to do when an imported object, mission, road, etc has the same id as an existing one
+        #[arg(long, value_enum, default_value_t = world_snapshot::ConflictStrategy::Remap)]
+        on_conflict: world_snapshot::ConflictStrategy,
+    },
+    /// Export the objects, delivery points and roads of every area to a GeoJSON file, for example to display them on a community map
+    ///
+    /// The coordinates are positions in the game world and not longitude and latitude.
+    #[command(name = "export-geojson")]
+    ExportGeoJson {
+        /// Where to save the file
+        path: PathBuf,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -150,7 +250,7 @@ async fn main() -> Result<()> {
     let args = Options::parse();
 
     // Create 'logs' directory
-    std::fs::create_dir_all("logs").context("Could not create logs directory")?;
+    std::fs::create_dir_all("logs/requests").context("Could not create logs directory")?;
 
     let colors = ColoredLevelConfig::new()
         .trace(Color::White)
@@ -159,10 +259,12 @@ async fn main() -> Result<()> {
         .warn(Color::Yellow)
         .error(Color::BrightRed);
     let log_file = DateBased::new("logs/", "%Y-%m-%d.log");
+    let request_log_file = DateBased::new("logs/requests/", "%Y-%m-%d.jsonl");
     const LOG_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
     fern::Dispatch::new()
         .chain(
             fern::Dispatch::new()
+                .filter(|metadata| metadata.target() != REQUEST_LOG_TARGET)
                 .format(move |out, message, record| {
                     let now = chrono::Local::now().format(LOG_TIME_FORMAT).to_string();
                     out.finish(format_args!(
@@ -177,6 +279,7 @@ async fn main() -> Result<()> {
         )
         .chain(
             fern::Dispatch::new()
+                .filter(|metadata| metadata.target() != REQUEST_LOG_TARGET)
                 .format(move |out, message, record| {
                     let now = chrono::Local::now().format(LOG_TIME_FORMAT).to_string();
                     out.finish(format_args!(
@@ -189,6 +292,12 @@ async fn main() -> Result<()> {
                 })
                 .chain(log_file),
         )
+        .chain(
+            fern::Dispatch::new()
+                .filter(|metadata| metadata.target() == REQUEST_LOG_TARGET)
+                .format(|out, message, _| out.finish(format_args!("{}", message)))
+                .chain(request_log_file),
+        )
         .level(log::LevelFilter::Info)
         .level_for("parcel_common", log::LevelFilter::Debug)
         .level_for("parcel_server", log::LevelFilter::Debug)
@@ -201,6 +310,10 @@ async fn main() -> Result<()> {
         anyhow::bail!("Both or none of the public and private keys needs to be specified");
     }
 
+    if args.multi_instance && args.database_url.is_none() {
+        anyhow::bail!("A database url needs to be specified to run multiple server instances");
+    }
+
     // make sure data directory exists
     std::fs::create_dir_all("data").context("Could not create data directory")?;
 
@@ -208,15 +321,58 @@ async fn main() -> Result<()> {
         .await
         .context("Failed to setup and launch postgresql server")?;
 
+    if let Some(command) = &args.command {
+        let result = run_command(command, &database_url, &args.game_data_path).await;
+
+        if let Err(err) = embedded::postgresql::stop_postgresql().await {
+            log::error!("Could not gracefully stop postgresql server: {}", err);
+        }
+
+        return result;
+    }
+
+    migrate_database(&database_url).context("Could not apply pending database migrations")?;
+
     // Create potentially mutable data outside of the HttpService factory, otherwise each worker thread will not share the same data globally.
-    let steam_data = web::Data::new(
-        Steam::new(args.steam_api_key.clone()).context("Could not create steam web api client")?,
-    );
+    let steam_data = web::Data::new(match args.steam_api_key.clone() {
+        Some(steam_api_key) => {
+            Some(Steam::new(steam_api_key).context("Could not create steam web api client")?)
+        }
+        None => {
+            log::warn!("No Steam web api key specified, Steam logins are disabled");
+            None
+        }
+    });
+    let local_auth_data = web::Data::new(match args.local_auth_key.clone() {
+        Some(local_auth_key) => {
+            log::info!("Local auth is enabled");
+            Some(LocalAuth::new(local_auth_key).context("Invalid local auth key")?)
+        }
+        None => None,
+    });
+    let request_log_config = Arc::new(RequestLogConfig {
+        enabled: args.request_log,
+        body_sample_rate: args.request_log_sample_rate,
+        endpoint_sample_rates: args
+            .request_log_endpoint_sample_rates
+            .iter()
+            .cloned()
+            .collect(),
+    });
+    let metrics_token = web::Data::new(args.metrics_token.clone().map(MetricsToken));
     let epic_data = web::Data::new(Epic::new().context("Could not create epic web api client")?);
-    let session_store =
-        web::Data::new(SessionStore::load_or_create(Path::new("data/sessions")).await);
-    let session_store_clone = session_store.clone();
     let database = web::Data::new(Database::new(&database_url));
+    let session_store = web::Data::new(SessionStore::new(database.clone().into_inner()));
+    let shared_state: Arc<dyn SharedState> = if args.multi_instance {
+        log::info!("Sharing state with other server instances through the database");
+        Arc::new(
+            DatabaseSharedState::start(database.clone().into_inner(), &database_url)
+                .await
+                .context("Could not listen for changes from other server instances")?,
+        )
+    } else {
+        Arc::new(LocalSharedState::default())
+    };
     let frontend_auth_cache = web::Data::new(FrontendAuthCache::with_time_to_live_secs(
         "FrontendAuthCache",
         60 * 2,
@@ -231,19 +387,33 @@ async fn main() -> Result<()> {
         .await
         .context("Could not load session blacklist")?,
     );
+    let num_revoked_sessions =
+        shared_caches::load_revoked_sessions(&*shared_state, &session_blacklist_cache)
+            .await
+            .context("Could not load logged out frontend sessions")?;
+
+    if num_revoked_sessions > 0 {
+        log::info!("Loaded {num_revoked_sessions} frontend sessions that were logged out on other server instances");
+    }
+
     let session_blacklist_cache_clone = session_blacklist_cache.clone();
     let session_permissions_cache = web::Data::new(SessionPermissionsCache::from_builder(
         CacheBuilder::default()
             .name("SessionPermissionsCache")
             .time_to_idle(Duration::from_secs(60 * 5)),
     ));
+    let _shared_cache_updater = shared_caches::spawn_cache_updater(
+        &*shared_state,
+        session_blacklist_cache.clone().into_inner(),
+        session_permissions_cache.clone().into_inner(),
+    );
     let jwt_secret = web::Data::new(
-        JwtSecret::load_or_generate_secret()
+        JwtSecret::load_or_generate_secret(&*shared_state)
             .await
             .context("Failed to load jwt secret")?,
     );
     let hash_secret = web::Data::new(
-        HashSecret::load_or_generate_secret()
+        HashSecret::load_or_generate_secret(&*shared_state)
             .await
             .context("Failed to load hash secret")?,
     );
@@ -251,21 +421,42 @@ async fn main() -> Result<()> {
         load_gamedata_from_file(&args.game_data_path).context("Could not load game data")?,
     );
     let server_settings = web::Data::new(
-        ServerSettings::load_from_path(Path::new("data/settings.json"))
-            .await
-            .context("Could not load settings")?,
+        load_settings::<SettingsValues, JsonFormat>(
+            Path::new("data/settings.json"),
+            "settings",
+            args.multi_instance.then_some(&shared_state),
+        )
+        .await
+        .context("Could not load settings")?,
     );
     let whitelist_settings = web::Data::new(
-        WhitelistSettings::load_from_path(Path::new("data/whitelist.txt"))
-            .await
-            .context("Could not load whitelist")?,
+        load_settings::<whitelist::Whitelist, WhitelistFormat>(
+            Path::new("data/whitelist.txt"),
+            "whitelist",
+            args.multi_instance.then_some(&shared_state),
+        )
+        .await
+        .context("Could not load whitelist")?,
     );
+    let shared_state = web::Data::from(shared_state);
 
-    migrate_database(&database_url).context("Could not apply pending database migrations")?;
     create_admin_account_if_not_exists(&*database, &*hash_secret)
         .await
         .context("Could not check for or create admin account")?;
 
+    // Sessions used to be saved to a file, import them so that players don't need to log in again after updating.
+    // Players can always log in again, so failing to import them shouldn't stop the server from starting
+    match session_store
+        .import_legacy_sessions(Path::new(LEGACY_SESSIONS_PATH))
+        .await
+    {
+        Ok(0) => {}
+        Ok(num_imported_sessions) => {
+            log::info!("Imported {num_imported_sessions} sessions from {LEGACY_SESSIONS_PATH}")
+        }
+        Err(err) => log::warn!("Could not import sessions from {LEGACY_SESSIONS_PATH}: {err:#}"),
+    }
+
     let gateway_url = args.gateway_url.as_ref().map(|url| format!("{}/ds", url));
 
     if let Some(gateway_url) = gateway_url.as_ref() {
@@ -277,8 +468,11 @@ async fn main() -> Result<()> {
         log::info!("Launching server on port {} with the public gateway url being inferred from the incoming connection", args.listen_port);
     }
 
-    let mut background_job_scheduler =
-        background_jobs::create_scheduler(database.clone().into_inner()).await?;
+    let mut background_job_scheduler = background_jobs::create_scheduler(
+        database.clone().into_inner(),
+        server_settings.clone().into_inner(),
+    )
+    .await?;
     background_job_scheduler
         .start()
         .await
@@ -288,6 +482,7 @@ async fn main() -> Result<()> {
         App::new()
             .app_data(steam_data.clone())
             .app_data(epic_data.clone())
+            .app_data(local_auth_data.clone())
             .app_data(session_store.clone())
             .app_data(database.clone())
             .app_data(web::Data::new(
@@ -301,23 +496,34 @@ async fn main() -> Result<()> {
             .app_data(game_data.clone())
             .app_data(server_settings.clone())
             .app_data(whitelist_settings.clone())
+            .app_data(metrics_token.clone())
+            .app_data(shared_state.clone())
             .service(
                 actix_web::web::scope("/ds/e")
                     .configure(endpoints::configure_endpoints)
-                    .wrap(middleware::deep_logger::DeepLogger {
-                        enabled: args.deep_logging,
+                    .wrap(RequestLogger {
+                        config: request_log_config.clone(),
                     })
                     // Make sure this is last middleware so that the data is decrypted before doing anything else that interacts with the encrypted data
                     .wrap(middleware::encryption::DataEncryption {
                         optional_encryption: args.optional_encryption,
                     })
-                    .wrap(wrap_errors::WrapErrors),
+                    .wrap(wrap_errors::WrapErrors)
+                    .wrap(middleware::metrics::RequestMetrics),
             )
-            .service(endpoints::auth::auth)
-            .service(endpoints::auth::me::me)
+            .service(
+                actix_web::web::scope("/auth")
+                    .service(endpoints::auth::auth)
+                    .service(endpoints::auth::auth_local)
+                    .service(endpoints::auth::me::me)
+                    .wrap(RequestLogger {
+                        config: request_log_config.clone(),
+                    }),
+            )
+            .service(endpoints::metrics::metrics)
             .service(actix_web::web::scope("/frontend").configure(frontend::configure_endpoints))
             .wrap(NormalizePath::trim())
-            .wrap(actix_web::middleware::Logger::default())
+            .wrap(access_logger())
     });
 
     if args.cert_public_key.is_some() {
@@ -347,7 +553,6 @@ async fn main() -> Result<()> {
         scheduler_stop_failed = true;
     }
 
-    session_store_clone.save_to_file().await?;
     session_blacklist_cache_clone
         .save_to_file(Path::new(BLACKLIST_CACHE_PATH))
         .await?;
@@ -361,6 +566,57 @@ async fn main() -> Result<()> {
     result
 }
 
+async fn run_command(command: &Command, database_url: &str, game_data_path: &Path) -> Result<()> {
+    migrate_database(database_url).context("Could not apply pending database migrations")?;
+    let database = Database::new(database_url);
+
+    match command {
+        Command::ExportWorld { path } => {
+            world_snapshot::export_world(&database, path)
+                .await
+                .context("Could not export world")?;
+        }
+        Command::ImportWorld { path, on_conflict } => {
+            let summary = world_snapshot::import_world(&database, path, *on_conflict)
+                .await
+                .context("Could not import world")?;
+
+            for (table, table_summary) in &summary.tables {
+                if table_summary.skipped > 0 {
+                    log::warn!(
+                        "Skipped {} out of {} rows in {} because of conflicts or missing references",
+                        table_summary.skipped,
+                        table_summary.imported + table_summary.skipped,
+                        table
+                    );
+                }
+            }
+        }
+        Command::ExportGeoJson { path } => {
+            let game_data =
+                load_gamedata_from_file(game_data_path).context("Could not load game data")?;
+
+            geojson::export_geojson(&database, &game_data, path)
+                .await
+                .context("Could not export GeoJSON")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses an `<endpoint>=<rate>` pair for the request log.
+fn parse_endpoint_sample_rate(value: &str) -> Result<(String, f64), String> {
+    let (endpoint, rate) = value
+        .split_once('=')
+        .ok_or_else(|| format!("Expected <endpoint>=<rate>, got \"{value}\""))?;
+    let rate = rate
+        .parse::<f64>()
+        .map_err(|err| format!("Invalid sample rate \"{rate}\": {err}"))?;
+
+    Ok((endpoint.to_owned(), rate))
+}
+
 fn load_rustls_config(
     private_key_path: &Path,
     public_key_path: &Path,
@@ -407,6 +663,31 @@ fn migrate_database(database_url: &str) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+/// Loads settings from the file in the data directory, or from the shared state if it's shared with other server instances.
+///
+/// When the settings are loaded from the shared state for the first time they're copied from the file.
+async fn load_settings<TData, TFormat>(
+    file_path: &Path,
+    shared_key: &str,
+    shared_state: Option<&Arc<dyn SharedState>>,
+) -> Result<Settings<TData>, anyhow::Error>
+where
+    TData: Default + Serialize + DeserializeOwned + Send + Sync + 'static,
+    TFormat: FileFormat<TData> + 'static,
+{
+    let file_persist = FilePersist::<TFormat>::new(file_path);
+
+    match shared_state {
+        Some(shared_state) => {
+            let shared_persist = SharedStatePersist::new(shared_state.clone(), shared_key);
+            shared_persist.seed_from(&file_persist).await?;
+
+            Settings::load(shared_persist).await
+        }
+        None => Settings::load(file_persist).await,
+    }
+}
+
 fn load_gamedata_from_file(game_data_path: &Path) -> Result<GameData, anyhow::Error> {
     log::info!("Loading game data");
 
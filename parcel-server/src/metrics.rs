@@ -0,0 +1,189 @@
+//! Server metrics that are exported in the Prometheus text format at `/metrics`.
+//!
+//! The metrics are kept in a global registry so that they can be recorded from anywhere,
+//! including the database and background jobs which don't have access to the app data.
+
+use std::{collections::BTreeMap, fmt::Write, sync::Mutex, time::Duration};
+
+/// The upper bounds of the histogram buckets in seconds.
+const BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+lazy_static::lazy_static! {
+    static ref METRICS: Metrics = Metrics::default();
+}
+
+#[derive(Default)]
+struct Metrics {
+    /// Keyed by route name and http status code
+    requests: Mutex<BTreeMap<(String, u16), Histogram>>,
+    /// Keyed by the status code of the error response, for example `SV-IE`
+    game_errors: Mutex<BTreeMap<String, u64>>,
+    database_connect: Mutex<Histogram>,
+    /// Keyed by cache name and whether the lookup was a hit
+    cache_lookups: Mutex<BTreeMap<(String, bool), u64>>,
+    /// Keyed by job name and whether the job succeeded
+    background_jobs: Mutex<BTreeMap<(String, bool), u64>>,
+}
+
+#[derive(Default)]
+struct Histogram {
+    /// The number of observations that are less than or equal to the bucket with the same index
+    buckets: [u64; BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+
+        for (bucket, upper_bound) in self.buckets.iter_mut().zip(BUCKETS) {
+            if seconds <= upper_bound {
+                *bucket += 1;
+            }
+        }
+
+        self.sum += seconds;
+        self.count += 1;
+    }
+
+    fn write(&self, out: &mut String, name: &str, labels: &str) {
+        let separator = if labels.is_empty() { "" } else { "," };
+
+        for (count, upper_bound) in self.buckets.iter().zip(BUCKETS) {
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{{labels}{separator}le=\"{upper_bound}\"}} {count}"
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "{name}_bucket{{{labels}{separator}le=\"+Inf\"}} {}",
+            self.count
+        );
+        let _ = writeln!(out, "{name}_sum{{{labels}}} {}", self.sum);
+        let _ = writeln!(out, "{name}_count{{{labels}}} {}", self.count);
+    }
+}
+
+/// Records a handled game api request.
+pub fn observe_request(route: &str, status: u16, duration: Duration) {
+    METRICS
+        .requests
+        .lock()
+        .unwrap()
+        .entry((route.to_owned(), status))
+        .or_default()
+        .observe(duration);
+}
+
+/// Records an error response from the game api.
+pub fn count_game_error(status: &str) {
+    *METRICS
+        .game_errors
+        .lock()
+        .unwrap()
+        .entry(status.to_owned())
+        .or_default() += 1;
+}
+
+/// Records how long it took to get a database connection.
+pub fn observe_database_connect(duration: Duration) {
+    METRICS.database_connect.lock().unwrap().observe(duration);
+}
+
+pub fn count_cache_lookup(cache: &str, hit: bool) {
+    *METRICS
+        .cache_lookups
+        .lock()
+        .unwrap()
+        .entry((cache.to_owned(), hit))
+        .or_default() += 1;
+}
+
+pub fn count_background_job(job: &str, succeeded: bool) {
+    *METRICS
+        .background_jobs
+        .lock()
+        .unwrap()
+        .entry((job.to_owned(), succeeded))
+        .or_default() += 1;
+}
+
+/// Renders every metric in the Prometheus text format.
+///
+/// Gauges that are read from other parts of the server when scraped are passed in.
+pub fn render(active_sessions: u64) -> String {
+    let mut out = String::new();
+
+    out.push_str(
+        "# HELP parcel_game_requests_seconds Game api requests by route and http status code\n",
+    );
+    out.push_str("# TYPE parcel_game_requests_seconds histogram\n");
+    for ((route, status), histogram) in METRICS.requests.lock().unwrap().iter() {
+        let labels = format!(
+            "route=\"{}\",status=\"{}\"",
+            escape_label_value(route),
+            status
+        );
+        histogram.write(&mut out, "parcel_game_requests_seconds", &labels);
+    }
+
+    out.push_str("# HELP parcel_game_errors_total Game api error responses by status code\n");
+    out.push_str("# TYPE parcel_game_errors_total counter\n");
+    for (status, count) in METRICS.game_errors.lock().unwrap().iter() {
+        let _ = writeln!(
+            out,
+            "parcel_game_errors_total{{status=\"{}\"}} {count}",
+            escape_label_value(status)
+        );
+    }
+
+    out.push_str("# HELP parcel_active_sessions Game sessions that haven't expired\n");
+    out.push_str("# TYPE parcel_active_sessions gauge\n");
+    let _ = writeln!(out, "parcel_active_sessions {active_sessions}");
+
+    out.push_str(
+        "# HELP parcel_database_connect_seconds Time spent waiting for a database connection\n",
+    );
+    out.push_str("# TYPE parcel_database_connect_seconds histogram\n");
+    METRICS
+        .database_connect
+        .lock()
+        .unwrap()
+        .write(&mut out, "parcel_database_connect_seconds", "");
+
+    out.push_str("# HELP parcel_cache_lookups_total Cache lookups by cache and result\n");
+    out.push_str("# TYPE parcel_cache_lookups_total counter\n");
+    for ((cache, hit), count) in METRICS.cache_lookups.lock().unwrap().iter() {
+        let _ = writeln!(
+            out,
+            "parcel_cache_lookups_total{{cache=\"{}\",result=\"{}\"}} {count}",
+            escape_label_value(cache),
+            if *hit { "hit" } else { "miss" }
+        );
+    }
+
+    out.push_str("# HELP parcel_background_jobs_total Background job runs by job and outcome\n");
+    out.push_str("# TYPE parcel_background_jobs_total counter\n");
+    for ((job, succeeded), count) in METRICS.background_jobs.lock().unwrap().iter() {
+        let _ = writeln!(
+            out,
+            "parcel_background_jobs_total{{job=\"{}\",outcome=\"{}\"}} {count}",
+            escape_label_value(job),
+            if *succeeded { "success" } else { "failure" }
+        );
+    }
+
+    out
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
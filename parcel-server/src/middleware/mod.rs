@@ -1,3 +1,4 @@
-pub mod deep_logger;
 pub mod encryption;
+pub mod metrics;
+pub mod request_logger;
 pub mod wrap_errors;
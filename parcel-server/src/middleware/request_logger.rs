@@ -0,0 +1,334 @@
+use std::{
+    collections::HashMap,
+    future::{ready, Ready},
+    rc::Rc,
+    sync::Arc,
+    time::Instant,
+};
+
+use actix_http::{
+    body::{BoxBody, EitherBody, MessageBody},
+    header::{HeaderName, HeaderValue},
+    HttpMessage,
+};
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::REFERER,
+    middleware::Logger,
+    web::{Buf, Bytes},
+    Error,
+};
+use futures_util::{future::LocalBoxFuture, StreamExt};
+use parcel_common::rand::generate_string;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::session::Session;
+
+/// The log target that request log entries are written to. Entries with this target are written to their own files.
+pub const REQUEST_LOG_TARGET: &str = "request_log";
+
+/// The value that redacted query parameters and json fields are replaced with.
+const REDACTED: &str = "[redacted]";
+
+/// Query parameters and json fields with names that end with any of these are redacted, ignoring case.
+///
+/// This covers session tokens, provider codes from Steam and Epic, and the local auth key.
+const REDACTED_KEYS: [&str; 5] = ["token", "code", "key", "password", "secret"];
+
+/// The same as the default access log format, except that the request line and referer have sensitive values redacted.
+const ACCESS_LOG_FORMAT: &str =
+    r#"%a "%{request_line}xi" %s %b "%{referer}xi" "%{User-Agent}i" %T"#;
+
+/// The path prefix of the endpoint that has the local auth key as a path parameter.
+const LOCAL_AUTH_PATH: &str = "/auth/local/";
+
+#[derive(Debug, Clone, Default)]
+pub struct RequestLogConfig {
+    pub enabled: bool,
+    /// The chance that the request and response bodies are logged, from 0 to 1
+    pub body_sample_rate: f64,
+    /// Overrides `body_sample_rate` for specific endpoints, keyed by the name of the endpoint function
+    pub endpoint_sample_rates: HashMap<String, f64>,
+}
+
+impl RequestLogConfig {
+    fn sample_rate(&self, endpoint: Option<&str>) -> f64 {
+        endpoint
+            .and_then(|endpoint| self.endpoint_sample_rates.get(endpoint))
+            .copied()
+            .unwrap_or(self.body_sample_rate)
+    }
+
+    /// Returns true if the bodies of any endpoint can be sampled, in which case the request body needs to be read before the endpoint is known.
+    fn samples_bodies(&self) -> bool {
+        self.body_sample_rate > 0.0 || self.endpoint_sample_rates.values().any(|rate| *rate > 0.0)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RequestLogEntry<'a> {
+    /// In RFC 3339 format
+    timestamp: String,
+    request_id: &'a str,
+    account_id: Option<&'a str>,
+    method: &'a str,
+    /// The route pattern if the request matched an endpoint, so that path parameters such as the local auth key are left out
+    path: &'a str,
+    query: Option<String>,
+    endpoint: Option<&'a str>,
+    status: u16,
+    duration_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_body: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_body: Option<Value>,
+}
+
+/// Writes a json line for every request to the request log, with sensitive values redacted.
+///
+/// Request and response bodies are only included for a sample of the requests. Since the game api encrypts the bodies
+/// this needs to be wrapped by the encryption middleware to log the decrypted bodies.
+pub struct RequestLogger {
+    pub config: Arc<RequestLogConfig>,
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequestLogger
+where
+    S: 'static + Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequestLoggerMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestLoggerMiddleware {
+            service: Rc::new(service),
+            config: self.config.clone(),
+        }))
+    }
+}
+
+pub struct RequestLoggerMiddleware<S> {
+    service: Rc<S>,
+    config: Arc<RequestLogConfig>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestLoggerMiddleware<S>
+where
+    S: 'static + Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        if !self.config.enabled {
+            let fut = self.service.call(req);
+            return Box::pin(async move { Ok(fut.await?.map_into_left_body()) });
+        }
+
+        let svc = self.service.clone();
+        let config = self.config.clone();
+
+        Box::pin(async move {
+            let start = Instant::now();
+            let request_id = generate_string(16, b"0123456789abcdef");
+
+            let request_body = if config.samples_bodies() {
+                let body = get_request_body(&mut req).await?;
+                req.set_payload(create_payload(body.clone()).into());
+                Some(body)
+            } else {
+                None
+            };
+
+            let mut res = svc.call(req).await?;
+            let duration = start.elapsed();
+
+            res.headers_mut().insert(
+                HeaderName::from_static("x-request-id"),
+                HeaderValue::from_str(&request_id).expect("Request id should be a valid header"),
+            );
+
+            let endpoint = res.request().match_name().map(str::to_owned);
+            let sample_rate = config.sample_rate(endpoint.as_deref());
+            let sampled = request_body.is_some() && rand::random::<f64>() < sample_rate;
+
+            let (res, response_body) = if sampled {
+                let (req, res) = res.into_parts();
+                let (res, body) = res.into_parts();
+
+                match body.try_into_bytes() {
+                    Ok(bytes) => {
+                        let response_body = redacted_json_body(&bytes);
+                        let res = res.set_body(BoxBody::new(bytes)).map_into_right_body();
+
+                        (ServiceResponse::new(req, res), response_body)
+                    }
+                    Err(body) => (
+                        ServiceResponse::new(req, res.set_body(body).map_into_left_body()),
+                        None,
+                    ),
+                }
+            } else {
+                (res.map_into_left_body(), None)
+            };
+
+            {
+                let http_request = res.request();
+                let extensions = http_request.extensions();
+                let pattern = http_request.match_pattern();
+                let entry = RequestLogEntry {
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    request_id: &request_id,
+                    account_id: extensions
+                        .get::<Session>()
+                        .map(|session| session.account_id.as_str()),
+                    method: http_request.method().as_str(),
+                    path: pattern.as_deref().unwrap_or(http_request.path()),
+                    query: Some(http_request.query_string())
+                        .filter(|query| !query.is_empty())
+                        .map(redact_query),
+                    endpoint: endpoint.as_deref(),
+                    status: res.status().as_u16(),
+                    duration_ms: duration.as_secs_f64() * 1000.0,
+                    request_body: request_body
+                        .filter(|_| sampled)
+                        .and_then(|body| redacted_json_body(&body)),
+                    response_body,
+                };
+
+                match serde_json::to_string(&entry) {
+                    Ok(line) => log::info!(target: REQUEST_LOG_TARGET, "{line}"),
+                    Err(err) => log::error!("Could not serialize request log entry: {err}"),
+                }
+            }
+
+            Ok(res)
+        })
+    }
+}
+
+/// Parses the body as json and redacts sensitive fields.
+///
+/// Returns None if the body is empty. Bodies that aren't json are replaced with a description since they could contain anything.
+pub fn redacted_json_body(body: &[u8]) -> Option<Value> {
+    if body.is_empty() {
+        return None;
+    }
+
+    match serde_json::from_slice::<Value>(body) {
+        Ok(mut json) => {
+            redact_json(&mut json);
+            Some(json)
+        }
+        Err(_) => Some(Value::String(format!(
+            "[{} bytes of non json data]",
+            body.len()
+        ))),
+    }
+}
+
+/// Replaces the values of sensitive fields in the json, including in nested objects and arrays.
+pub fn redact_json(json: &mut Value) {
+    match json {
+        Value::Object(fields) => {
+            for (key, value) in fields.iter_mut() {
+                if is_redacted_key(key) {
+                    *value = Value::String(REDACTED.into());
+                } else {
+                    redact_json(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+/// Replaces the values of sensitive query parameters.
+pub fn redact_query(query: &str) -> String {
+    query
+        .split('&')
+        .map(|parameter| match parameter.split_once('=') {
+            Some((key, _)) if is_redacted_key(key) => format!("{key}={REDACTED}"),
+            _ => parameter.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Creates the access logger, which logs every request in the common log format with the same values redacted as in the request log.
+pub fn access_logger() -> Logger {
+    Logger::new(ACCESS_LOG_FORMAT)
+        .custom_request_replace("request_line", redacted_request_line)
+        .custom_request_replace("referer", |req| {
+            req.headers()
+                .get(REFERER)
+                .and_then(|referer| referer.to_str().ok())
+                .map(redact_uri)
+                .unwrap_or_else(|| "-".to_owned())
+        })
+}
+
+/// Returns the method, uri and http version of the request, with the local auth key and sensitive query parameters redacted.
+pub fn redacted_request_line(req: &ServiceRequest) -> String {
+    format!(
+        "{} {} {:?}",
+        req.method(),
+        redact_uri(&req.uri().to_string()),
+        req.version()
+    )
+}
+
+/// Replaces the local auth key in the path and the values of sensitive query parameters.
+pub fn redact_uri(uri: &str) -> String {
+    let (path, query) = match uri.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (uri, None),
+    };
+
+    // The path isn't normalized yet when the access log is written, so the prefix can be anywhere in it
+    let mut result = match path.find(LOCAL_AUTH_PATH) {
+        Some(index) => format!("{}{REDACTED}", &path[..index + LOCAL_AUTH_PATH.len()]),
+        None => path.to_owned(),
+    };
+
+    if let Some(query) = query {
+        result.push('?');
+        result.push_str(&redact_query(query));
+    }
+
+    result
+}
+
+fn is_redacted_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    REDACTED_KEYS.iter().any(|redacted| key.ends_with(redacted))
+}
+
+async fn get_request_body(req: &mut ServiceRequest) -> Result<Bytes, Error> {
+    let mut payload = req.take_payload();
+    let mut body = Vec::new();
+
+    while let Some(chunk) = payload.next().await {
+        body.extend_from_slice(chunk?.chunk());
+    }
+
+    Ok(body.into())
+}
+
+fn create_payload(bytes: Bytes) -> actix_http::h1::Payload {
+    let (_, mut payload) = actix_http::h1::Payload::create(true);
+    payload.unread_data(bytes);
+    payload
+}
@@ -10,7 +10,7 @@ use actix_web::{
 };
 use futures_util::future::LocalBoxFuture;
 
-use crate::response_error::CommonError;
+use crate::{metrics, response_error::CommonError};
 
 // There are two steps in middleware processing.
 // 1. Middleware initialization, middleware factory gets called with
@@ -69,7 +69,14 @@ where
                 let new_response = match bytes {
                     Ok(bytes) => {
                         // try parse as CommonError, if it fails we should replace the original error as it might contain sensitive information
-                        if serde_json::from_slice::<CommonError>(&bytes).is_err() {
+                        let common_error = serde_json::from_slice::<CommonError>(&bytes);
+                        metrics::count_game_error(
+                            common_error
+                                .as_ref()
+                                .map_or("SV-IE", |err| err.status.as_str()),
+                        );
+
+                        if common_error.is_err() {
                             log::debug!(
                                 "Wrapping error response. Status = {}, Error = {:#?}",
                                 res.status(),
@@ -1,14 +1,16 @@
 use std::{collections::HashMap, fmt::Display};
 
 use actix_http::{header::Header, StatusCode};
-use actix_web::{web::Data, FromRequest};
+use actix_web::{web::Data, FromRequest, HttpMessage};
 use actix_web_httpauth::headers::authorization::{Authorization, Bearer};
+use chrono::{NaiveDateTime, Utc};
 use futures_util::future::LocalBoxFuture;
 use parcel_common::api_types::auth::Provider;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::{
     data::session_store::SessionStore,
+    db::models::game_session::GameSession,
     response_error::{impl_response_error, CommonResponseError},
 };
 
@@ -18,16 +20,27 @@ pub struct Session {
     pub provider_id: String,
     pub account_id: String,
     pub token: String,
+    pub logged_in_at: NaiveDateTime,
+    /// The last time the session was used to make a request.
+    ///
+    /// This is only updated once every [`LAST_SEEN_RESOLUTION_SECS`] to avoid writing to the store on every request.
+    pub last_seen_at: NaiveDateTime,
     values: HashMap<String, String>,
 }
 
+pub const LAST_SEEN_RESOLUTION_SECS: i64 = 60;
+
 impl Session {
     pub fn new(provider: Provider, provider_id: &str, account_id: &str, token: String) -> Self {
+        let now = Utc::now().naive_utc();
+
         Self {
             provider,
             provider_id: provider_id.into(),
             account_id: account_id.into(),
             token,
+            logged_in_at: now,
+            last_seen_at: now,
             values: HashMap::new(),
         }
     }
@@ -70,12 +83,42 @@ impl Session {
     pub fn set_raw(&mut self, key: &str, val: &str) {
         self.values.insert(key.into(), val.into());
     }
+
+    /// Converts the session to a database row that expires at the specified date.
+    pub fn to_game_session(&self, expires_at: NaiveDateTime) -> GameSession {
+        GameSession {
+            token: self.token.clone(),
+            provider: self.provider,
+            provider_id: self.provider_id.clone(),
+            account_id: self.account_id.clone(),
+            logged_in_at: self.logged_in_at,
+            last_seen_at: self.last_seen_at,
+            expires_at,
+            data: serde_json::to_value(&self.values)
+                .expect("String values should always be serializable"),
+        }
+    }
+}
+
+impl From<GameSession> for Session {
+    fn from(value: GameSession) -> Self {
+        Self {
+            provider: value.provider,
+            provider_id: value.provider_id,
+            account_id: value.account_id,
+            token: value.token,
+            logged_in_at: value.logged_in_at,
+            last_seen_at: value.last_seen_at,
+            values: serde_json::from_value(value.data).unwrap_or_default(),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum FromRequestError {
     UnknownToken,
     Unauthorized,
+    InternalError(anyhow::Error),
 }
 
 impl Display for FromRequestError {
@@ -83,6 +126,9 @@ impl Display for FromRequestError {
         match self {
             FromRequestError::UnknownToken => write!(f, "Unknown token"),
             FromRequestError::Unauthorized => write!(f, "No token specified"),
+            FromRequestError::InternalError(err) => {
+                write!(f, "Could not load session: {}", err)
+            }
         }
     }
 }
@@ -93,6 +139,7 @@ impl CommonResponseError for FromRequestError {
         match self {
             FromRequestError::UnknownToken => "AU-UT",
             FromRequestError::Unauthorized => "AU-UA",
+            FromRequestError::InternalError(_) => "SV-IE",
         }
         .into()
     }
@@ -102,6 +149,7 @@ impl CommonResponseError for FromRequestError {
             FromRequestError::UnknownToken | FromRequestError::Unauthorized => {
                 StatusCode::UNAUTHORIZED
             }
+            FromRequestError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
@@ -109,6 +157,7 @@ impl CommonResponseError for FromRequestError {
         match self {
             FromRequestError::UnknownToken => "bad token",
             FromRequestError::Unauthorized => "no permission",
+            FromRequestError::InternalError(_) => "internal error",
         }
         .into()
     }
@@ -129,10 +178,28 @@ impl FromRequest for Session {
             };
 
             let session_store = req.app_data::<Data<SessionStore>>().unwrap();
-            let session = session_store.load_session(&token).await;
+            let session = session_store
+                .load_session(&token)
+                .await
+                .map_err(FromRequestError::InternalError)?;
 
             match session {
-                Some(session) => Ok(session),
+                Some(mut session) => {
+                    let now = Utc::now().naive_utc();
+
+                    if (now - session.last_seen_at).num_seconds() >= LAST_SEEN_RESOLUTION_SECS {
+                        session.last_seen_at = now;
+                        session_store
+                            .update_last_seen(&token, now)
+                            .await
+                            .map_err(FromRequestError::InternalError)?;
+                    }
+
+                    // Lets middleware such as the request logger know who made the request
+                    req.extensions_mut().insert(session.clone());
+
+                    Ok(session)
+                }
                 None => Err(FromRequestError::UnknownToken),
             }
         })
@@ -1,15 +1,38 @@
 use std::{
+    any::Any,
     marker::PhantomData,
     path::{Path, PathBuf},
     sync::Arc,
 };
 
-use notify::{EventHandler, RecommendedWatcher, Watcher};
+use notify::{EventHandler, Watcher};
 use serde::{de::DeserializeOwned, Serialize};
-use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use tokio::sync::{broadcast::error::RecvError, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
+use crate::data::shared_state::{SharedState, SharedStateEvent};
+
+/// Where settings are loaded from and saved to.
+#[async_trait::async_trait]
+pub trait Persist<TData>: Send + Sync
+where
+    TData: Send + Sync,
+{
+    /// Returns None if the settings haven't been saved yet.
+    async fn read(&self) -> Result<Option<TData>, anyhow::Error>;
+    async fn write(&self, data: &TData) -> Result<(), anyhow::Error>;
+
+    /// Sets `is_dirty` to true whenever the settings are changed from somewhere else, for example if the file is edited manually.
+    ///
+    /// It stops watching for changes when the returned value is dropped.
+    fn watch(
+        &self,
+        is_dirty: Arc<RwLock<bool>>,
+    ) -> Result<Box<dyn Any + Send + Sync>, anyhow::Error>;
+}
+
+/// How settings are stored in a file.
 #[async_trait::async_trait]
-pub trait Persist<TData>
+pub trait FileFormat<TData>
 where
     TData: Send + Sync,
 {
@@ -17,62 +40,63 @@ where
     async fn read_file(file_path: &Path) -> Result<TData, anyhow::Error>;
 }
 
-pub struct Settings<TData, TDataPersist> {
+pub struct Settings<TData>
+where
+    TData: Send + Sync,
+{
     lock: RwLock<TData>,
-    file_path: PathBuf,
+    persist: Box<dyn Persist<TData>>,
     /// If true, the settings will be reloaded the next time `read()` is called.
     is_dirty: Arc<RwLock<bool>>,
-    _watcher: RecommendedWatcher,
-    _data_persist: PhantomData<TDataPersist>,
+    _watcher: Box<dyn Any + Send + Sync>,
 }
 
-impl<TData, TPersist> Settings<TData, TPersist>
+impl<TData> Settings<TData>
 where
     TData: Default + Send + Sync,
-    TPersist: Persist<TData>,
 {
-    /// Loads settings from the file at the specified path.
-    /// If the file doesn't exist the default settings are saved to the path and then returned.
+    /// Loads the settings.
+    /// If they haven't been saved yet the default settings are saved and then returned.
     ///
-    /// If the file is edited manually the settings will be reloaded automatically the next time `read()` is called.
-    pub async fn load_from_path(file_path: &Path) -> Result<Self, anyhow::Error> {
-        let settings = if file_path.try_exists()? {
-            log::debug!("Loading settings from file: {}", file_path.display());
-            TPersist::read_file(file_path).await?
-        } else {
-            log::warn!("Settings file not found, using default settings");
-            let values = TData::default();
-
-            TPersist::write_file(file_path, &values).await?;
-
-            values
+    /// If the settings are changed from somewhere else they will be reloaded automatically the next time `read()` is called.
+    pub async fn load(persist: impl Persist<TData> + 'static) -> Result<Self, anyhow::Error> {
+        let settings = match persist.read().await? {
+            Some(settings) => settings,
+            None => {
+                log::warn!("No saved settings found, using default settings");
+                let values = TData::default();
+
+                persist.write(&values).await?;
+
+                values
+            }
         };
 
         let is_dirty = Arc::new(RwLock::new(false));
-        let mut watcher = notify::recommended_watcher(SetBoolTrueEventHandler(is_dirty.clone()))?;
-
-        watcher.watch(file_path, notify::RecursiveMode::NonRecursive)?;
+        let watcher = persist.watch(is_dirty.clone())?;
 
         Ok(Self {
             lock: RwLock::new(settings),
-            file_path: file_path.to_owned(),
+            persist: Box::new(persist),
             is_dirty,
             _watcher: watcher,
-            _data_persist: PhantomData,
         })
     }
 
     /// Returns a read guard over the settings.
-    /// It does not load settings from the file again unless the file was changed since the last time `read()` or `write()` was called.
+    /// It does not load the settings again unless they were changed since the last time `read()` or `write()` was called.
     pub async fn read(&self) -> RwLockReadGuard<TData> {
         if *self.is_dirty.read().await {
             log::debug!("Reloading settings");
 
             let mut write_guard = self.lock.write().await;
 
-            match TPersist::read_file(&self.file_path).await {
+            match self.persist.read().await {
                 Ok(settings) => {
-                    *write_guard = settings;
+                    if let Some(settings) = settings {
+                        *write_guard = settings;
+                    }
+
                     *self.is_dirty.write().await = false;
                 }
                 Err(err) => {
@@ -85,7 +109,7 @@ where
     }
 
     /// Returns a callback in which you can change the setting fields.
-    /// After the callback finishes the changes will be written back to the file.
+    /// After the callback finishes the changes will be saved.
     pub async fn write<F>(&self, callback: F) -> Result<(), anyhow::Error>
     where
         F: FnOnce(&mut RwLockWriteGuard<'_, TData>),
@@ -93,17 +117,152 @@ where
         let mut settings = self.lock.write().await;
         callback(&mut settings);
 
-        TPersist::write_file(&self.file_path, &*settings).await?;
+        self.persist.write(&*settings).await?;
         *self.is_dirty.write().await = false;
 
         Ok(())
     }
 }
 
-pub struct JsonPersist;
+/// Saves settings to a file in the specified format.
+pub struct FilePersist<TFormat> {
+    file_path: PathBuf,
+    _format: PhantomData<fn() -> TFormat>,
+}
+
+impl<TFormat> FilePersist<TFormat> {
+    pub fn new(file_path: &Path) -> Self {
+        Self {
+            file_path: file_path.to_owned(),
+            _format: PhantomData,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<TData, TFormat> Persist<TData> for FilePersist<TFormat>
+where
+    TData: Send + Sync,
+    TFormat: FileFormat<TData>,
+{
+    async fn read(&self) -> Result<Option<TData>, anyhow::Error> {
+        if !self.file_path.try_exists()? {
+            return Ok(None);
+        }
+
+        log::debug!("Loading settings from file: {}", self.file_path.display());
+        Ok(Some(TFormat::read_file(&self.file_path).await?))
+    }
+
+    async fn write(&self, data: &TData) -> Result<(), anyhow::Error> {
+        TFormat::write_file(&self.file_path, data).await
+    }
+
+    fn watch(
+        &self,
+        is_dirty: Arc<RwLock<bool>>,
+    ) -> Result<Box<dyn Any + Send + Sync>, anyhow::Error> {
+        let mut watcher = notify::recommended_watcher(SetBoolTrueEventHandler(is_dirty))?;
+        watcher.watch(&self.file_path, notify::RecursiveMode::NonRecursive)?;
+
+        Ok(Box::new(watcher))
+    }
+}
+
+/// Saves settings as json in the shared state, so that every server instance uses the same settings.
+pub struct SharedStatePersist {
+    shared_state: Arc<dyn SharedState>,
+    key: String,
+}
+
+impl SharedStatePersist {
+    pub fn new(shared_state: Arc<dyn SharedState>, key: &str) -> Self {
+        Self {
+            shared_state,
+            key: key.to_owned(),
+        }
+    }
+
+    /// Copies the settings from `other` if there are no settings in the shared state yet.
+    ///
+    /// Used to keep the existing settings file when a server starts sharing its settings with other instances.
+    pub async fn seed_from<TData>(&self, other: &dyn Persist<TData>) -> Result<(), anyhow::Error>
+    where
+        TData: Serialize + DeserializeOwned + Send + Sync,
+    {
+        if self.shared_state.get(&self.key).await?.is_some() {
+            return Ok(());
+        }
+
+        if let Some(data) = other.read().await? {
+            log::info!(
+                "Copying local settings to the shared \"{}\" settings",
+                self.key
+            );
+            self.shared_state
+                .get_or_insert(&self.key, serde_json::to_value(&data)?)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<TData> Persist<TData> for SharedStatePersist
+where
+    TData: Serialize + DeserializeOwned + Send + Sync,
+{
+    async fn read(&self) -> Result<Option<TData>, anyhow::Error> {
+        match self.shared_state.get(&self.key).await? {
+            Some(value) => Ok(Some(serde_json::from_value(value)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn write(&self, data: &TData) -> Result<(), anyhow::Error> {
+        self.shared_state
+            .set(&self.key, serde_json::to_value(data)?, None)
+            .await?;
+        self.shared_state
+            .publish(&SharedStateEvent::ValueChanged {
+                key: self.key.clone(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    fn watch(
+        &self,
+        is_dirty: Arc<RwLock<bool>>,
+    ) -> Result<Box<dyn Any + Send + Sync>, anyhow::Error> {
+        let mut events = self.shared_state.subscribe();
+        let key = self.key.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(SharedStateEvent::ValueChanged { key: changed_key })
+                        if changed_key == key =>
+                    {
+                        *is_dirty.write().await = true;
+                    }
+                    Ok(_) => {}
+                    // Reload to be safe since the missed events could have changed the settings
+                    Err(RecvError::Lagged(_)) => *is_dirty.write().await = true,
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+
+        Ok(Box::new(AbortOnDrop(task)))
+    }
+}
+
+pub struct JsonFormat;
 
 #[async_trait::async_trait]
-impl<TData> Persist<TData> for JsonPersist
+impl<TData> FileFormat<TData> for JsonFormat
 where
     TData: Serialize + DeserializeOwned + Send + Sync,
 {
@@ -136,3 +295,11 @@ impl EventHandler for SetBoolTrueEventHandler {
         }
     }
 }
+
+struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
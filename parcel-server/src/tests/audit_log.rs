@@ -0,0 +1,107 @@
+use parcel_common::api_types::{
+    frontend::{audit_log::AuditAction, settings::RetentionSettings},
+    object::Object,
+    requests::delete_object::{DeleteObjectRequest, DeleteObjectResponse},
+};
+
+use crate::{
+    background_jobs::apply_retention_rules::RetentionCandidates,
+    data::database::audit_log::AuditLogFilter,
+};
+
+use super::{call_game_api, likes::create_postbox_request, TestServer};
+
+#[actix_web::test]
+async fn deleting_an_object_is_recorded() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let creator = server.login("Creator").await.unwrap();
+    let visitor = server.login("Visitor").await.unwrap();
+
+    let object: Object = call_game_api(
+        &app,
+        Some(&creator),
+        "createObject",
+        &create_postbox_request(),
+    )
+    .await
+    .unwrap();
+
+    for player in [&visitor, &creator] {
+        call_game_api::<_, _, _, DeleteObjectResponse>(
+            &app,
+            Some(player),
+            "deleteObject",
+            &DeleteObjectRequest {
+                object_id: object.object_id.clone(),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let conn = server.database.connect().await.unwrap();
+    let audit_log = conn.audit_log();
+    let (entries, total) = audit_log
+        .list(
+            &AuditLogFilter {
+                action: Some(AuditAction::DeleteObject),
+                target_id: Some(&object.object_id),
+                ..Default::default()
+            },
+            0,
+            10,
+        )
+        .await
+        .unwrap();
+
+    // Newest first, and only the creator's deletion hides the object from everyone
+    assert_eq!(total, 2);
+    assert_eq!(
+        entries[0].game_actor_id.as_deref(),
+        Some(creator.account_id.as_str())
+    );
+    assert_eq!(entries[0].after.as_ref().unwrap()["isDeleted"], true);
+    assert_eq!(
+        entries[1].game_actor_id.as_deref(),
+        Some(visitor.account_id.as_str())
+    );
+    assert_eq!(entries[1].after.as_ref().unwrap()["isDeleted"], false);
+
+    let (entries, total) = audit_log
+        .list(
+            &AuditLogFilter {
+                game_actor_id: Some(&visitor.account_id),
+                ..Default::default()
+            },
+            0,
+            10,
+        )
+        .await
+        .unwrap();
+    assert_eq!(total, 1);
+    assert_eq!(entries.len(), 1);
+
+    // Entries are only removed by the retention rules once they're old enough
+    let rules = RetentionSettings {
+        delete_audit_log_after_days: Some(1),
+        ..Default::default()
+    };
+    let report = RetentionCandidates::find(&conn, &rules)
+        .await
+        .unwrap()
+        .report();
+    assert_eq!(report.audit_log_entries, 0);
+
+    let rules = RetentionSettings {
+        delete_audit_log_after_days: Some(0),
+        ..Default::default()
+    };
+    let report = RetentionCandidates::find(&conn, &rules)
+        .await
+        .unwrap()
+        .remove(&conn, None)
+        .await
+        .unwrap();
+    assert_eq!(report.audit_log_entries, 2);
+}
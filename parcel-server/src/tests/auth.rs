@@ -0,0 +1,315 @@
+use actix_http::Request;
+use actix_web::{
+    body::MessageBody,
+    dev::{Service, ServiceResponse},
+    test, web,
+};
+use chrono::{Duration, Utc};
+use parcel_common::api_types::{
+    auth::{AuthResponse, Provider},
+    frontend::{audit_log::AuditAction, auth::FrontendPermissions},
+    requests::get_like_history::{GetLikeHistoryRequest, GetLikeHistoryResponse},
+};
+use serde_json::{json, Value};
+
+use crate::{
+    data::database::audit_log::AuditLogFilter, db::models::ban::NewBan,
+    response_error::CommonError, session::Session,
+};
+
+use super::{call_frontend_api, call_game_api, TestPlayer, TestServer, LOCAL_AUTH_KEY};
+
+async fn get_auth<S, B>(app: &S, uri: &str) -> Result<AuthResponse, CommonError>
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let response = test::call_service(app, test::TestRequest::get().uri(uri).to_request()).await;
+
+    if response.status().is_success() {
+        Ok(test::read_body_json(response).await)
+    } else {
+        Err(test::read_body_json(response).await)
+    }
+}
+
+#[actix_web::test]
+async fn local_provider_logs_in_with_key() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+
+    let response = get_auth(
+        &app,
+        &format!("/auth/ds?provider=local&display_name=Sam&code={LOCAL_AUTH_KEY}"),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(response.user.provider, Provider::Local);
+    assert_eq!(response.user.display_name, "Sam");
+
+    // The session token should be usable with the game api
+    let player = TestPlayer {
+        account_id: response.user.id.clone(),
+        token: response.session.token,
+    };
+    call_game_api::<_, _, _, GetLikeHistoryResponse>(
+        &app,
+        Some(&player),
+        "getLikeHistory",
+        &GetLikeHistoryRequest {
+            since: 0,
+            account_id: "".into(),
+        },
+    )
+    .await
+    .unwrap();
+
+    // Logging in through the url with the key should end up on the same account,
+    // but respond with the provider the game is running on
+    let response = get_auth(
+        &app,
+        &format!("/auth/local/{LOCAL_AUTH_KEY}?provider=steam&display_name=Sam&code=ticket"),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(response.user.provider, Provider::Steam);
+    assert_eq!(response.user.id, player.account_id);
+}
+
+#[actix_web::test]
+async fn local_provider_rejects_invalid_key() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+
+    let error = get_auth(&app, "/auth/ds?provider=local&display_name=Sam&code=wrong")
+        .await
+        .unwrap_err();
+    assert_eq!(error.status, "AU-IC");
+
+    let error = get_auth(
+        &app,
+        "/auth/local/wrong?provider=steam&display_name=Sam&code=ticket",
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(error.status, "AU-IC");
+}
+
+#[actix_web::test]
+async fn steam_provider_logs_in_with_verified_ticket() {
+    let server = TestServer::start().await.unwrap();
+
+    let first = server.login("Sam").await.unwrap();
+    let second = server.login("Sam").await.unwrap();
+    let other = server.login("Alex").await.unwrap();
+
+    // Logging in again uses the same account but starts a new session
+    assert_eq!(first.account_id, second.account_id);
+    assert_ne!(first.token, second.token);
+    assert_ne!(first.account_id, other.account_id);
+
+    let conn = server.database.connect().await.unwrap();
+    let accounts = conn
+        .accounts()
+        .get_by_ids(&[&first.account_id])
+        .await
+        .unwrap();
+    assert_eq!(accounts[0].provider, Provider::Steam);
+    assert_eq!(accounts[0].display_name, "Sam");
+}
+
+#[actix_web::test]
+async fn invalid_steam_ticket_is_rejected() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+
+    let error = get_auth(
+        &app,
+        "/auth/ds?provider=steam&display_name=Sam&code=not_a_ticket",
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(error.status, "AU-IC");
+}
+
+#[actix_web::test]
+async fn disabled_steam_provider_is_rejected() {
+    let mut server = TestServer::start().await.unwrap();
+    server.steam = web::Data::new(None);
+    let app = server.init_app().await;
+
+    let error = get_auth(&app, "/auth/ds?provider=steam&display_name=Sam&code=ticket")
+        .await
+        .unwrap_err();
+    assert_eq!(error.status, "AU-PD");
+}
+
+#[actix_web::test]
+async fn banned_account_is_rejected_until_lifted() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let uri = format!("/auth/ds?provider=local&display_name=Sam&code={LOCAL_AUTH_KEY}");
+
+    let response = get_auth(&app, &uri).await.unwrap();
+    let conn = server.database.connect().await.unwrap();
+    let account = conn
+        .accounts()
+        .get_by_ids(&[&response.user.id])
+        .await
+        .unwrap()
+        .remove(0);
+    let now = Utc::now().naive_utc();
+
+    // Expired bans don't apply
+    let expired = now - Duration::hours(1);
+    conn.bans()
+        .create(&NewBan {
+            account_id: Some(&account.id),
+            provider: account.provider,
+            provider_id: &account.provider_id,
+            reason: "expired",
+            issued_by: None,
+            created_at: &(now - Duration::days(1)),
+            expires_at: Some(&expired),
+        })
+        .await
+        .unwrap();
+    get_auth(&app, &uri).await.unwrap();
+
+    // A ban on the provider id applies even if it wasn't issued for the account
+    let ban = conn
+        .bans()
+        .create(&NewBan {
+            account_id: None,
+            provider: account.provider,
+            provider_id: &account.provider_id,
+            reason: "griefing",
+            issued_by: None,
+            created_at: &now,
+            expires_at: None,
+        })
+        .await
+        .unwrap();
+    let error = get_auth(&app, &uri).await.unwrap_err();
+    assert_eq!(error.status, "AU-BN");
+
+    conn.bans().lift(ban.id, None, &now).await.unwrap().unwrap();
+    assert!(conn
+        .bans()
+        .lift(ban.id, None, &now)
+        .await
+        .unwrap()
+        .is_none());
+    get_auth(&app, &uri).await.unwrap();
+}
+
+#[actix_web::test]
+async fn banning_through_the_frontend_kicks_and_blocks_the_player() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let admin = server
+        .login_frontend(FrontendPermissions::ManageAccounts)
+        .await
+        .unwrap();
+    let uri = format!("/auth/ds?provider=local&display_name=Sam&code={LOCAL_AUTH_KEY}");
+
+    let response = get_auth(&app, &uri).await.unwrap();
+    let player = TestPlayer {
+        account_id: response.user.id.clone(),
+        token: response.session.token,
+    };
+
+    // A second session, such as one started through another server instance, should be ended as well
+    let other_session = TestPlayer {
+        account_id: player.account_id.clone(),
+        token: "other_session".into(),
+    };
+    let account = server
+        .database
+        .connect()
+        .await
+        .unwrap()
+        .accounts()
+        .get_by_ids(&[&player.account_id])
+        .await
+        .unwrap()
+        .remove(0);
+    server
+        .session_store
+        .save_session(Session::new(
+            account.provider,
+            &account.provider_id,
+            &other_session.account_id,
+            other_session.token.clone(),
+        ))
+        .await
+        .unwrap();
+
+    let ban: Value = call_frontend_api(
+        &app,
+        &admin,
+        test::TestRequest::post().set_json(json!({
+            "target": { "type": "account", "accountId": player.account_id },
+            "reason": "griefing",
+            "expiresAt": null,
+        })),
+        "bans",
+    )
+    .await
+    .unwrap();
+    assert_eq!(ban["isActive"], true);
+
+    // The player's sessions are ended and they can't log in again
+    for player in [&player, &other_session] {
+        let error = call_game_api::<_, _, _, GetLikeHistoryResponse>(
+            &app,
+            Some(player),
+            "getLikeHistory",
+            &GetLikeHistoryRequest {
+                since: 0,
+                account_id: "".into(),
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.status, "AU-UT");
+    }
+
+    let error = get_auth(&app, &uri).await.unwrap_err();
+    assert_eq!(error.status, "AU-BN");
+
+    let ban_id = ban["id"].as_i64().unwrap();
+    call_frontend_api::<_, _, Value>(
+        &app,
+        &admin,
+        test::TestRequest::delete(),
+        &format!("bans/{ban_id}"),
+    )
+    .await
+    .unwrap();
+    get_auth(&app, &uri).await.unwrap();
+
+    let conn = server.database.connect().await.unwrap();
+    let (entries, total) = conn
+        .audit_log()
+        .list(
+            &AuditLogFilter {
+                target_id: Some(&ban_id.to_string()),
+                ..Default::default()
+            },
+            0,
+            10,
+        )
+        .await
+        .unwrap();
+    assert_eq!(total, 2);
+    assert_eq!(entries[0].action, AuditAction::LiftBan);
+    assert_eq!(entries[1].action, AuditAction::CreateBan);
+    assert_eq!(
+        entries[1].after.as_ref().unwrap()["accountId"],
+        player.account_id
+    );
+    assert_eq!(entries[1].after.as_ref().unwrap()["reason"], "griefing");
+}
@@ -0,0 +1,86 @@
+use parcel_common::api_types::{
+    requests::get_like_history::{GetLikeHistoryRequest, GetLikeHistoryResponse},
+    EncryptedData,
+};
+
+use super::{call_game_api, send_raw, TestPlayer, TestServer};
+
+fn like_history_request() -> GetLikeHistoryRequest {
+    GetLikeHistoryRequest {
+        since: 0,
+        account_id: "".into(),
+    }
+}
+
+#[actix_web::test]
+async fn responses_are_encrypted() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let player = server.login("Player").await.unwrap();
+
+    let json = serde_json::to_vec(&like_history_request()).unwrap();
+    let body = serde_json::to_vec(&EncryptedData {
+        data: Some(parcel_common::aes::encrypt_json_data(&json)),
+    })
+    .unwrap();
+
+    let response = send_raw(&app, Some(&player), "getLikeHistory", body)
+        .await
+        .unwrap();
+    let encrypted_data = serde_json::from_str::<EncryptedData>(&response).unwrap();
+    let decrypted = parcel_common::aes::decrypt_json_data(&encrypted_data.data.unwrap()).unwrap();
+    let history = serde_json::from_str::<GetLikeHistoryResponse>(&decrypted).unwrap();
+
+    assert!(history.like_histories.is_empty());
+}
+
+#[actix_web::test]
+async fn invalid_encrypted_data_is_rejected() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let player = server.login("Player").await.unwrap();
+
+    let body = serde_json::to_vec(&EncryptedData {
+        data: Some("bm90IGVuY3J5cHRlZCBkYXRhIGF0IGFsbCwgbm90IGV2ZW4gYSBsaXR0bGU=".into()),
+    })
+    .unwrap();
+
+    let error = send_raw(&app, Some(&player), "getLikeHistory", body)
+        .await
+        .unwrap_err();
+
+    // The decryption error is made opaque by the WrapErrors middleware
+    assert_eq!(error.status, "SV-IE");
+}
+
+#[actix_web::test]
+async fn requests_without_a_valid_session_are_rejected() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+
+    let error = call_game_api::<_, _, _, GetLikeHistoryResponse>(
+        &app,
+        None,
+        "getLikeHistory",
+        &like_history_request(),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(error.status, "AU-UA");
+
+    let unknown_player = TestPlayer {
+        account_id: "unknown".into(),
+        token: "notarealtoken".into(),
+    };
+    let error = call_game_api::<_, _, _, GetLikeHistoryResponse>(
+        &app,
+        Some(&unknown_player),
+        "getLikeHistory",
+        &like_history_request(),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(error.status, "AU-UT");
+}
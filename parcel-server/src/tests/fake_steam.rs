@@ -0,0 +1,154 @@
+//! A fake Steam web api that the server's Steam client is pointed at, so that players can log in through `/auth/ds` the same way the game does.
+//!
+//! A ticket is the hex encoded display name of the player. Every display name gets its own steam id the first time it's used.
+
+use std::{net::TcpListener, sync::Mutex};
+
+use actix_web::{dev::ServerHandle, get, web, App, HttpResponse, HttpServer};
+use serde::Deserialize;
+use serde_json::json;
+
+/// The steam id of the first player, the following players get the next ids in the order they log in.
+const FIRST_STEAM_ID: u64 = 76561197960265728;
+
+/// The error code Steam responds with when the ticket is not valid.
+const INVALID_TICKET: i32 = 3;
+
+/// Returns the ticket that logs in the player with the display name.
+pub fn ticket_for(display_name: &str) -> String {
+    hex::encode(display_name)
+}
+
+pub struct FakeSteam {
+    url: String,
+    handle: ServerHandle,
+}
+
+impl FakeSteam {
+    /// Starts the web api on a random port.
+    ///
+    /// Must be called from within an actix runtime, such as in an `actix_web::test`.
+    pub fn start() -> Result<Self, anyhow::Error> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}", listener.local_addr()?);
+        let players = web::Data::new(Players::default());
+
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(players.clone())
+                .service(authenticate_user_ticket)
+                .service(get_player_summaries)
+        })
+        .workers(1)
+        .disable_signals()
+        .listen(listener)?
+        .run();
+        let handle = server.handle();
+        actix_web::rt::spawn(server);
+
+        Ok(Self { url, handle })
+    }
+
+    /// The url to use as the api url of the Steam client.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl Drop for FakeSteam {
+    fn drop(&mut self) {
+        // The stop command is sent right away, there's no need to wait for the workers to stop
+        let _stopped = self.handle.stop(false);
+    }
+}
+
+/// The display names of the players that have logged in, indexed by their steam id minus `FIRST_STEAM_ID`.
+#[derive(Default)]
+struct Players(Mutex<Vec<String>>);
+
+impl Players {
+    fn get_or_add_steam_id(&self, display_name: &str) -> u64 {
+        let mut players = self.0.lock().unwrap();
+        let index = match players.iter().position(|name| name == display_name) {
+            Some(index) => index,
+            None => {
+                players.push(display_name.to_owned());
+                players.len() - 1
+            }
+        };
+
+        FIRST_STEAM_ID + index as u64
+    }
+
+    fn get_display_name(&self, steam_id: u64) -> Option<String> {
+        let index = steam_id.checked_sub(FIRST_STEAM_ID)?;
+
+        self.0.lock().unwrap().get(index as usize).cloned()
+    }
+}
+
+#[derive(Deserialize)]
+struct AuthenticateUserTicketQuery {
+    ticket: String,
+}
+
+#[get("/ISteamUserAuth/AuthenticateUserTicket/v1/")]
+async fn authenticate_user_ticket(
+    query: web::Query<AuthenticateUserTicketQuery>,
+    players: web::Data<Players>,
+) -> HttpResponse {
+    let display_name = hex::decode(&query.ticket)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .filter(|display_name| !display_name.is_empty());
+
+    let Some(display_name) = display_name else {
+        return HttpResponse::Ok().json(json!({
+            "response": {
+                "error": { "errorcode": INVALID_TICKET, "errordesc": "Invalid ticket" }
+            }
+        }));
+    };
+
+    let steam_id = players.get_or_add_steam_id(&display_name).to_string();
+
+    HttpResponse::Ok().json(json!({
+        "response": {
+            "params": {
+                "result": "OK",
+                "steamid": steam_id,
+                "ownersteamid": steam_id,
+                "vacbanned": false,
+                "publisherbanned": false,
+            }
+        }
+    }))
+}
+
+#[derive(Deserialize)]
+struct GetPlayerSummariesQuery {
+    /// The server only asks for one player at a time
+    steamids: u64,
+}
+
+#[get("/ISteamUser/GetPlayerSummaries/v2/")]
+async fn get_player_summaries(
+    query: web::Query<GetPlayerSummariesQuery>,
+    players: web::Data<Players>,
+) -> HttpResponse {
+    let players = players
+        .get_display_name(query.steamids)
+        .map(|display_name| {
+            json!({
+                "steamid": query.steamids.to_string(),
+                "personaname": display_name,
+                "avatar": "",
+                "avatarmedium": "",
+                "avatarfull": "",
+            })
+        })
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    HttpResponse::Ok().json(json!({ "response": { "players": players } }))
+}
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use parcel_common::api_types::{
+    object::{Object, ObjectType},
+    requests::{
+        create_object::CreateObjectRequest,
+        find_qpid_objects::{FindQpidObjectsResponse, ObjectRequest},
+    },
+};
+
+use super::{
+    call_game_api,
+    likes::{create_postbox_request, find_objects_request},
+    TestServer,
+};
+
+/// Creates a postbox `meters` away from the one in `create_postbox_request`.
+fn postbox_request(meters: i32) -> CreateObjectRequest {
+    let request = create_postbox_request();
+
+    CreateObjectRequest {
+        // The exponent is 3 so positions are in millimeters
+        pos_x: request.pos_x + meters * 1000,
+        ..request
+    }
+}
+
+#[actix_web::test]
+async fn objects_built_on_top_of_each_other_are_collapsed() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let first = server.login("First").await.unwrap();
+    let second = server.login("Second").await.unwrap();
+    let visitor = server.login("Visitor").await.unwrap();
+
+    let mut created = Vec::new();
+
+    for (player, meters) in [(&first, 0), (&second, 0), (&second, 10)] {
+        let object: Object =
+            call_game_api(&app, Some(player), "createObject", &postbox_request(meters))
+                .await
+                .unwrap();
+        created.push(object);
+    }
+
+    let found: FindQpidObjectsResponse = call_game_api(
+        &app,
+        Some(&visitor),
+        "findQpidObjects",
+        &find_objects_request(),
+    )
+    .await
+    .unwrap();
+    let found_ids = found
+        .normal
+        .object_p
+        .unwrap_or_default()
+        .into_iter()
+        .map(|object| object.object_id)
+        .collect::<Vec<_>>();
+
+    // Only one of the two overlapping postboxes should be returned
+    assert_eq!(found_ids.len(), 2);
+    assert!(found_ids.contains(&created[2].object_id));
+}
+
+#[actix_web::test]
+async fn objects_from_strand_contracts_are_preferred() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let stranger = server.login("Stranger").await.unwrap();
+    let contract = server.login("Contract").await.unwrap();
+    let visitor = server.login("Visitor").await.unwrap();
+
+    // The stranger's object is more recent, which would otherwise rank it higher
+    let contract_object: Object =
+        call_game_api(&app, Some(&contract), "createObject", &postbox_request(0))
+            .await
+            .unwrap();
+    call_game_api::<_, _, _, Object>(&app, Some(&stranger), "createObject", &postbox_request(20))
+        .await
+        .unwrap();
+
+    let conn = server.database.connect().await.unwrap();
+    conn.accounts()
+        .add_strand_contracts(
+            &visitor.account_id,
+            [contract.account_id.as_str()].into_iter(),
+        )
+        .await
+        .unwrap();
+
+    let mut request = find_objects_request();
+    request.object = Some(ObjectRequest {
+        counts: HashMap::from([(ObjectType::Postbox, 1)]),
+    });
+
+    for _ in 0..3 {
+        let found: FindQpidObjectsResponse =
+            call_game_api(&app, Some(&visitor), "findQpidObjects", &request)
+                .await
+                .unwrap();
+        let found_objects = found.normal.object_p.unwrap_or_default();
+
+        assert_eq!(found_objects.len(), 1);
+        assert_eq!(found_objects[0].object_id, contract_object.object_id);
+    }
+}
+
+#[actix_web::test]
+async fn overlapping_objects_do_not_use_up_the_type_limit() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let creator = server.login("Creator").await.unwrap();
+    let visitor = server.login("Visitor").await.unwrap();
+
+    // The distant postbox is the oldest, so it's ranked below every overlapping one
+    let distant: Object = call_game_api(&app, Some(&creator), "createObject", &postbox_request(10))
+        .await
+        .unwrap();
+
+    for _ in 0..3 {
+        call_game_api::<_, _, _, Object>(&app, Some(&creator), "createObject", &postbox_request(0))
+            .await
+            .unwrap();
+    }
+
+    let mut request = find_objects_request();
+    request.object = Some(ObjectRequest {
+        counts: HashMap::from([(ObjectType::Postbox, 2)]),
+    });
+
+    let found: FindQpidObjectsResponse =
+        call_game_api(&app, Some(&visitor), "findQpidObjects", &request)
+            .await
+            .unwrap();
+    let found_ids = found
+        .normal
+        .object_p
+        .unwrap_or_default()
+        .into_iter()
+        .map(|object| object.object_id)
+        .collect::<Vec<_>>();
+
+    assert_eq!(found_ids.len(), 2);
+    assert!(found_ids.contains(&distant.object_id));
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use parcel_common::api_types::{area::AreaHash, object::Object, road::Road};
+use parcel_game_data::{Area, ConstructionPointType, GameData, QpidArea, QpidAreaMetaData};
+use serde_json::json;
+
+use crate::geojson::{build_feature_collection, Layers};
+
+use super::{call_game_api, likes::create_postbox_request, roads::create_road_request, TestServer};
+
+fn game_data() -> GameData {
+    let mut game_data = GameData::default();
+
+    for (qpid_id, location) in [(100, (10.0, 20.0, 30.0)), (200, (40.0, 50.0, 60.0))] {
+        game_data.qpid_areas.insert(
+            qpid_id,
+            QpidArea {
+                qpid_id,
+                names: BTreeMap::new(),
+                metadata: QpidAreaMetaData {
+                    order_in_list: 0,
+                    construction_type: ConstructionPointType::DeliveryBase,
+                    area: Area::Area02,
+                    location,
+                },
+            },
+        );
+    }
+
+    game_data
+}
+
+#[actix_web::test]
+async fn world_is_exported_as_feature_collection() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let player = server.login("Player").await.unwrap();
+
+    let postbox: Object = call_game_api(
+        &app,
+        Some(&player),
+        "createObject",
+        &create_postbox_request(),
+    )
+    .await
+    .unwrap();
+    let road: Road = call_game_api(
+        &app,
+        Some(&player),
+        "createRoad",
+        &create_road_request(1, 2),
+    )
+    .await
+    .unwrap();
+
+    let conn = server.database.connect().await.unwrap();
+    let game_data = game_data();
+    let collection = build_feature_collection(&conn, &game_data, None, &Layers::default())
+        .await
+        .unwrap();
+    let collection = serde_json::to_value(&collection).unwrap();
+    let features = collection["features"].as_array().unwrap();
+
+    assert_eq!(collection["type"], "FeatureCollection");
+    assert_eq!(features.len(), 4);
+
+    let object = features
+        .iter()
+        .find(|f| f["id"] == postbox.object_id.as_str())
+        .unwrap();
+    assert_eq!(object["type"], "Feature");
+    assert_eq!(
+        object["geometry"],
+        json!({ "type": "Point", "coordinates": [1.0, 2.0, 3.0] })
+    );
+    assert_eq!(object["properties"]["kind"], "object");
+    assert_eq!(
+        object["properties"]["creatorId"],
+        player.account_id.as_str()
+    );
+
+    let road = features
+        .iter()
+        .find(|f| f["id"] == road.online_id.as_str())
+        .unwrap();
+    assert_eq!(
+        road["geometry"],
+        json!({
+            "type": "LineString",
+            "coordinates": [[10.0, 20.0, 30.0], [40.0, 50.0, 60.0]],
+        })
+    );
+
+    // Nothing was created in the eastern region and the delivery points are in the central region
+    let eastern = build_feature_collection(
+        &conn,
+        &game_data,
+        Some(AreaHash::EasternRegion),
+        &Layers::default(),
+    )
+    .await
+    .unwrap();
+    assert!(eastern.features.is_empty());
+
+    let delivery_points = build_feature_collection(
+        &conn,
+        &game_data,
+        None,
+        &Layers {
+            objects: false,
+            roads: false,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(delivery_points.features.len(), 2);
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use actix_http::Request;
+use actix_web::{
+    body::MessageBody,
+    dev::{Service, ServiceResponse},
+    test::TestRequest,
+    web,
+};
+use flagset::FlagSet;
+use parcel_common::api_types::{
+    frontend::auth::FrontendPermissions,
+    requests::devote_highway_resources::{DevoteHighwayResourcesRequest, PutHistory},
+};
+use parcel_game_data::{Area, GameData, HighwaySegment, HighwaySegmentMetaData};
+use serde_json::Value;
+
+use super::{call_frontend_api, call_game_api, TestPlayer, TestServer};
+
+const CONSTRUCTION_ID: i32 = 1000;
+const METAL: i16 = 2;
+const CERAMIC: i16 = 3;
+
+async fn devote<S, B>(app: &S, player: &TestPlayer, resource_id: i16, put_num: i32)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    call_game_api::<_, _, _, ()>(
+        app,
+        Some(player),
+        "devoteHighwayResources",
+        &DevoteHighwayResourcesRequest {
+            put_histories: vec![PutHistory {
+                construction_id: CONSTRUCTION_ID,
+                put_num,
+                resource_id,
+            }],
+        },
+    )
+    .await
+    .unwrap();
+}
+
+#[actix_web::test]
+async fn top_contributors_are_ranked_by_total_devoted_resources() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let small = server.login("Small").await.unwrap();
+    let large = server.login("Large").await.unwrap();
+
+    devote(&app, &small, METAL, 100).await;
+    devote(&app, &large, METAL, 200).await;
+    devote(&app, &large, CERAMIC, 50).await;
+
+    let conn = server.database.connect().await.unwrap();
+    let highway_resources = conn.highway_resources();
+
+    let totals = highway_resources.list_total_resources().await.unwrap();
+    assert_eq!(totals.len(), 2);
+    assert_eq!(totals[0].resource_id, METAL);
+    assert_eq!(totals[0].num_resources, 300);
+    assert_eq!(totals[1].resource_id, CERAMIC);
+    assert_eq!(totals[1].num_resources, 50);
+
+    let contributors = highway_resources.get_top_contributors(5).await.unwrap();
+    assert_eq!(contributors.len(), 2);
+    assert_eq!(contributors[0].account_id, large.account_id);
+    assert_eq!(contributors[0].num_resources, 250);
+    assert_eq!(contributors[1].account_id, small.account_id);
+
+    let contributors = highway_resources.get_top_contributors(1).await.unwrap();
+    assert_eq!(contributors.len(), 1);
+    assert_eq!(contributors[0].construction_id, CONSTRUCTION_ID);
+    assert_eq!(contributors[0].account_id, large.account_id);
+}
+
+#[actix_web::test]
+async fn completion_is_computed_from_the_total_devoted_resources() {
+    let mut server = TestServer::start().await.unwrap();
+    let mut game_data = GameData::default();
+    game_data.highway_segments.insert(
+        CONSTRUCTION_ID,
+        HighwaySegment {
+            construction_id: CONSTRUCTION_ID,
+            names: BTreeMap::new(),
+            metadata: HighwaySegmentMetaData {
+                order_in_list: 0,
+                area: Area::Area01,
+                location: (0.0, 0.0, 0.0),
+            },
+            required_resources: BTreeMap::from([(METAL, 400), (CERAMIC, 100)]),
+        },
+    );
+    server.game_data = web::Data::new(game_data);
+    let app = server.init_app().await;
+    let first = server.login("First").await.unwrap();
+    let second = server.login("Second").await.unwrap();
+    let user = server
+        .login_frontend(FlagSet::<FrontendPermissions>::default())
+        .await
+        .unwrap();
+
+    // Metal beyond the required amount doesn't make up for the missing ceramic
+    devote(&app, &first, METAL, 300).await;
+    devote(&app, &second, METAL, 200).await;
+    devote(&app, &second, CERAMIC, 50).await;
+
+    let constructions: Value = call_frontend_api(&app, &user, TestRequest::get(), "highways")
+        .await
+        .unwrap();
+    let construction = &constructions[0];
+
+    assert_eq!(construction["constructionId"], CONSTRUCTION_ID);
+    assert_eq!(construction["completionPercentage"], 90.0);
+    assert_eq!(construction["resources"][0]["resourceId"], METAL);
+    assert_eq!(construction["resources"][0]["numResources"], 500);
+    assert_eq!(construction["resources"][0]["requiredResources"], 400);
+    assert_eq!(construction["resources"][1]["resourceId"], CERAMIC);
+    assert_eq!(construction["resources"][1]["numResources"], 50);
+    assert_eq!(construction["resources"][1]["requiredResources"], 100);
+}
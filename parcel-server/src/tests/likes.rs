@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+
+use parcel_common::api_types::{
+    area::AreaHash,
+    object::{Object, ObjectType},
+    requests::{
+        create_object::CreateObjectRequest,
+        find_qpid_objects::{FindQpidObjectsRequest, FindQpidObjectsResponse, ObjectRequest},
+        get_like_history::{GetLikeHistoryRequest, GetLikeHistoryResponse},
+        send_like::SendLikeRequest,
+    },
+};
+
+use super::{call_game_api, TestServer};
+
+const QPID_ID: i32 = 100;
+
+pub fn create_postbox_request() -> CreateObjectRequest {
+    CreateObjectRequest {
+        exponent: 3,
+        likes: 0,
+        area_hash: AreaHash::CentralRegion,
+        priority: 0,
+        pos_x: 1000,
+        pos_y: 2000,
+        pos_z: 3000,
+        rot_x: 0,
+        rot_y: 90,
+        rot_z: 0,
+        grid_x: 5,
+        grid_y: 6,
+        qpid_id: QPID_ID,
+        sub_type: "".into(),
+        object_type: ObjectType::Postbox,
+        comment: None,
+        rope_info: None,
+        stone_info: None,
+        bridge_info: None,
+        parking_info: None,
+        vehicle_info: None,
+        extra_info: None,
+        customize_info: None,
+    }
+}
+
+pub fn find_objects_request() -> FindQpidObjectsRequest {
+    FindQpidObjectsRequest {
+        area_hash: AreaHash::CentralRegion,
+        qpid_id: QPID_ID,
+        account_ids: None,
+        object: Some(ObjectRequest {
+            counts: HashMap::from([(ObjectType::Postbox, 10)]),
+        }),
+        road: None,
+        mission: None,
+    }
+}
+
+#[actix_web::test]
+async fn liked_object_shows_up_in_creators_like_history() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let creator = server.login("Creator").await.unwrap();
+    let liker = server.login("Liker").await.unwrap();
+
+    let created: Object = call_game_api(
+        &app,
+        Some(&creator),
+        "createObject",
+        &create_postbox_request(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(created.creator_account_id, creator.account_id);
+    assert_eq!(created.position, (1000, 2000, 3000));
+
+    // The object should be found by other players in the same qpid area
+    let found: FindQpidObjectsResponse = call_game_api(
+        &app,
+        Some(&liker),
+        "findQpidObjects",
+        &find_objects_request(),
+    )
+    .await
+    .unwrap();
+    let found_objects = found.normal.object_p.unwrap_or_default();
+
+    assert_eq!(found_objects.len(), 1);
+    assert_eq!(found_objects[0].object_id, created.object_id);
+
+    call_game_api::<_, _, _, ()>(
+        &app,
+        Some(&liker),
+        "sendLike",
+        &SendLikeRequest {
+            index: -1,
+            likes_auto: 3,
+            likes_manual: 2,
+            like_type: "".into(),
+            online_id: created.object_id.clone(),
+            online_type: "p".into(),
+            account_id: creator.account_id.clone(),
+        },
+    )
+    .await
+    .unwrap();
+
+    let history: GetLikeHistoryResponse = call_game_api(
+        &app,
+        Some(&creator),
+        "getLikeHistory",
+        &GetLikeHistoryRequest {
+            since: 0,
+            account_id: "".into(),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(history.like_histories.len(), 1);
+    let like = &history.like_histories[0];
+    assert_eq!(like.online_id, created.object_id);
+    assert_eq!(like.account_id, liker.account_id);
+    assert_eq!(like.likes_auto, 3);
+    assert_eq!(like.likes_manual, 2);
+
+    // Likes are acknowledged once they've been returned, so they should not be returned again
+    let history: GetLikeHistoryResponse = call_game_api(
+        &app,
+        Some(&creator),
+        "getLikeHistory",
+        &GetLikeHistoryRequest {
+            since: 0,
+            account_id: "".into(),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert!(history.like_histories.is_empty());
+
+    // The likes should also be added to the object itself
+    let found: FindQpidObjectsResponse = call_game_api(
+        &app,
+        Some(&liker),
+        "findQpidObjects",
+        &find_objects_request(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(found.normal.object_p.unwrap_or_default()[0].likes, 5);
+}
+
+#[actix_web::test]
+async fn own_objects_are_not_returned_by_find_qpid_objects() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let creator = server.login("Creator").await.unwrap();
+
+    call_game_api::<_, _, _, Object>(
+        &app,
+        Some(&creator),
+        "createObject",
+        &create_postbox_request(),
+    )
+    .await
+    .unwrap();
+
+    let found: FindQpidObjectsResponse = call_game_api(
+        &app,
+        Some(&creator),
+        "findQpidObjects",
+        &find_objects_request(),
+    )
+    .await
+    .unwrap();
+
+    assert!(found.normal.object_p.unwrap_or_default().is_empty());
+}
+
+#[actix_web::test]
+async fn liking_own_object_is_rejected() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let creator = server.login("Creator").await.unwrap();
+
+    let created: Object = call_game_api(
+        &app,
+        Some(&creator),
+        "createObject",
+        &create_postbox_request(),
+    )
+    .await
+    .unwrap();
+
+    let error = call_game_api::<_, _, _, ()>(
+        &app,
+        Some(&creator),
+        "sendLike",
+        &SendLikeRequest {
+            index: -1,
+            likes_auto: 0,
+            likes_manual: 1,
+            like_type: "".into(),
+            online_id: created.object_id,
+            online_type: "p".into(),
+            account_id: creator.account_id.clone(),
+        },
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(error.status, "SL-UV");
+}
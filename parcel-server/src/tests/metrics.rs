@@ -0,0 +1,46 @@
+use actix_web::test;
+use parcel_common::api_types::requests::get_like_history::{
+    GetLikeHistoryRequest, GetLikeHistoryResponse,
+};
+
+use super::{call_game_api, TestServer};
+
+#[actix_web::test]
+async fn game_requests_are_exported_by_route_name() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let player = server.login("Sam").await.unwrap();
+    let request = GetLikeHistoryRequest {
+        since: 0,
+        account_id: "".into(),
+    };
+
+    call_game_api::<_, _, _, GetLikeHistoryResponse>(
+        &app,
+        Some(&player),
+        "getLikeHistory",
+        &request,
+    )
+    .await
+    .unwrap();
+
+    // Requests without a session are rejected
+    let error =
+        call_game_api::<_, _, _, GetLikeHistoryResponse>(&app, None, "getLikeHistory", &request)
+            .await
+            .unwrap_err();
+    assert_eq!(error.status, "AU-UA");
+
+    let response =
+        test::call_service(&app, test::TestRequest::get().uri("/metrics").to_request()).await;
+    assert!(response.status().is_success());
+
+    let body = test::read_body(response).await;
+    let body = std::str::from_utf8(&body).unwrap();
+
+    assert!(body
+        .contains("parcel_game_requests_seconds_count{route=\"get_like_history\",status=\"200\"}"));
+    assert!(body.contains("parcel_game_errors_total{status=\"AU-UA\"}"));
+    assert!(body.contains("parcel_active_sessions 1\n"));
+    assert!(body.contains("parcel_database_connect_seconds_count "));
+}
@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+
+use actix_http::StatusCode;
+use actix_web::{test::TestRequest, web};
+use parcel_common::api_types::{
+    area::AreaHash,
+    frontend::{audit_log::AuditAction, auth::FrontendPermissions},
+    mission::{MissionType, OnlineMissionType},
+    requests::{
+        add_missions::NewMission,
+        find_missions::{FindMissionsRequest, FindMissionsResponse},
+    },
+};
+use parcel_game_data::{GameData, Language, MissionDefinition};
+use serde_json::{json, Value};
+
+use crate::data::database::audit_log::AuditLogFilter;
+
+use super::{call_frontend_api, call_game_api, TestServer};
+
+const QPID_ID: i32 = 300;
+
+fn lost_cargo(online_mission_type: OnlineMissionType) -> NewMission {
+    NewMission {
+        area_hash: AreaHash::CentralRegion,
+        qpid_id: QPID_ID,
+        qpid_start_location: 1,
+        qpid_end_location: 2,
+        mission_static_id: 0,
+        mission_type: MissionType::LostObject,
+        online_mission_type,
+        supply_info: None,
+        dynamic_start_info: None,
+        dynamic_end_info: None,
+        dynamic_mission_info: None,
+        catapult_shell_info: None,
+        baggages: None,
+    }
+}
+
+fn find_missions_request(
+    mission_limit_per_pot: u32,
+    private_limit_per_pot: u32,
+) -> FindMissionsRequest {
+    FindMissionsRequest {
+        area_hash: AreaHash::CentralRegion,
+        limit: 100,
+        limit_pot_baggages: 0,
+        mission_limit_per_pot,
+        private_limit_per_pot,
+        qpid_ids: vec![QPID_ID],
+        target_ids: None,
+        target_rate: 60,
+    }
+}
+
+#[actix_web::test]
+async fn one_creator_can_not_fill_a_pot() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let hoarder = server.login("Hoarder").await.unwrap();
+    let other = server.login("Other").await.unwrap();
+    let visitor = server.login("Visitor").await.unwrap();
+
+    let conn = server.database.connect().await.unwrap();
+    let missions = conn.missions();
+
+    for _ in 0..5 {
+        missions
+            .save_mission(&lost_cargo(OnlineMissionType::Dynamic), &hoarder.account_id)
+            .await
+            .unwrap();
+    }
+
+    let other_mission = missions
+        .save_mission(&lost_cargo(OnlineMissionType::Dynamic), &other.account_id)
+        .await
+        .unwrap();
+
+    let found: FindMissionsResponse = call_game_api(
+        &app,
+        Some(&visitor),
+        "findMissions",
+        &find_missions_request(3, 0),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(found.missions.len(), 3);
+    assert!(found
+        .missions
+        .iter()
+        .any(|mission| mission.online_id == other_mission.mission.id));
+}
+
+#[actix_web::test]
+async fn private_missions_are_limited_separately() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let creator = server.login("Creator").await.unwrap();
+    let visitor = server.login("Visitor").await.unwrap();
+
+    let conn = server.database.connect().await.unwrap();
+    let missions = conn.missions();
+
+    for online_mission_type in [
+        OnlineMissionType::Private,
+        OnlineMissionType::Private,
+        OnlineMissionType::Dynamic,
+    ] {
+        missions
+            .save_mission(&lost_cargo(online_mission_type), &creator.account_id)
+            .await
+            .unwrap();
+    }
+
+    let found: FindMissionsResponse = call_game_api(
+        &app,
+        Some(&visitor),
+        "findMissions",
+        &find_missions_request(0, 1),
+    )
+    .await
+    .unwrap();
+
+    let num_private = found
+        .missions
+        .iter()
+        .filter(|mission| mission.online_mission_type == OnlineMissionType::Private)
+        .count();
+
+    assert_eq!(found.missions.len(), 2);
+    assert_eq!(num_private, 1);
+}
+
+#[actix_web::test]
+async fn mission_changes_are_recorded() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let creator = server.login("Creator").await.unwrap();
+    let admin = server
+        .login_frontend(FrontendPermissions::ManageMissions)
+        .await
+        .unwrap();
+    let moderator = server
+        .login_frontend(FrontendPermissions::ManageObjects)
+        .await
+        .unwrap();
+
+    let conn = server.database.connect().await.unwrap();
+    let mission_id = conn
+        .missions()
+        .save_mission(&lost_cargo(OnlineMissionType::Dynamic), &creator.account_id)
+        .await
+        .unwrap()
+        .mission
+        .id;
+
+    let status = call_frontend_api::<_, _, Value>(
+        &app,
+        &moderator,
+        TestRequest::delete(),
+        &format!("missions/{mission_id}"),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let details: Value = call_frontend_api(
+        &app,
+        &admin,
+        TestRequest::put().set_json(json!({ "progressState": "Progress" })),
+        &format!("missions/{mission_id}/progressState"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(details["progressState"], "Progress");
+
+    let details: Value = call_frontend_api(
+        &app,
+        &admin,
+        TestRequest::post(),
+        &format!("missions/{mission_id}/reset"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(details["progressState"], "Available");
+
+    call_frontend_api::<_, _, Value>(
+        &app,
+        &admin,
+        TestRequest::delete(),
+        &format!("missions/{mission_id}"),
+    )
+    .await
+    .unwrap();
+    assert!(conn
+        .missions()
+        .get_by_id(&mission_id)
+        .await
+        .unwrap()
+        .is_none());
+
+    let (entries, total) = conn
+        .audit_log()
+        .list(
+            &AuditLogFilter {
+                target_id: Some(&mission_id),
+                ..Default::default()
+            },
+            0,
+            10,
+        )
+        .await
+        .unwrap();
+
+    // Newest first, and the refused deletion isn't recorded
+    assert_eq!(total, 3);
+    assert!(entries
+        .iter()
+        .all(|entry| entry.frontend_actor_id == Some(admin.account_id)));
+    assert_eq!(entries[0].action, AuditAction::DeleteMission);
+    assert_eq!(
+        entries[0].before.as_ref().unwrap()["creatorId"],
+        creator.account_id
+    );
+    assert!(entries[0].after.is_none());
+    assert_eq!(entries[1].action, AuditAction::ResetMission);
+    assert_eq!(
+        entries[1].before.as_ref().unwrap()["progressState"],
+        "Progress"
+    );
+    assert_eq!(
+        entries[1].after.as_ref().unwrap()["progressState"],
+        "Available"
+    );
+    assert_eq!(entries[2].action, AuditAction::SetMissionProgressState);
+    assert_eq!(
+        entries[2].before.as_ref().unwrap()["progressState"],
+        "Ready"
+    );
+    assert_eq!(
+        entries[2].after.as_ref().unwrap()["progressState"],
+        "Progress"
+    );
+}
+
+#[actix_web::test]
+async fn missions_are_named_from_the_game_data() {
+    let mut server = TestServer::start().await.unwrap();
+    let mut game_data = GameData::default();
+    game_data.missions.insert(
+        1,
+        MissionDefinition {
+            mission_static_id: 1,
+            names: BTreeMap::from([(Language::English, "Deliver the cargo".to_owned())]),
+        },
+    );
+    server.game_data = web::Data::new(game_data);
+    let app = server.init_app().await;
+    let creator = server.login("Creator").await.unwrap();
+    let admin = server
+        .login_frontend(FrontendPermissions::ManageMissions)
+        .await
+        .unwrap();
+
+    let conn = server.database.connect().await.unwrap();
+    let mut names = Vec::new();
+
+    for mission_static_id in [1, 2] {
+        let mission_id = conn
+            .missions()
+            .save_mission(
+                &NewMission {
+                    mission_static_id,
+                    ..lost_cargo(OnlineMissionType::Dynamic)
+                },
+                &creator.account_id,
+            )
+            .await
+            .unwrap()
+            .mission
+            .id;
+
+        let details: Value = call_frontend_api(
+            &app,
+            &admin,
+            TestRequest::get(),
+            &format!("missions/{mission_id}"),
+        )
+        .await
+        .unwrap();
+        names.push(details["missionName"].clone());
+    }
+
+    assert_eq!(names, [json!("Deliver the cargo"), Value::Null]);
+}
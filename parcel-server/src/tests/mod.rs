@@ -0,0 +1,402 @@
+//! End to end tests that drive the game api the same way the game does.
+//!
+//! Every test gets its own temporary postgresql server, session store and settings, so tests can run in parallel without affecting each other.
+
+mod audit_log;
+mod auth;
+mod encryption;
+mod fake_steam;
+mod find_objects;
+mod geojson;
+mod highways;
+mod likes;
+mod metrics;
+mod missions;
+mod qpid_objects;
+mod request_log;
+mod retention;
+mod roads;
+mod sessions;
+mod shared_state;
+mod statistics;
+mod world_snapshot;
+
+use std::path::{Path, PathBuf};
+
+use actix_http::{Request, StatusCode};
+use actix_web::{
+    body::MessageBody,
+    dev::{Service, ServiceResponse},
+    http::header,
+    test, web, App,
+};
+use anyhow::Context;
+use chrono::{Duration, Utc};
+use flagset::FlagSet;
+use jwt::SignWithKey;
+use moka::future::CacheBuilder;
+use parcel_common::{
+    api_types::{
+        auth::AuthResponse,
+        frontend::auth::{FrontendPermissions, JwtPayload},
+        EncryptedData,
+    },
+    rand,
+};
+use parcel_game_data::GameData;
+use postgresql_embedded::PostgreSQL;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use self::fake_steam::FakeSteam;
+use crate::{
+    data::{
+        database::Database,
+        jwt_secret::JwtSecret,
+        platforms::{epic::Epic, local::LocalAuth, steam::Steam},
+        session_store::SessionStore,
+    },
+    db::models::frontend_account::NewFrontendAccount,
+    embedded, endpoints,
+    endpoints::metrics::MetricsToken,
+    frontend,
+    frontend::jwt_session::{
+        SessionBlacklistCache, SessionBlacklistCacheExpiry, SessionPermissionsCache,
+    },
+    middleware,
+    middleware::wrap_errors,
+    response_error::CommonError,
+    settings::{FilePersist, JsonFormat},
+    whitelist::WhitelistFormat,
+    GatewayUrl, ServerSettings, WhitelistSettings,
+};
+
+/// The key that players can use to log in with local auth.
+pub const LOCAL_AUTH_KEY: &str = "test_key";
+
+/// A server with its own database and data directory that is removed when the server is dropped.
+pub struct TestServer {
+    pub database: web::Data<Database>,
+    pub database_url: String,
+    pub session_store: web::Data<SessionStore>,
+    pub server_settings: web::Data<ServerSettings>,
+    pub whitelist: web::Data<WhitelistSettings>,
+    pub local_auth: web::Data<Option<LocalAuth>>,
+    pub game_data: web::Data<GameData>,
+    pub jwt_secret: web::Data<JwtSecret>,
+    pub session_blacklist_cache: web::Data<SessionBlacklistCache>,
+    pub session_permissions_cache: web::Data<SessionPermissionsCache>,
+    pub steam: web::Data<Option<Steam>>,
+    _fake_steam: FakeSteam,
+    // Declared before data_dir so that the server is stopped before its directory is deleted
+    _postgresql: PostgreSQL,
+    _data_dir: TempDir,
+}
+
+/// A player that has been authenticated through the fake Steam web api.
+#[derive(Debug, Clone)]
+pub struct TestPlayer {
+    pub account_id: String,
+    pub token: String,
+}
+
+/// A frontend account with a token for the frontend api.
+#[derive(Debug, Clone)]
+pub struct TestFrontendUser {
+    pub account_id: i64,
+    pub token: String,
+}
+
+impl TestServer {
+    pub async fn start() -> Result<Self, anyhow::Error> {
+        let data_dir = TempDir::new()?;
+        let (postgresql, database_url) =
+            embedded::postgresql::setup_temporary_postgresql(data_dir.path())
+                .await
+                .context("Failed to setup and launch postgresql server")?;
+
+        crate::migrate_database(&database_url)
+            .context("Could not apply pending database migrations")?;
+
+        let server_settings = ServerSettings::load(FilePersist::<JsonFormat>::new(
+            &data_dir.path().join("settings.json"),
+        ))
+        .await?;
+        let whitelist = WhitelistSettings::load(FilePersist::<WhitelistFormat>::new(
+            &data_dir.path().join("whitelist.txt"),
+        ))
+        .await?;
+
+        // Let anyone log in so that tests don't need to whitelist every player
+        server_settings
+            .write(|settings| settings.public_server = true)
+            .await?;
+
+        let database = web::Data::new(Database::new(&database_url));
+        let fake_steam = FakeSteam::start().context("Failed to start the fake Steam web api")?;
+        let steam = Steam::with_api_url("test_key".into(), fake_steam.url().into())?;
+
+        Ok(Self {
+            session_store: web::Data::new(SessionStore::new(database.clone().into_inner())),
+            database,
+            database_url,
+            server_settings: web::Data::new(server_settings),
+            whitelist: web::Data::new(whitelist),
+            local_auth: web::Data::new(Some(LocalAuth::new(LOCAL_AUTH_KEY.into())?)),
+            game_data: web::Data::new(GameData::default()),
+            jwt_secret: web::Data::new(JwtSecret::generate()),
+            session_blacklist_cache: web::Data::new(SessionBlacklistCache::from_builder(
+                CacheBuilder::default().expire_after(SessionBlacklistCacheExpiry),
+            )),
+            session_permissions_cache: web::Data::new(SessionPermissionsCache::from_builder(
+                CacheBuilder::default(),
+            )),
+            steam: web::Data::new(Some(steam)),
+            _fake_steam: fake_steam,
+            _postgresql: postgresql,
+            _data_dir: data_dir,
+        })
+    }
+
+    /// Creates the app with the auth endpoints, game api and frontend api configured the same way as in `main`.
+    ///
+    /// Steam sends its requests to the fake Steam web api of this server.
+    pub async fn init_app(
+        &self,
+    ) -> impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = actix_web::Error>
+    {
+        test::init_service(
+            App::new()
+                .app_data(self.database.clone())
+                .app_data(self.session_store.clone())
+                .app_data(self.server_settings.clone())
+                .app_data(self.whitelist.clone())
+                .app_data(self.local_auth.clone())
+                .app_data(self.steam.clone())
+                .app_data(web::Data::new(
+                    Epic::new().expect("Epic web api client should be created"),
+                ))
+                .app_data(web::Data::new(None::<GatewayUrl>))
+                .app_data(web::Data::new(None::<MetricsToken>))
+                .app_data(self.game_data.clone())
+                .app_data(self.jwt_secret.clone())
+                .app_data(self.session_blacklist_cache.clone())
+                .app_data(self.session_permissions_cache.clone())
+                .service(
+                    web::scope("/ds/e")
+                        .configure(endpoints::configure_endpoints)
+                        .wrap(middleware::encryption::DataEncryption {
+                            optional_encryption: false,
+                        })
+                        .wrap(wrap_errors::WrapErrors)
+                        .wrap(middleware::metrics::RequestMetrics),
+                )
+                .service(
+                    web::scope("/auth")
+                        .service(endpoints::auth::auth)
+                        .service(endpoints::auth::auth_local),
+                )
+                .service(endpoints::metrics::metrics)
+                .service(web::scope("/frontend/api").configure(frontend::api::configure_endpoints)),
+        )
+        .await
+    }
+
+    /// Authenticates a player through the `auth/ds` endpoint with a Steam ticket that the fake Steam web api accepts.
+    ///
+    /// An account is created the first time a display name logs in, and every login starts a new session.
+    pub async fn login(&self, display_name: &str) -> Result<TestPlayer, anyhow::Error> {
+        let app = self.init_app().await;
+        let uri = format!(
+            "/auth/ds?provider=steam&display_name={display_name}&code={}",
+            fake_steam::ticket_for(display_name)
+        );
+        let response =
+            test::call_service(&app, test::TestRequest::get().uri(&uri).to_request()).await;
+
+        let status = response.status();
+        let body = test::read_body(response).await;
+        anyhow::ensure!(
+            status.is_success(),
+            "Logging in failed with status {status}: {}",
+            String::from_utf8_lossy(&body)
+        );
+        let response = serde_json::from_slice::<AuthResponse>(&body)
+            .context("Unexpected response from the auth endpoint")?;
+
+        Ok(TestPlayer {
+            account_id: response.user.id,
+            token: response.session.token,
+        })
+    }
+
+    /// Creates a frontend account with the permissions and signs a token for it, the same way as logging in to the frontend.
+    pub async fn login_frontend(
+        &self,
+        permissions: impl Into<FlagSet<FrontendPermissions>>,
+    ) -> Result<TestFrontendUser, anyhow::Error> {
+        let account = self
+            .database
+            .connect()
+            .await?
+            .frontend_accounts()
+            .add_account(&NewFrontendAccount {
+                game_account_id: None,
+                created_at: None,
+                permissions: permissions.into().bits(),
+            })
+            .await?;
+        let token = JwtPayload {
+            expires_at: (Utc::now() + Duration::hours(1)).timestamp(),
+            account_id: account.id,
+        }
+        .sign_with_key(self.jwt_secret.as_ref())?;
+
+        Ok(TestFrontendUser {
+            account_id: account.id,
+            token,
+        })
+    }
+}
+
+/// Sends an AES encrypted request to a game api endpoint and decrypts the response.
+///
+/// If the server responds with an error it is returned as a `CommonError`.
+pub async fn call_game_api<S, B, TReq, TRes>(
+    app: &S,
+    player: Option<&TestPlayer>,
+    method: &str,
+    request: &TReq,
+) -> Result<TRes, CommonError>
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+    TReq: Serialize,
+    TRes: DeserializeOwned,
+{
+    let json = serde_json::to_vec(request).expect("Request should be serializable");
+    let body = EncryptedData {
+        data: Some(parcel_common::aes::encrypt_json_data(&json)),
+    };
+
+    let response = send_raw(app, player, method, serde_json::to_vec(&body).unwrap()).await;
+
+    match response {
+        Ok(body) => {
+            // Endpoints that return an EmptyResponse have an empty body which is not encrypted
+            let decrypted = match body.is_empty() {
+                true => "null".into(),
+                false => parcel_common::aes::decrypt_json_response(&body)
+                    .expect("Response should be encrypted")
+                    .unwrap_or_else(|| "null".into()),
+            };
+
+            Ok(serde_json::from_str(&decrypted).unwrap_or_else(|err| {
+                panic!("Could not deserialize response from {method}: {err}\n{decrypted}")
+            }))
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// Sends a request without encrypting the body and returns the raw response body.
+pub async fn send_raw<S, B>(
+    app: &S,
+    player: Option<&TestPlayer>,
+    method: &str,
+    body: Vec<u8>,
+) -> Result<String, CommonError>
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let mut request = test::TestRequest::post()
+        .uri(&format!("/ds/e/{method}"))
+        .insert_header((header::CONTENT_TYPE, "application/json"))
+        .set_payload(body);
+
+    if let Some(player) = player {
+        request =
+            request.insert_header((header::AUTHORIZATION, format!("Bearer {}", player.token)));
+    }
+
+    let response = test::call_service(app, request.to_request()).await;
+    let status = response.status();
+    let body = test::read_body(response).await;
+    let body = String::from_utf8(body.to_vec()).expect("Response should be valid utf8");
+
+    if status.is_success() {
+        Ok(body)
+    } else {
+        Err(serde_json::from_str(&body).unwrap_or_else(|_| {
+            panic!("Expected an error response from {method} with status {status}: {body}")
+        }))
+    }
+}
+
+#[derive(Deserialize)]
+struct FrontendResponse<T> {
+    data: T,
+}
+
+/// Sends a request to the frontend api as the user and returns the data in the response.
+///
+/// The uri of the request should be relative to `/frontend/api`. If the server responds with an error its status code is returned.
+pub async fn call_frontend_api<S, B, TRes>(
+    app: &S,
+    user: &TestFrontendUser,
+    request: test::TestRequest,
+    uri: &str,
+) -> Result<TRes, StatusCode>
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+    TRes: DeserializeOwned,
+{
+    let request = request
+        .uri(&format!("/frontend/api/{uri}"))
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", user.token)))
+        .to_request();
+
+    let response = test::call_service(app, request).await;
+    let status = response.status();
+
+    if !status.is_success() {
+        return Err(status);
+    }
+
+    let body = test::read_body(response).await;
+    let response: FrontendResponse<TRes> = serde_json::from_slice(&body).unwrap_or_else(|err| {
+        panic!(
+            "Could not deserialize response from {uri}: {err}\n{}",
+            String::from_utf8_lossy(&body)
+        )
+    });
+
+    Ok(response.data)
+}
+
+/// A directory in the system's temp directory that is deleted when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new() -> Result<Self, anyhow::Error> {
+        let path = std::env::temp_dir().join(format!(
+            "parcel-server-test-{}",
+            rand::generate_string(16, b"abcdefghijklmnopqrstuvwxyz0123456789")
+        ));
+        std::fs::create_dir_all(&path)
+            .with_context(|| format!("Could not create temp dir: {}", path.display()))?;
+
+        Ok(Self(path))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
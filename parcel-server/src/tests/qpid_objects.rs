@@ -0,0 +1,180 @@
+use actix_http::StatusCode;
+use actix_web::test::TestRequest;
+use parcel_common::api_types::{
+    frontend::{audit_log::AuditAction, auth::FrontendPermissions},
+    object::Object,
+};
+use serde_json::{json, Value};
+
+use crate::data::database::audit_log::AuditLogFilter;
+
+use super::{call_frontend_api, call_game_api, likes::create_postbox_request, TestServer};
+
+#[actix_web::test]
+async fn managing_objects_requires_permission() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let creator = server.login("Creator").await.unwrap();
+    let moderator = server
+        .login_frontend(FrontendPermissions::ManageMissions)
+        .await
+        .unwrap();
+
+    let object: Object = call_game_api(
+        &app,
+        Some(&creator),
+        "createObject",
+        &create_postbox_request(),
+    )
+    .await
+    .unwrap();
+
+    let status = call_frontend_api::<_, _, Value>(
+        &app,
+        &moderator,
+        TestRequest::put().set_json(json!({ "isDeleted": true })),
+        &format!("qpidObjects/object/{}/deleted", object.object_id),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let conn = server.database.connect().await.unwrap();
+    let object = conn
+        .qpid_objects()
+        .get_by_id(&object.object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!object.is_deleted);
+
+    let (_, total) = conn
+        .audit_log()
+        .list(&AuditLogFilter::default(), 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(total, 0);
+}
+
+#[actix_web::test]
+async fn objects_can_be_deleted_and_given_to_another_creator() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let griefer = server.login("Griefer").await.unwrap();
+    let other = server.login("Other").await.unwrap();
+    let admin = server
+        .login_frontend(FrontendPermissions::ManageObjects)
+        .await
+        .unwrap();
+
+    let mut object_ids = Vec::new();
+
+    for _ in 0..2 {
+        let object: Object = call_game_api(
+            &app,
+            Some(&griefer),
+            "createObject",
+            &create_postbox_request(),
+        )
+        .await
+        .unwrap();
+        object_ids.push(object.object_id);
+    }
+
+    let details: Value = call_frontend_api(
+        &app,
+        &admin,
+        TestRequest::put().set_json(json!({ "accountId": other.account_id })),
+        &format!("qpidObjects/object/{}/creator", object_ids[0]),
+    )
+    .await
+    .unwrap();
+    assert_eq!(details["object"]["creator"]["id"], other.account_id);
+
+    let response: Value = call_frontend_api(
+        &app,
+        &admin,
+        TestRequest::put().set_json(json!({ "isDeleted": true })),
+        &format!("qpidObjects/creator/{}/deleted", griefer.account_id),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response["numAffectedObjects"], 1);
+
+    let conn = server.database.connect().await.unwrap();
+    let objects = conn.qpid_objects();
+    assert!(
+        !objects
+            .get_by_id(&object_ids[0])
+            .await
+            .unwrap()
+            .unwrap()
+            .is_deleted
+    );
+    assert!(
+        objects
+            .get_by_id(&object_ids[1])
+            .await
+            .unwrap()
+            .unwrap()
+            .is_deleted
+    );
+
+    let details: Value = call_frontend_api(
+        &app,
+        &admin,
+        TestRequest::put().set_json(json!({ "isDeleted": false })),
+        &format!("qpidObjects/object/{}/deleted", object_ids[1]),
+    )
+    .await
+    .unwrap();
+    assert_eq!(details["object"]["isDeleted"], false);
+
+    let status = call_frontend_api::<_, _, Value>(
+        &app,
+        &admin,
+        TestRequest::put().set_json(json!({ "isDeleted": true })),
+        "qpidObjects/object/missing/deleted",
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (entries, total) = conn
+        .audit_log()
+        .list(
+            &AuditLogFilter {
+                frontend_actor_id: Some(admin.account_id),
+                ..Default::default()
+            },
+            0,
+            10,
+        )
+        .await
+        .unwrap();
+
+    // Newest first, and nothing is recorded for the object that doesn't exist
+    assert_eq!(total, 3);
+    assert_eq!(entries[0].action, AuditAction::SetObjectDeleted);
+    assert_eq!(
+        entries[0].target_id.as_deref(),
+        Some(object_ids[1].as_str())
+    );
+    assert_eq!(entries[0].before.as_ref().unwrap()["isDeleted"], true);
+    assert_eq!(entries[0].after.as_ref().unwrap()["isDeleted"], false);
+    assert_eq!(entries[1].action, AuditAction::SetObjectsDeletedByCreator);
+    assert_eq!(
+        entries[1].target_id.as_deref(),
+        Some(griefer.account_id.as_str())
+    );
+    assert_eq!(entries[1].after.as_ref().unwrap()["numAffectedObjects"], 1);
+    assert_eq!(entries[2].action, AuditAction::SetObjectCreator);
+    assert_eq!(
+        entries[2].before.as_ref().unwrap()["creatorId"],
+        griefer.account_id
+    );
+    assert_eq!(
+        entries[2].after.as_ref().unwrap()["creatorId"],
+        other.account_id
+    );
+}
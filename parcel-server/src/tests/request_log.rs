@@ -0,0 +1,68 @@
+use actix_web::test::TestRequest;
+use serde_json::json;
+
+use crate::middleware::request_logger::{
+    redact_query, redact_uri, redacted_json_body, redacted_request_line,
+};
+
+#[test]
+fn provider_codes_are_redacted_from_queries() {
+    assert_eq!(
+        redact_query("provider=steam&display_name=Sam&code=1234"),
+        "provider=steam&display_name=Sam&code=[redacted]"
+    );
+}
+
+#[test]
+fn local_auth_keys_are_redacted_from_the_access_log() {
+    let req = TestRequest::get()
+        .uri("/auth/local/secret-key?provider=steam&display_name=Sam&code=ticket")
+        .to_srv_request();
+
+    assert_eq!(
+        redacted_request_line(&req),
+        "GET /auth/local/[redacted]?provider=steam&display_name=Sam&code=[redacted] HTTP/1.1"
+    );
+    assert_eq!(
+        redact_uri("//auth/local/secret-key/"),
+        "//auth/local/[redacted]"
+    );
+    assert_eq!(
+        redact_uri("https://example.com/login?code=1234"),
+        "https://example.com/login?code=[redacted]"
+    );
+    assert_eq!(redact_uri("/ds/e/findQpidObjects"), "/ds/e/findQpidObjects");
+}
+
+#[test]
+fn session_tokens_are_redacted_from_nested_bodies() {
+    let body = json!({
+        "session": {
+            "gateway": "http://localhost/ds",
+            "token": "secret session token",
+        },
+        "users": [{ "callbackToken": "another secret" }],
+    });
+
+    let redacted = redacted_json_body(&serde_json::to_vec(&body).unwrap()).unwrap();
+
+    assert_eq!(
+        redacted,
+        json!({
+            "session": {
+                "gateway": "http://localhost/ds",
+                "token": "[redacted]",
+            },
+            "users": [{ "callbackToken": "[redacted]" }],
+        })
+    );
+}
+
+#[test]
+fn non_json_bodies_are_not_logged() {
+    assert_eq!(
+        redacted_json_body(b"not json"),
+        Some(json!("[8 bytes of non json data]"))
+    );
+    assert_eq!(redacted_json_body(b""), None);
+}
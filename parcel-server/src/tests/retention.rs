@@ -0,0 +1,109 @@
+use parcel_common::api_types::{
+    frontend::{audit_log::AuditAction, settings::RetentionSettings},
+    object::Object,
+    requests::{create_object::CreateObjectRequest, find_qpid_objects::FindQpidObjectsResponse},
+};
+
+use crate::{
+    background_jobs::apply_retention_rules::RetentionCandidates,
+    data::database::audit_log::AuditLogFilter,
+};
+
+use super::{
+    call_game_api,
+    likes::{create_postbox_request, find_objects_request},
+    TestServer,
+};
+
+#[actix_web::test]
+async fn objects_over_the_area_limit_are_deleted() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let creator = server.login("Creator").await.unwrap();
+    let visitor = server.login("Visitor").await.unwrap();
+
+    for pos_x in [1000, 2000, 3000] {
+        call_game_api::<_, _, _, Object>(&app, Some(&creator), "createObject", &postbox_at(pos_x))
+            .await
+            .unwrap();
+    }
+
+    let rules = RetentionSettings {
+        max_objects_per_qpid_area: Some(1),
+        ..Default::default()
+    };
+
+    let conn = server.database.connect().await.unwrap();
+    let candidates = RetentionCandidates::find(&conn, &rules).await.unwrap();
+    assert_eq!(candidates.report().objects_over_area_limit, 2);
+
+    let report = candidates.remove(&conn, None).await.unwrap();
+    assert_eq!(report.objects_over_area_limit, 2);
+
+    // The run is recorded once with the counts of what was removed
+    let (entries, total) = conn
+        .audit_log()
+        .list(
+            &AuditLogFilter {
+                action: Some(AuditAction::ApplyRetentionRules),
+                ..Default::default()
+            },
+            0,
+            10,
+        )
+        .await
+        .unwrap();
+    assert_eq!(total, 1);
+    assert_eq!(entries[0].frontend_actor_id, None);
+    assert_eq!(
+        entries[0].after.as_ref().unwrap()["objectsOverAreaLimit"],
+        2
+    );
+    assert_eq!(entries[0].after.as_ref().unwrap()["unlikedObjects"], 0);
+
+    let found: FindQpidObjectsResponse = call_game_api(
+        &app,
+        Some(&visitor),
+        "findQpidObjects",
+        &find_objects_request(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(found.normal.object_p.unwrap_or_default().len(), 1);
+}
+
+#[actix_web::test]
+async fn unliked_objects_do_not_count_towards_the_area_limit() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let creator = server.login("Creator").await.unwrap();
+
+    for pos_x in [1000, 2000] {
+        call_game_api::<_, _, _, Object>(&app, Some(&creator), "createObject", &postbox_at(pos_x))
+            .await
+            .unwrap();
+    }
+
+    let rules = RetentionSettings {
+        delete_unliked_objects_after_days: Some(0),
+        max_objects_per_qpid_area: Some(1),
+        ..Default::default()
+    };
+
+    let conn = server.database.connect().await.unwrap();
+    let report = RetentionCandidates::find(&conn, &rules)
+        .await
+        .unwrap()
+        .report();
+
+    assert_eq!(report.unliked_objects, 2);
+    assert_eq!(report.objects_over_area_limit, 0);
+}
+
+fn postbox_at(pos_x: i32) -> CreateObjectRequest {
+    CreateObjectRequest {
+        pos_x,
+        ..create_postbox_request()
+    }
+}
@@ -0,0 +1,135 @@
+use actix_http::Request;
+use actix_web::{
+    body::MessageBody,
+    dev::{Service, ServiceResponse},
+};
+use parcel_common::api_types::{
+    area::AreaHash,
+    requests::{
+        create_road::CreateRoadRequest,
+        find_qpid_objects::{FindQpidObjectsRequest, FindQpidObjectsResponse, RoadRequest},
+    },
+    road::Road,
+};
+
+use super::{call_game_api, TestPlayer, TestServer};
+
+const DATA_VERSION: i32 = 2;
+const END_QPID_ID: i32 = 200;
+
+pub fn create_road_request(start_location_id: i32, end_location_id: i32) -> CreateRoadRequest {
+    CreateRoadRequest {
+        area_hash: AreaHash::CentralRegion,
+        data: "AAAA".into(),
+        data_version: DATA_VERSION,
+        start_location_id,
+        end_location_id,
+        start_qpid_id: 100,
+        end_qpid_id: END_QPID_ID,
+        max_height_difference: 10,
+        path_length: 500,
+        via_qpids: None,
+    }
+}
+
+fn find_roads_request(
+    prioritized_location_id: i32,
+    required_location_id: Option<i32>,
+) -> FindQpidObjectsRequest {
+    FindQpidObjectsRequest {
+        area_hash: AreaHash::CentralRegion,
+        qpid_id: END_QPID_ID,
+        account_ids: None,
+        object: None,
+        road: Some(RoadRequest {
+            count: 10,
+            prioritized_location_id,
+            end_qpids: vec![END_QPID_ID],
+            required_location_id,
+            data_version: DATA_VERSION,
+        }),
+        mission: None,
+    }
+}
+
+async fn find_road_ids<S, B>(
+    app: &S,
+    player: &TestPlayer,
+    request: &FindQpidObjectsRequest,
+) -> Vec<String>
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let found: FindQpidObjectsResponse =
+        call_game_api(app, Some(player), "findQpidObjects", request)
+            .await
+            .unwrap();
+
+    found
+        .normal
+        .roads
+        .unwrap_or_default()
+        .into_iter()
+        .map(|road| road.online_id)
+        .collect()
+}
+
+#[actix_web::test]
+async fn roads_from_strand_contracts_and_prioritized_locations_come_first() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let contract = server.login("Contract").await.unwrap();
+    let stranger = server.login("Stranger").await.unwrap();
+    let visitor = server.login("Visitor").await.unwrap();
+
+    let contract_road: Road = call_game_api(
+        &app,
+        Some(&contract),
+        "createRoad",
+        &create_road_request(1, 2),
+    )
+    .await
+    .unwrap();
+    let prioritized_road: Road = call_game_api(
+        &app,
+        Some(&stranger),
+        "createRoad",
+        &create_road_request(3, 4),
+    )
+    .await
+    .unwrap();
+    // Created last so it would be returned first if only the creation date was used
+    let other_road: Road = call_game_api(
+        &app,
+        Some(&stranger),
+        "createRoad",
+        &create_road_request(5, 6),
+    )
+    .await
+    .unwrap();
+
+    let conn = server.database.connect().await.unwrap();
+    conn.accounts()
+        .add_strand_contracts(
+            &visitor.account_id,
+            [contract.account_id.as_str()].into_iter(),
+        )
+        .await
+        .unwrap();
+
+    let found = find_road_ids(&app, &visitor, &find_roads_request(4, None)).await;
+
+    assert_eq!(
+        found,
+        vec![
+            contract_road.online_id,
+            prioritized_road.online_id,
+            other_road.online_id.clone()
+        ]
+    );
+
+    let found = find_road_ids(&app, &visitor, &find_roads_request(-1, Some(5))).await;
+
+    assert_eq!(found, vec![other_road.online_id]);
+}
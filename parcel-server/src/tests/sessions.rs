@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use bincode::Options;
+use chrono::{Duration, Utc};
+use parcel_common::api_types::{
+    auth::Provider,
+    requests::get_like_history::{GetLikeHistoryRequest, GetLikeHistoryResponse},
+};
+use serde::Serialize;
+
+use crate::{data::session_store::SessionStore, session::LAST_SEEN_RESOLUTION_SECS};
+
+use super::{call_game_api, TempDir, TestPlayer, TestServer};
+
+/// The format sessions were saved to the sessions file in by earlier releases.
+#[derive(Serialize)]
+struct LegacySession {
+    provider: Provider,
+    provider_id: String,
+    account_id: String,
+    token: String,
+    values: HashMap<String, String>,
+}
+
+#[actix_web::test]
+async fn requests_update_last_seen() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let player = server.login("Sam").await.unwrap();
+
+    // Pretend the player logged in and made their last request a while ago
+    let long_ago = Utc::now().naive_utc() - Duration::seconds(LAST_SEEN_RESOLUTION_SECS * 10);
+    let mut session = server
+        .session_store
+        .load_session(&player.token)
+        .await
+        .unwrap()
+        .unwrap();
+    session.logged_in_at = long_ago;
+    session.last_seen_at = long_ago;
+    server.session_store.save_session(session).await.unwrap();
+
+    call_game_api::<_, _, _, GetLikeHistoryResponse>(
+        &app,
+        Some(&player),
+        "getLikeHistory",
+        &GetLikeHistoryRequest {
+            since: 0,
+            account_id: "".into(),
+        },
+    )
+    .await
+    .unwrap();
+
+    let session = server
+        .session_store
+        .list_sessions()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|session| session.account_id == player.account_id)
+        .unwrap();
+    assert_eq!(session.logged_in_at, long_ago);
+    assert!(
+        (Utc::now().naive_utc() - session.last_seen_at).num_seconds() < LAST_SEEN_RESOLUTION_SECS
+    );
+}
+
+#[actix_web::test]
+async fn kicked_sessions_are_not_restored_by_last_seen_updates() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let sam = server.login("Sam").await.unwrap();
+    let fragile = server.login("Fragile").await.unwrap();
+
+    server
+        .session_store
+        .delete_session(&sam.token)
+        .await
+        .unwrap();
+    server
+        .session_store
+        .update_last_seen(&sam.token, Utc::now().naive_utc())
+        .await
+        .unwrap();
+    assert!(server
+        .session_store
+        .load_session(&sam.token)
+        .await
+        .unwrap()
+        .is_none());
+
+    let error = call_game_api::<_, _, _, GetLikeHistoryResponse>(
+        &app,
+        Some(&sam),
+        "getLikeHistory",
+        &GetLikeHistoryRequest {
+            since: 0,
+            account_id: "".into(),
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(error.status, "AU-UT");
+
+    assert_eq!(server.session_store.list_sessions().await.unwrap().len(), 1);
+    assert_eq!(server.session_store.delete_all_sessions().await.unwrap(), 1);
+    assert!(server
+        .session_store
+        .load_session(&fragile.token)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(server
+        .session_store
+        .list_sessions()
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[actix_web::test]
+async fn sessions_are_shared_between_server_instances() {
+    let server = TestServer::start().await.unwrap();
+    let player = server.login("Sam").await.unwrap();
+
+    // A restarted server, or another server using the same database
+    let other_store = SessionStore::new(server.database.clone().into_inner());
+
+    let session = other_store
+        .load_session(&player.token)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(session.account_id, player.account_id);
+
+    other_store.delete_session(&player.token).await.unwrap();
+    assert!(other_store
+        .load_session(&player.token)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[actix_web::test]
+async fn sessions_expire_when_unused() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let player = server.login("Sam").await.unwrap();
+
+    let mut session = server
+        .session_store
+        .load_session(&player.token)
+        .await
+        .unwrap()
+        .unwrap();
+    session.last_seen_at = Utc::now().naive_utc() - Duration::days(2);
+    server.session_store.save_session(session).await.unwrap();
+
+    let error = call_game_api::<_, _, _, GetLikeHistoryResponse>(
+        &app,
+        Some(&player),
+        "getLikeHistory",
+        &GetLikeHistoryRequest {
+            since: 0,
+            account_id: "".into(),
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(error.status, "AU-UT");
+    assert!(server
+        .session_store
+        .list_sessions()
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[actix_web::test]
+async fn legacy_sessions_are_imported() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let account_id = server.login("Sam").await.unwrap().account_id;
+    let dir = TempDir::new().unwrap();
+    let file_path = dir.path().join("sessions");
+
+    let bytes = bincode::DefaultOptions::new()
+        .with_varint_encoding()
+        .with_little_endian()
+        .serialize(&[LegacySession {
+            provider: Provider::Steam,
+            provider_id: "fake_Sam".into(),
+            account_id: account_id.clone(),
+            token: "legacy_token".into(),
+            values: HashMap::new(),
+        }])
+        .unwrap();
+    std::fs::write(&file_path, bytes).unwrap();
+
+    let num_imported = server
+        .session_store
+        .import_legacy_sessions(&file_path)
+        .await
+        .unwrap();
+    assert_eq!(num_imported, 1);
+    assert!(!file_path.exists());
+
+    let player = TestPlayer {
+        account_id,
+        token: "legacy_token".into(),
+    };
+    call_game_api::<_, _, _, GetLikeHistoryResponse>(
+        &app,
+        Some(&player),
+        "getLikeHistory",
+        &GetLikeHistoryRequest {
+            since: 0,
+            account_id: "".into(),
+        },
+    )
+    .await
+    .unwrap();
+}
+
+#[actix_web::test]
+async fn unreadable_legacy_sessions_are_moved_aside() {
+    let server = TestServer::start().await.unwrap();
+    let dir = TempDir::new().unwrap();
+    let file_path = dir.path().join("sessions");
+    // A list of five sessions that ends after the first session's provider
+    std::fs::write(&file_path, [5, 0]).unwrap();
+
+    let num_imported = server
+        .session_store
+        .import_legacy_sessions(&file_path)
+        .await
+        .unwrap();
+    assert_eq!(num_imported, 0);
+    assert!(!file_path.exists());
+    assert!(dir.path().join("sessions.unreadable").exists());
+}
@@ -0,0 +1,183 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::Utc;
+use parcel_common::api_types::frontend::auth::{AuthAccountInfo, CheckAuthResponse};
+use serde_json::json;
+
+use crate::{
+    data::shared_state::{DatabaseSharedState, SharedState, SharedStateEvent},
+    frontend::shared_caches,
+    settings::SharedStatePersist,
+    ServerSettings,
+};
+
+use super::TestServer;
+
+/// How long to wait for an event to arrive from the database before failing.
+const EVENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+async fn start_instance(server: &TestServer) -> Arc<DatabaseSharedState> {
+    Arc::new(
+        DatabaseSharedState::start(server.database.clone().into_inner(), &server.database_url)
+            .await
+            .unwrap(),
+    )
+}
+
+#[actix_web::test]
+async fn events_are_received_by_every_instance() {
+    let server = TestServer::start().await.unwrap();
+    let first = start_instance(&server).await;
+    let second = start_instance(&server).await;
+    let mut first_events = first.subscribe();
+    let mut second_events = second.subscribe();
+
+    first
+        .publish(&SharedStateEvent::FrontendPermissionsChanged { account_id: 42 })
+        .await
+        .unwrap();
+
+    for events in [&mut first_events, &mut second_events] {
+        let event = tokio::time::timeout(EVENT_TIMEOUT, events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            event,
+            SharedStateEvent::FrontendPermissionsChanged { account_id: 42 }
+        ));
+    }
+}
+
+#[actix_web::test]
+async fn first_inserted_value_is_kept() {
+    let server = TestServer::start().await.unwrap();
+    let first = start_instance(&server).await;
+    let second = start_instance(&server).await;
+
+    let first_secret = first
+        .get_or_insert("jwt_secret", json!("first"))
+        .await
+        .unwrap();
+    let second_secret = second
+        .get_or_insert("jwt_secret", json!("second"))
+        .await
+        .unwrap();
+
+    assert_eq!(first_secret, json!("first"));
+    assert_eq!(second_secret, json!("first"));
+}
+
+#[actix_web::test]
+async fn expired_values_are_ignored() {
+    let server = TestServer::start().await.unwrap();
+    let shared_state = start_instance(&server).await;
+    let now = Utc::now().naive_utc();
+
+    shared_state
+        .set(
+            "revoked_session/expired",
+            json!(1),
+            Some(now - chrono::Duration::minutes(1)),
+        )
+        .await
+        .unwrap();
+    shared_state
+        .set(
+            "revoked_session/active",
+            json!(2),
+            Some(now + chrono::Duration::minutes(1)),
+        )
+        .await
+        .unwrap();
+    // Underscores should not be treated as wildcards when listing by prefix
+    shared_state
+        .set("revokedXsession/other", json!(3), None)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        shared_state.get("revoked_session/expired").await.unwrap(),
+        None
+    );
+    assert_eq!(
+        shared_state.list("revoked_session/").await.unwrap(),
+        vec![("revoked_session/active".to_owned(), json!(2))]
+    );
+}
+
+#[actix_web::test]
+async fn settings_changes_are_loaded_by_other_instances() {
+    let server = TestServer::start().await.unwrap();
+    let first = start_instance(&server).await;
+    let second = start_instance(&server).await;
+    let first_settings = ServerSettings::load(SharedStatePersist::new(first, "settings"))
+        .await
+        .unwrap();
+    let second_settings = ServerSettings::load(SharedStatePersist::new(second, "settings"))
+        .await
+        .unwrap();
+
+    first_settings
+        .write(|settings| settings.public_server = true)
+        .await
+        .unwrap();
+
+    // The second instance reloads the settings after the change event reaches it
+    let reloaded = tokio::time::timeout(EVENT_TIMEOUT, async {
+        while !second_settings.read().await.public_server {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await;
+
+    assert!(reloaded.is_ok());
+}
+
+#[actix_web::test]
+async fn auth_response_is_taken_once_by_another_instance() {
+    let server = TestServer::start().await.unwrap();
+    let first = start_instance(&server).await;
+    let second = start_instance(&server).await;
+    let mut second_events = second.subscribe();
+
+    shared_caches::store_auth_response(
+        &*first,
+        "callback",
+        &CheckAuthResponse::Success(AuthAccountInfo {
+            name: "Sam".into(),
+            avatar_url: None,
+            auth_token: "secret_token".into(),
+            game_account_id: None,
+            permissions: Vec::new(),
+        }),
+    )
+    .await
+    .unwrap();
+
+    // Only the callback token is sent to the other instances
+    let event = tokio::time::timeout(EVENT_TIMEOUT, second_events.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!serde_json::to_string(&event)
+        .unwrap()
+        .contains("secret_token"));
+    let SharedStateEvent::FrontendAuthFinished { callback_token } = event else {
+        panic!("Unexpected event {event:?}");
+    };
+
+    let response = shared_caches::take_auth_response(&*second, &callback_token)
+        .await
+        .unwrap();
+    assert!(matches!(
+        response,
+        Some(CheckAuthResponse::Success(AuthAccountInfo { auth_token, .. })) if auth_token == "secret_token"
+    ));
+
+    // The response is deleted once it has been read
+    assert!(shared_caches::take_auth_response(&*first, &callback_token)
+        .await
+        .unwrap()
+        .is_none());
+}
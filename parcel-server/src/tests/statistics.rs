@@ -0,0 +1,121 @@
+use actix_http::StatusCode;
+use actix_web::test::TestRequest;
+use chrono::{Duration, Utc};
+use flagset::FlagSet;
+use parcel_common::api_types::{
+    frontend::auth::FrontendPermissions,
+    object::{Object, ObjectType},
+    requests::send_like::SendLikeRequest,
+};
+use serde_json::Value;
+
+use crate::data::database::statistics::StatisticsFilter;
+
+use super::{call_frontend_api, call_game_api, likes::create_postbox_request, TestServer};
+
+#[actix_web::test]
+async fn statistics_are_aggregated_per_account_and_server_wide() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let creator = server.login("Creator").await.unwrap();
+    let liker = server.login("Liker").await.unwrap();
+
+    let postbox: Object = call_game_api(
+        &app,
+        Some(&creator),
+        "createObject",
+        &create_postbox_request(),
+    )
+    .await
+    .unwrap();
+    call_game_api::<_, _, _, Object>(
+        &app,
+        Some(&liker),
+        "createObject",
+        &create_postbox_request(),
+    )
+    .await
+    .unwrap();
+
+    call_game_api::<_, _, _, ()>(
+        &app,
+        Some(&liker),
+        "sendLike",
+        &SendLikeRequest {
+            index: -1,
+            likes_auto: 3,
+            likes_manual: 2,
+            like_type: "".into(),
+            online_id: postbox.object_id.clone(),
+            online_type: "p".into(),
+            account_id: creator.account_id.clone(),
+        },
+    )
+    .await
+    .unwrap();
+
+    let conn = server.database.connect().await.unwrap();
+    let statistics = conn.statistics();
+
+    let creator_filter = StatisticsFilter {
+        account_id: Some(&creator.account_id),
+        ..Default::default()
+    };
+    let objects = statistics.count_objects(&creator_filter).await.unwrap();
+    assert_eq!(objects.len(), 1);
+    assert_eq!(objects[0].object_type, ObjectType::Postbox);
+    assert_eq!(objects[0].count, 1);
+
+    let likes = statistics.likes_per_day(&creator_filter).await.unwrap();
+    assert_eq!(likes.len(), 1);
+    assert_eq!(likes[0].likes_given, 0);
+    assert_eq!(likes[0].likes_received, 5);
+
+    let liker_filter = StatisticsFilter {
+        account_id: Some(&liker.account_id),
+        ..Default::default()
+    };
+    let likes = statistics.likes_per_day(&liker_filter).await.unwrap();
+    assert_eq!(likes[0].likes_given, 5);
+    assert_eq!(likes[0].likes_received, 0);
+
+    let server_wide = StatisticsFilter::default();
+    let objects = statistics.count_objects(&server_wide).await.unwrap();
+    assert_eq!(objects[0].count, 2);
+
+    // Nothing happened in the future
+    let future = StatisticsFilter {
+        from: Some(Utc::now().naive_utc() + Duration::days(1)),
+        ..Default::default()
+    };
+    assert!(statistics.count_objects(&future).await.unwrap().is_empty());
+    assert!(statistics.likes_per_day(&future).await.unwrap().is_empty());
+
+    let missions = statistics.count_missions(&creator_filter).await.unwrap();
+    assert_eq!(missions.created, 0);
+    assert_eq!(missions.completed, 0);
+}
+
+#[actix_web::test]
+async fn server_statistics_require_permission() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let player = server
+        .login_frontend(FlagSet::<FrontendPermissions>::default())
+        .await
+        .unwrap();
+    let admin = server
+        .login_frontend(FrontendPermissions::ManageAccounts)
+        .await
+        .unwrap();
+
+    let status = call_frontend_api::<_, _, Value>(&app, &player, TestRequest::get(), "statistics")
+        .await
+        .unwrap_err();
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let statistics: Value = call_frontend_api(&app, &admin, TestRequest::get(), "statistics")
+        .await
+        .unwrap();
+    assert!(statistics.is_object());
+}
@@ -0,0 +1,196 @@
+use chrono::Utc;
+use parcel_common::api_types::{
+    object::Object,
+    requests::{devote_highway_resources::PutHistory, find_qpid_objects::FindQpidObjectsResponse},
+};
+
+use crate::{
+    data::database::likes::LikeTarget,
+    world_snapshot::{self, ConflictStrategy},
+};
+
+use super::{
+    call_game_api,
+    likes::{create_postbox_request, find_objects_request},
+    TempDir, TestServer,
+};
+
+#[actix_web::test]
+async fn exported_world_can_be_imported_on_another_server() {
+    let source = TestServer::start().await.unwrap();
+    let source_app = source.init_app().await;
+    let creator = source.login("Creator").await.unwrap();
+
+    let created: Object = call_game_api(
+        &source_app,
+        Some(&creator),
+        "createObject",
+        &create_postbox_request(),
+    )
+    .await
+    .unwrap();
+
+    let archive_dir = TempDir::new().unwrap();
+    let archive_path = archive_dir.path().join("world.zip");
+    let manifest = world_snapshot::export_world(&source.database, &archive_path)
+        .await
+        .unwrap();
+
+    assert_eq!(manifest.tables["accounts"], 1);
+    assert_eq!(manifest.tables["qpid_objects"], 1);
+
+    let target = TestServer::start().await.unwrap();
+    let target_app = target.init_app().await;
+    world_snapshot::import_world(&target.database, &archive_path, ConflictStrategy::Skip)
+        .await
+        .unwrap();
+
+    // Logging in with the same account on the new server should give the imported account back
+    let player = target.login("Creator").await.unwrap();
+    assert_eq!(player.account_id, creator.account_id);
+
+    let visitor = target.login("Visitor").await.unwrap();
+    let found: FindQpidObjectsResponse = call_game_api(
+        &target_app,
+        Some(&visitor),
+        "findQpidObjects",
+        &find_objects_request(),
+    )
+    .await
+    .unwrap();
+    let found_objects = found.normal.object_p.unwrap_or_default();
+
+    assert_eq!(found_objects.len(), 1);
+    assert_eq!(found_objects[0].object_id, created.object_id);
+    assert_eq!(found_objects[0].creator_account_id, creator.account_id);
+}
+
+#[actix_web::test]
+async fn conflicting_ids_are_skipped_or_remapped() {
+    let server = TestServer::start().await.unwrap();
+    let app = server.init_app().await;
+    let creator = server.login("Creator").await.unwrap();
+    let visitor = server.login("Visitor").await.unwrap();
+
+    call_game_api::<_, _, _, Object>(
+        &app,
+        Some(&creator),
+        "createObject",
+        &create_postbox_request(),
+    )
+    .await
+    .unwrap();
+
+    let archive_dir = TempDir::new().unwrap();
+    let archive_path = archive_dir.path().join("world.zip");
+    world_snapshot::export_world(&server.database, &archive_path)
+        .await
+        .unwrap();
+
+    let count_objects = || async {
+        let found: FindQpidObjectsResponse = call_game_api(
+            &app,
+            Some(&visitor),
+            "findQpidObjects",
+            &find_objects_request(),
+        )
+        .await
+        .unwrap();
+
+        found.normal.object_p.unwrap_or_default().len()
+    };
+
+    // Importing the world into itself merges the accounts and skips the objects that already exist
+    world_snapshot::import_world(&server.database, &archive_path, ConflictStrategy::Skip)
+        .await
+        .unwrap();
+    assert_eq!(count_objects().await, 1);
+
+    // Remapping imports a copy of the objects with new ids
+    world_snapshot::import_world(&server.database, &archive_path, ConflictStrategy::Remap)
+        .await
+        .unwrap();
+    assert_eq!(count_objects().await, 2);
+}
+
+#[actix_web::test]
+async fn importing_the_same_world_twice_does_not_add_to_the_totals_again() {
+    let source = TestServer::start().await.unwrap();
+    let giver = source.login("Giver").await.unwrap();
+    let receiver = source.login("Receiver").await.unwrap();
+
+    let conn = source.database.connect().await.unwrap();
+    conn.likes()
+        .give_likes(
+            0,
+            3,
+            "highway",
+            &giver.account_id,
+            &receiver.account_id,
+            LikeTarget::Highway(1000),
+        )
+        .await
+        .unwrap();
+    conn.highway_resources()
+        .devote_resources(
+            &giver.account_id,
+            &[PutHistory {
+                construction_id: 1000,
+                put_num: 150,
+                resource_id: 2,
+            }],
+        )
+        .await
+        .unwrap();
+    conn.accounts()
+        .add_relationship_history(
+            &giver.account_id,
+            &receiver.account_id,
+            &Utc::now().naive_utc(),
+        )
+        .await
+        .unwrap();
+
+    let archive_dir = TempDir::new().unwrap();
+    let archive_path = archive_dir.path().join("world.zip");
+    world_snapshot::export_world(&source.database, &archive_path)
+        .await
+        .unwrap();
+
+    let target = TestServer::start().await.unwrap();
+    let first =
+        world_snapshot::import_world(&target.database, &archive_path, ConflictStrategy::Skip)
+            .await
+            .unwrap();
+    let second =
+        world_snapshot::import_world(&target.database, &archive_path, ConflictStrategy::Skip)
+            .await
+            .unwrap();
+
+    for table in [
+        "likes",
+        "devoted_highway_resources",
+        "account_histories",
+        "total_highway_likes",
+        "total_highway_resources",
+    ] {
+        assert_eq!(first.tables[table].imported, 1, "{table}");
+        assert_eq!(second.tables[table].imported, 0, "{table}");
+    }
+
+    let conn = target.database.connect().await.unwrap();
+    let total_likes = conn
+        .likes()
+        .get_total_highway_likes([receiver.account_id.as_str()])
+        .await
+        .unwrap();
+    assert_eq!(total_likes[&receiver.account_id], 3);
+
+    let total_resources = conn
+        .highway_resources()
+        .list_total_resources()
+        .await
+        .unwrap();
+    assert_eq!(total_resources.len(), 1);
+    assert_eq!(total_resources[0].num_resources, 150);
+}
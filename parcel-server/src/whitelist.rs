@@ -1,8 +1,10 @@
 use std::path::Path;
 
 use parcel_common::api_types::frontend::settings::WhitelistEntry;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Whitelist(Vec<WhitelistEntry>);
 
 impl Whitelist {
@@ -35,10 +37,10 @@ impl std::ops::DerefMut for Whitelist {
     }
 }
 
-pub struct WhitelistPersist;
+pub struct WhitelistFormat;
 
 #[async_trait::async_trait]
-impl super::settings::Persist<Whitelist> for WhitelistPersist {
+impl super::settings::FileFormat<Whitelist> for WhitelistFormat {
     // The file format looks like the following:
     // There is one line for each entry:
     // provider_id;name_reference
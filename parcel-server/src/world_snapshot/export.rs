@@ -0,0 +1,70 @@
+use std::{collections::BTreeMap, fs::File, io::Write, path::Path};
+
+use anyhow::Context;
+use chrono::Utc;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use serde_json::Value;
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use crate::data::database::Database;
+
+use super::{table_path, Manifest, FORMAT_VERSION, MANIFEST_PATH, TABLES};
+
+/// Exports the shared world to an archive at the specified path, overwriting it if it already exists.
+pub async fn export_world(database: &Database, path: &Path) -> Result<Manifest, anyhow::Error> {
+    let conn = database.connect().await?;
+
+    // Read everything in one transaction so that the tables are consistent with each other
+    let (schema_version, tables) = conn
+        .transaction(|conn| {
+            async move {
+                let snapshots = conn.world_snapshots();
+                snapshots.use_snapshot_isolation().await?;
+
+                let schema_version = snapshots.schema_version().await?;
+                let mut tables = Vec::with_capacity(TABLES.len());
+
+                for table in TABLES {
+                    tables.push((table.name, snapshots.export_table(table.name).await?));
+                }
+
+                Ok((schema_version, tables))
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    let file = File::create(path)
+        .with_context(|| format!("Could not create archive at {}", path.display()))?;
+    let mut zip = ZipWriter::new(file);
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut row_counts = BTreeMap::new();
+
+    for (table, rows) in tables {
+        let num_rows = match serde_json::from_str::<Value>(&rows)? {
+            Value::Array(rows) => rows.len(),
+            _ => anyhow::bail!("Expected an array of rows from table {}", table),
+        };
+
+        zip.start_file(table_path(table), options)?;
+        zip.write_all(rows.as_bytes())?;
+
+        log::info!("Exported {} rows from {}", num_rows, table);
+        row_counts.insert(table.to_owned(), num_rows);
+    }
+
+    let manifest = Manifest {
+        format_version: FORMAT_VERSION,
+        schema_version,
+        exported_at: Utc::now().naive_utc(),
+        tables: row_counts,
+    };
+
+    zip.start_file(MANIFEST_PATH, options)?;
+    serde_json::to_writer_pretty(&mut zip, &manifest)?;
+    zip.finish()?;
+
+    log::info!("Exported world to {}", path.display());
+
+    Ok(manifest)
+}
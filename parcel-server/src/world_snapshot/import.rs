@@ -0,0 +1,485 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs::File,
+    io::Read,
+    path::Path,
+};
+
+use anyhow::Context;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use parcel_common::api_types::object::ObjectType;
+use serde_json::{Map, Value};
+use zip::ZipArchive;
+
+use crate::{
+    data::database::{
+        accounts::generate_account_id, missions::generate_mission_id,
+        qpid_objects::generate_object_id, roads::generate_road_id,
+        wasted_baggages::generate_wasted_baggage_id, world_snapshots::WorldSnapshots, Database,
+    },
+    db::QueryError,
+};
+
+use super::{
+    table_path, ConflictStrategy, IdKind, Manifest, PrimaryKey, Reference, Table, FORMAT_VERSION,
+    MANIFEST_PATH, TABLES,
+};
+
+/// Number of rows to insert per query for tables where the generated ids aren't needed.
+const BATCH_SIZE: usize = 500;
+
+type Row = Map<String, Value>;
+
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    /// Imported accounts that already existed on this server (same provider and provider id)
+    pub merged_accounts: usize,
+    /// Rows that were imported with a new id because the id was already used
+    pub remapped_ids: usize,
+    /// Number of imported and skipped rows per table
+    pub tables: BTreeMap<String, TableSummary>,
+}
+
+#[derive(Debug, Default)]
+pub struct TableSummary {
+    pub imported: usize,
+    pub skipped: usize,
+}
+
+/// Maps ids from the archive to the ids they were imported with.
+///
+/// Rows that weren't imported are not in the map.
+#[derive(Default)]
+struct IdMap(HashMap<IdKind, HashMap<String, Value>>);
+
+impl IdMap {
+    fn get(&self, kind: IdKind, old_id: &Value) -> Option<&Value> {
+        self.0.get(&kind)?.get(&id_key(old_id)?)
+    }
+
+    fn insert(&mut self, kind: IdKind, old_id: &Value, new_id: Value) {
+        if let Some(key) = id_key(old_id) {
+            self.0.entry(kind).or_default().insert(key, new_id);
+        }
+    }
+}
+
+fn id_key(id: &Value) -> Option<String> {
+    match id {
+        Value::String(id) => Some(id.clone()),
+        Value::Number(id) => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+/// Imports a world archive created with `export_world`.
+///
+/// Everything is imported in a single transaction, so if anything fails nothing is imported.
+pub async fn import_world(
+    database: &Database,
+    path: &Path,
+    conflict_strategy: ConflictStrategy,
+) -> Result<ImportSummary, anyhow::Error> {
+    let file = File::open(path)
+        .with_context(|| format!("Could not open archive at {}", path.display()))?;
+    let mut zip = ZipArchive::new(file).context("Could not read archive")?;
+
+    let manifest: Manifest = serde_json::from_reader(
+        zip.by_name(MANIFEST_PATH)
+            .context("The archive is missing a manifest")?,
+    )
+    .context("Could not parse manifest")?;
+
+    if manifest.format_version != FORMAT_VERSION {
+        anyhow::bail!(
+            "Unsupported archive version {} (expected {})",
+            manifest.format_version,
+            FORMAT_VERSION
+        );
+    }
+
+    let mut tables = Vec::with_capacity(TABLES.len());
+
+    for table in TABLES {
+        let mut json = String::new();
+        match zip.by_name(&table_path(table.name)) {
+            Ok(mut file) => file.read_to_string(&mut json)?,
+            Err(zip::result::ZipError::FileNotFound) => {
+                anyhow::bail!("The archive is missing the {} table", table.name)
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let rows: Vec<Row> = serde_json::from_str(&json)
+            .with_context(|| format!("Could not parse rows of table {}", table.name))?;
+        tables.push((table, rows));
+    }
+
+    let conn = database.connect().await?;
+    let schema_version = conn.world_snapshots().schema_version().await?;
+
+    if manifest.schema_version != schema_version {
+        anyhow::bail!(
+            "The archive was exported from a server with a different database version ({}, this server is at {}). Export it again with the same server version as this one",
+            manifest.schema_version,
+            schema_version
+        );
+    }
+
+    let summary = conn
+        .transaction(|conn| {
+            async move {
+                let snapshots = conn.world_snapshots();
+                let mut importer = Importer {
+                    snapshots: &snapshots,
+                    conflict_strategy,
+                    ids: IdMap::default(),
+                    skipped_rows: HashMap::default(),
+                    summary: ImportSummary::default(),
+                };
+
+                for (table, rows) in tables {
+                    importer.import_table(table, rows).await?;
+                }
+
+                Ok(importer.summary)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    log::info!(
+        "Imported world from {} ({} accounts merged, {} ids remapped)",
+        path.display(),
+        summary.merged_accounts,
+        summary.remapped_ids
+    );
+
+    Ok(summary)
+}
+
+struct Importer<'a> {
+    snapshots: &'a WorldSnapshots<'a>,
+    conflict_strategy: ConflictStrategy,
+    ids: IdMap,
+    /// Rows that were skipped because they matched an existing row, keyed by table name
+    skipped_rows: HashMap<&'static str, Vec<Row>>,
+    summary: ImportSummary,
+}
+
+impl<'a> Importer<'a> {
+    async fn import_table(&mut self, table: &Table, rows: Vec<Row>) -> Result<(), QueryError> {
+        let num_rows = rows.len();
+        let mut rows = rows
+            .into_iter()
+            .filter_map(|row| self.map_references(table, row))
+            .collect::<Vec<_>>();
+
+        self.subtract_skipped_totals(table, &mut rows);
+
+        let mut columns = self.snapshots.table_columns(table.name).await?;
+
+        let imported = match table.primary_key {
+            PrimaryKey::Text(kind) => {
+                rows = self.map_text_ids(table, kind, rows).await?;
+                self.insert_rows(table, &columns, &rows).await?
+            }
+            PrimaryKey::Serial(kind) => {
+                columns.retain(|column| column != "id");
+
+                match kind {
+                    Some(kind) => {
+                        let mut imported = 0;
+
+                        for mut row in rows {
+                            let old_id = row.remove("id").unwrap_or_default();
+                            let new_id = self
+                                .snapshots
+                                .insert_row_returning_id(
+                                    table.name,
+                                    &columns,
+                                    &to_json(&row),
+                                    table.on_conflict,
+                                )
+                                .await?;
+
+                            if let Some(new_id) = new_id {
+                                self.ids.insert(kind, &old_id, new_id.into());
+                                imported += 1;
+                            }
+                        }
+
+                        imported
+                    }
+                    None => self.insert_rows(table, &columns, &rows).await?,
+                }
+            }
+            PrimaryKey::Natural(key_columns) => {
+                columns.retain(|column| column != "id");
+                rows = self.skip_existing_rows(table, key_columns, rows).await?;
+                self.insert_rows(table, &columns, &rows).await?
+            }
+            PrimaryKey::None => self.insert_rows(table, &columns, &rows).await?,
+        };
+
+        log::info!(
+            "Imported {} out of {} rows into {}",
+            imported,
+            num_rows,
+            table.name
+        );
+
+        self.summary.tables.insert(
+            table.name.to_owned(),
+            TableSummary {
+                imported,
+                skipped: num_rows - imported,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Changes references to other tables to the ids they were imported with.
+    /// Returns None if the row references something that wasn't imported.
+    fn map_references(&self, table: &Table, mut row: Row) -> Option<Row> {
+        for reference in table.references {
+            match reference {
+                Reference::Required(column, kind) => {
+                    let value = row.get_mut(*column)?;
+
+                    if value.is_null() {
+                        continue;
+                    }
+
+                    *value = self.ids.get(*kind, value)?.clone();
+                }
+                Reference::Soft(column, kind) => {
+                    if let Some(value) = row.get_mut(*column) {
+                        if let Some(new_value) = self.ids.get(*kind, value) {
+                            *value = new_value.clone();
+                        }
+                    }
+                }
+            }
+        }
+
+        Some(row)
+    }
+
+    /// Decides which id each row will be imported with and removes rows that should be skipped.
+    async fn map_text_ids(
+        &mut self,
+        table: &Table,
+        kind: IdKind,
+        rows: Vec<Row>,
+    ) -> Result<Vec<Row>, QueryError> {
+        let ids = rows
+            .iter()
+            .filter_map(|row| row.get("id").and_then(Value::as_str).map(String::from))
+            .collect::<Vec<_>>();
+        let mut used_ids = self.snapshots.existing_ids(table.name, &ids).await?;
+        let mut result = Vec::with_capacity(rows.len());
+
+        for mut row in rows {
+            let old_id = row.get("id").cloned().unwrap_or_default();
+
+            if kind == IdKind::Account {
+                // Accounts that already exist on this server are merged with the imported account
+                let provider = row.get("provider").and_then(Value::as_i64);
+                let provider_id = row.get("provider_id").and_then(Value::as_str);
+
+                if let (Some(provider), Some(provider_id)) = (provider, provider_id) {
+                    if let Some(existing_id) = self
+                        .snapshots
+                        .find_account_id_by_provider(provider as i32, provider_id)
+                        .await?
+                    {
+                        self.ids.insert(kind, &old_id, existing_id.into());
+                        self.summary.merged_accounts += 1;
+                        continue;
+                    }
+                }
+            }
+
+            let Some(id) = old_id.as_str() else {
+                log::warn!("Skipping row without an id in {}", table.name);
+                continue;
+            };
+
+            if used_ids.contains(id) {
+                match self.conflict_strategy {
+                    ConflictStrategy::Skip => continue,
+                    ConflictStrategy::Remap => {
+                        let Some(new_id) = generate_id(kind, &row) else {
+                            log::warn!("Could not generate a new id for {} in {}", id, table.name);
+                            continue;
+                        };
+                        used_ids.insert(new_id.clone());
+                        row.insert("id".into(), Value::String(new_id.clone()));
+                        self.ids.insert(kind, &old_id, Value::String(new_id));
+                        self.summary.remapped_ids += 1;
+                    }
+                }
+            } else {
+                used_ids.insert(id.to_owned());
+                self.ids.insert(kind, &old_id, old_id.clone());
+            }
+
+            result.push(row);
+        }
+
+        Ok(result)
+    }
+
+    /// Removes rows that have the same values in the key columns as an existing row.
+    async fn skip_existing_rows(
+        &mut self,
+        table: &Table,
+        key_columns: &[&str],
+        rows: Vec<Row>,
+    ) -> Result<Vec<Row>, QueryError> {
+        let mut existing = HashSet::new();
+
+        for (chunk_index, chunk) in rows.chunks(BATCH_SIZE).enumerate() {
+            let indices = self
+                .snapshots
+                .existing_rows(table.name, key_columns, &to_json(chunk))
+                .await?;
+            existing.extend(
+                indices
+                    .into_iter()
+                    .map(|index| chunk_index * BATCH_SIZE + index),
+            );
+        }
+
+        let (skipped, rows): (Vec<_>, Vec<_>) = rows
+            .into_iter()
+            .enumerate()
+            .partition(|(index, _)| existing.contains(index));
+
+        self.skipped_rows.insert(
+            table.name,
+            skipped.into_iter().map(|(_, row)| row).collect(),
+        );
+
+        Ok(rows.into_iter().map(|(_, row)| row).collect())
+    }
+
+    /// Subtracts what the skipped rows of another table added to the totals,
+    /// so that only the rows that were imported are added to the totals on this server.
+    ///
+    /// Totals that end up at zero are removed.
+    fn subtract_skipped_totals(&self, table: &Table, rows: &mut Vec<Row>) {
+        let (total_column, key_columns, skipped_amounts) = match table.name {
+            "total_highway_likes" => (
+                "likes",
+                &["account_id"][..],
+                self.skipped_amounts("likes", &["to_id"], |row| {
+                    // Likes given to highway segments have an online id of h followed by the construction id
+                    row.get("online_id")
+                        .and_then(Value::as_str)
+                        .is_some_and(|online_id| online_id.starts_with('h'))
+                        .then(|| get_i64(row, "likes_manual") + get_i64(row, "likes_auto"))
+                }),
+            ),
+            "total_highway_resources" => (
+                "num_resources",
+                &["construction_id", "resource_id"][..],
+                self.skipped_amounts(
+                    "devoted_highway_resources",
+                    &["construction_id", "resource_id"],
+                    |row| Some(get_i64(row, "num_resources")),
+                ),
+            ),
+            _ => return,
+        };
+
+        if skipped_amounts.is_empty() {
+            return;
+        }
+
+        rows.retain_mut(|row| {
+            let Some(skipped) = skipped_amounts.get(&row_key(row, key_columns)) else {
+                return true;
+            };
+
+            let total = get_i64(row, total_column) - skipped;
+            row.insert(total_column.into(), total.into());
+
+            total > 0
+        });
+    }
+
+    /// Returns the sum of the amounts of the skipped rows of the table, keyed by the values in the key columns.
+    fn skipped_amounts(
+        &self,
+        table_name: &str,
+        key_columns: &[&str],
+        get_amount: impl Fn(&Row) -> Option<i64>,
+    ) -> HashMap<String, i64> {
+        let mut amounts = HashMap::new();
+
+        for row in self.skipped_rows.get(table_name).into_iter().flatten() {
+            if let Some(amount) = get_amount(row) {
+                *amounts.entry(row_key(row, key_columns)).or_default() += amount;
+            }
+        }
+
+        amounts
+    }
+
+    async fn insert_rows(
+        &self,
+        table: &Table,
+        columns: &[String],
+        rows: &[Row],
+    ) -> Result<usize, QueryError> {
+        let mut imported = 0;
+
+        for chunk in rows.chunks(BATCH_SIZE) {
+            imported += self
+                .snapshots
+                .insert_rows(table.name, columns, &to_json(chunk), table.on_conflict)
+                .await?;
+        }
+
+        Ok(imported)
+    }
+}
+
+fn to_json(value: &(impl serde::Serialize + ?Sized)) -> String {
+    serde_json::to_string(value).expect("Json values can always be serialized")
+}
+
+/// Returns the values of the columns in a form that can be compared with the values of other rows.
+fn row_key(row: &Row, columns: &[&str]) -> String {
+    to_json(
+        &columns
+            .iter()
+            .map(|column| row.get(*column))
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn get_i64(row: &Row, column: &str) -> i64 {
+    row.get(column).and_then(Value::as_i64).unwrap_or(0)
+}
+
+/// Returns None if the row doesn't contain what's needed to generate the id.
+fn generate_id(kind: IdKind, row: &Row) -> Option<String> {
+    Some(match kind {
+        IdKind::Account => generate_account_id(),
+        IdKind::Object => {
+            let object_type: ObjectType =
+                serde_json::from_value(row.get("object_type")?.clone()).ok()?;
+            generate_object_id(&object_type)
+        }
+        IdKind::Mission => generate_mission_id(),
+        IdKind::Road => generate_road_id(),
+        IdKind::WastedBaggage => generate_wasted_baggage_id(),
+        IdKind::Comment | IdKind::MissionBaggage => {
+            unreachable!("Serial ids are generated by the database")
+        }
+    })
+}
@@ -0,0 +1,323 @@
+//! Exporting and importing the shared world to and from a single archive, for example to move a community to another server.
+//!
+//! The archive is a zip file containing `manifest.json` and one json file per table in `tables/`.
+//! Frontend accounts and rankings are not included since they're specific to the server they were created on.
+
+mod export;
+mod import;
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+pub use export::export_world;
+pub use import::import_world;
+
+/// Increment this if the layout of the archive changes.
+pub const FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_PATH: &str = "manifest.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format_version: u32,
+    /// The latest database migration that was applied on the server the world was exported from.
+    /// The world can only be imported on a server with the same migrations applied.
+    pub schema_version: String,
+    pub exported_at: NaiveDateTime,
+    /// Number of exported rows per table
+    pub tables: BTreeMap<String, usize>,
+}
+
+/// What to do when an imported row has the same id as an existing row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ConflictStrategy {
+    /// Don't import the row or anything that depends on it
+    Skip,
+    /// Generate a new id for the imported row
+    Remap,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum IdKind {
+    Account,
+    Object,
+    Comment,
+    Mission,
+    MissionBaggage,
+    Road,
+    WastedBaggage,
+}
+
+enum PrimaryKey {
+    /// An id generated by the server. It's kept as is when imported unless it conflicts with an existing row.
+    Text(IdKind),
+    /// An id generated by the database. A new id is always assigned when imported.
+    /// The kind is only set if other tables reference the id.
+    Serial(Option<IdKind>),
+    /// An id generated by the database that nothing references, with the row identified by the values of these columns instead.
+    /// Rows that match an existing row are skipped, so that importing the same world twice doesn't add them again.
+    Natural(&'static [&'static str]),
+    /// The table is identified by a reference to another table.
+    None,
+}
+
+enum Reference {
+    /// The row is not imported if the referenced row wasn't imported.
+    Required(&'static str, IdKind),
+    /// The value is only changed if the referenced row was imported with a new id.
+    Soft(&'static str, IdKind),
+}
+
+struct Table {
+    name: &'static str,
+    primary_key: PrimaryKey,
+    references: &'static [Reference],
+    on_conflict: &'static str,
+}
+
+const DO_NOTHING: &str = "ON CONFLICT DO NOTHING";
+
+/// All exported tables, in the order they need to be imported in.
+const TABLES: &[Table] = &[
+    Table {
+        name: "accounts",
+        primary_key: PrimaryKey::Text(IdKind::Account),
+        references: &[],
+        on_conflict: "",
+    },
+    Table {
+        name: "player_profiles",
+        primary_key: PrimaryKey::None,
+        references: &[Reference::Required("account_id", IdKind::Account)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "account_histories",
+        primary_key: PrimaryKey::Natural(&["account_id", "encountered_id"]),
+        references: &[
+            Reference::Required("account_id", IdKind::Account),
+            Reference::Required("encountered_id", IdKind::Account),
+        ],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "account_strand_contracts",
+        primary_key: PrimaryKey::Serial(None),
+        references: &[
+            Reference::Required("owner_account_id", IdKind::Account),
+            Reference::Required("contract_account_id", IdKind::Account),
+        ],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "qpid_objects",
+        primary_key: PrimaryKey::Text(IdKind::Object),
+        references: &[Reference::Required("creator_id", IdKind::Account)],
+        on_conflict: "",
+    },
+    Table {
+        name: "qpid_object_bridge_infos",
+        primary_key: PrimaryKey::None,
+        references: &[Reference::Required("object_id", IdKind::Object)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "qpid_object_customize_infos",
+        primary_key: PrimaryKey::None,
+        references: &[Reference::Required("object_id", IdKind::Object)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "qpid_object_extra_infos",
+        primary_key: PrimaryKey::None,
+        references: &[Reference::Required("object_id", IdKind::Object)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "qpid_object_parking_infos",
+        primary_key: PrimaryKey::None,
+        references: &[Reference::Required("object_id", IdKind::Object)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "qpid_object_rope_infos",
+        primary_key: PrimaryKey::None,
+        references: &[Reference::Required("object_id", IdKind::Object)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "qpid_object_stone_infos",
+        primary_key: PrimaryKey::None,
+        references: &[Reference::Required("object_id", IdKind::Object)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "qpid_object_vehicle_infos",
+        primary_key: PrimaryKey::None,
+        references: &[Reference::Required("object_id", IdKind::Object)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "qpid_object_baggages",
+        primary_key: PrimaryKey::Serial(None),
+        references: &[
+            Reference::Required("object_id", IdKind::Object),
+            Reference::Required("creator", IdKind::Account),
+        ],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "qpid_object_comments",
+        primary_key: PrimaryKey::Serial(Some(IdKind::Comment)),
+        references: &[
+            Reference::Required("object_id", IdKind::Object),
+            Reference::Required("writer", IdKind::Account),
+            Reference::Soft("reference_object", IdKind::Object),
+        ],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "qpid_object_comment_phrases",
+        primary_key: PrimaryKey::Serial(None),
+        references: &[Reference::Required("comment_id", IdKind::Comment)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "qpid_object_construction_materials",
+        primary_key: PrimaryKey::Serial(None),
+        references: &[
+            Reference::Required("object_id", IdKind::Object),
+            Reference::Required("contributor_id", IdKind::Account),
+        ],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "qpid_object_recycle_materials",
+        primary_key: PrimaryKey::Serial(None),
+        references: &[
+            Reference::Required("object_id", IdKind::Object),
+            Reference::Required("contributor_id", IdKind::Account),
+        ],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "qpid_object_tags",
+        primary_key: PrimaryKey::Serial(None),
+        references: &[Reference::Required("object_id", IdKind::Object)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "missions",
+        primary_key: PrimaryKey::Text(IdKind::Mission),
+        references: &[
+            Reference::Required("creator_id", IdKind::Account),
+            Reference::Required("worker_id", IdKind::Account),
+        ],
+        on_conflict: "",
+    },
+    Table {
+        name: "mission_supply_infos",
+        primary_key: PrimaryKey::None,
+        references: &[Reference::Required("mission_id", IdKind::Mission)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "mission_dynamic_mission_infos",
+        primary_key: PrimaryKey::None,
+        references: &[Reference::Required("mission_id", IdKind::Mission)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "mission_catapult_shell_infos",
+        primary_key: PrimaryKey::None,
+        references: &[Reference::Required("mission_id", IdKind::Mission)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "mission_dynamic_location_infos",
+        primary_key: PrimaryKey::Serial(None),
+        references: &[Reference::Required("mission_id", IdKind::Mission)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "mission_baggages",
+        primary_key: PrimaryKey::Serial(Some(IdKind::MissionBaggage)),
+        references: &[Reference::Required("mission_id", IdKind::Mission)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "mission_baggage_ammo_infos",
+        primary_key: PrimaryKey::None,
+        references: &[Reference::Required("baggage_id", IdKind::MissionBaggage)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "mission_relations",
+        primary_key: PrimaryKey::Serial(None),
+        references: &[
+            Reference::Required("mission_id", IdKind::Mission),
+            Reference::Required("account_id", IdKind::Account),
+        ],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "roads",
+        primary_key: PrimaryKey::Text(IdKind::Road),
+        references: &[Reference::Required("creator_id", IdKind::Account)],
+        on_conflict: "",
+    },
+    Table {
+        name: "road_data",
+        primary_key: PrimaryKey::None,
+        references: &[Reference::Required("road_id", IdKind::Road)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "road_via_qpids",
+        primary_key: PrimaryKey::None,
+        references: &[Reference::Required("road_id", IdKind::Road)],
+        on_conflict: DO_NOTHING,
+    },
+    Table {
+        name: "wasted_baggages",
+        primary_key: PrimaryKey::Text(IdKind::WastedBaggage),
+        references: &[Reference::Required("creator_id", IdKind::Account)],
+        on_conflict: "",
+    },
+    Table {
+        name: "likes",
+        primary_key: PrimaryKey::Natural(&["time", "from_id", "to_id", "online_id", "type"]),
+        references: &[
+            Reference::Required("from_id", IdKind::Account),
+            Reference::Required("to_id", IdKind::Account),
+            Reference::Soft("online_id", IdKind::Object),
+        ],
+        on_conflict: DO_NOTHING,
+    },
+    // Only the likes that were imported are added to the totals, see Importer::subtract_skipped_totals
+    Table {
+        name: "total_highway_likes",
+        primary_key: PrimaryKey::None,
+        references: &[Reference::Required("account_id", IdKind::Account)],
+        on_conflict: "ON CONFLICT (account_id) DO UPDATE SET likes = total_highway_likes.likes + EXCLUDED.likes",
+    },
+    Table {
+        name: "devoted_highway_resources",
+        primary_key: PrimaryKey::Natural(&["account_id", "construction_id", "time", "resource_id"]),
+        references: &[Reference::Required("account_id", IdKind::Account)],
+        on_conflict: DO_NOTHING,
+    },
+    // Only the devoted resources that were imported are added to the totals, see Importer::subtract_skipped_totals
+    Table {
+        name: "total_highway_resources",
+        primary_key: PrimaryKey::Serial(None),
+        references: &[],
+        on_conflict: "ON CONFLICT (construction_id, resource_id) DO UPDATE SET num_resources = total_highway_resources.num_resources + EXCLUDED.num_resources",
+    },
+];
+
+fn table_path(table: &str) -> String {
+    format!("tables/{table}.json")
+}